@@ -0,0 +1,185 @@
+//! Append-only audit log of admin actions, plus the global settings history
+//!
+//! Entries are written from the mutating admin endpoints. The write path is a
+//! single stable-map insert keyed by a monotonically increasing sequence number,
+//! so recording an action never scans existing entries.
+
+use crate::storage;
+use crate::types::*;
+use candid::Principal;
+
+/// Maximum number of entries returned by a single list call
+pub const MAX_AUDIT_PAGE_SIZE: u32 = 500;
+
+/// Target and details are truncated (in UTF-8 bytes) so an entry always fits
+/// its 1 KiB stable-storage bound
+const MAX_TARGET_BYTES: usize = 256;
+const MAX_DETAILS_BYTES: usize = 512;
+
+/// Number of settings changes retained; older ones are dropped
+const MAX_SETTINGS_HISTORY: u64 = 200;
+
+/// Settings summaries are truncated (in UTF-8 bytes) to fit the 2 KiB bound
+const MAX_SUMMARY_BYTES: usize = 1536;
+
+/// Cut `s` to at most `max_bytes`, backing off to a char boundary
+fn truncate_to_bytes(mut s: String, max_bytes: usize) -> String {
+    if s.len() > max_bytes {
+        let mut end = max_bytes;
+        while !s.is_char_boundary(end) {
+            end -= 1;
+        }
+        s.truncate(end);
+    }
+    s
+}
+
+/// Record an admin action
+pub fn record(actor: Principal, action: AuditAction, target: String, details: String) {
+    let entry = AuditEntry {
+        seq: 0, // assigned by storage
+        actor,
+        action,
+        target: truncate_to_bytes(target, MAX_TARGET_BYTES),
+        timestamp: ic_cdk::api::time(),
+        details: truncate_to_bytes(details, MAX_DETAILS_BYTES),
+    };
+    // The action itself already happened; losing its log line beats trapping
+    if let Err(ApiError::InvalidInput(msg)) = storage::try_append_audit_entry(entry) {
+        ic_cdk::println!("Warning: dropping audit entry: {}", msg);
+    }
+}
+
+/// List audit entries newest first
+pub fn list(offset: u32, limit: u32) -> Vec<AuditEntry> {
+    let limit = limit.min(MAX_AUDIT_PAGE_SIZE);
+    storage::list_audit_entries(offset as usize, limit as usize)
+}
+
+/// Record a settings change made at `now` if anything actually changed
+pub fn record_settings_change(actor: Principal, old: &GlobalSettings, new: &GlobalSettings, now: u64) {
+    let summary = diff_settings(old, new);
+    if summary.is_empty() {
+        return;
+    }
+    let summary = truncate_to_bytes(summary, MAX_SUMMARY_BYTES);
+    let change = SettingsChange {
+        seq: 0, // assigned by storage
+        actor,
+        timestamp: now,
+        summary,
+    };
+    if let Err(ApiError::InvalidInput(msg)) = storage::try_append_settings_change(change, MAX_SETTINGS_HISTORY) {
+        ic_cdk::println!("Warning: dropping settings change: {}", msg);
+    }
+}
+
+/// List settings changes newest first
+pub fn settings_history(limit: u32) -> Vec<SettingsChange> {
+    storage::list_settings_changes(limit.min(MAX_SETTINGS_HISTORY as u32) as usize)
+}
+
+/// Human-readable "field: old -> new" summary of changed settings
+fn diff_settings(old: &GlobalSettings, new: &GlobalSettings) -> String {
+    let mut changes: Vec<String> = Vec::new();
+    
+    macro_rules! diff {
+        ($field:ident) => {
+            if old.$field != new.$field {
+                changes.push(format!("{}: {:?} -> {:?}", stringify!($field), old.$field, new.$field));
+            }
+        };
+    }
+    
+    diff!(forward_window_months);
+    diff!(claims_paused);
+    diff!(default_event_duration_minutes);
+    diff!(org_name);
+    diff!(org_tagline);
+    diff!(org_logo_url);
+    diff!(max_concurrent_sessions);
+    diff!(enforce_concurrency_cap);
+    diff!(validate_one_off_host);
+    diff!(require_link);
+    diff!(auto_cancel_past_unclaimed);
+    diff!(auto_cancel_grace_hours);
+    diff!(business_hours);
+    diff!(min_duration_minutes);
+    diff!(max_duration_minutes);
+    diff!(namespaced_instance_ids);
+    diff!(notify_admins_on_join);
+    diff!(org_default_notification_settings);
+    diff!(week_starts_on);
+    diff!(coverage_alert_threshold_pct);
+    diff!(min_claim_lead_minutes);
+    diff!(always_email_critical_removals);
+    diff!(org_timezone);
+    diff!(notification_templates);
+    
+    changes.join("; ")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn diff_lists_only_changed_fields() {
+        let old = GlobalSettings::default();
+        let new = GlobalSettings { claims_paused: true, forward_window_months: 6, ..old.clone() };
+
+        assert_eq!(diff_settings(&old, &old), "");
+        let summary = diff_settings(&old, &new);
+        assert!(summary.contains("claims_paused: false -> true"));
+        assert!(summary.contains("forward_window_months: "));
+        assert!(!summary.contains("org_name"));
+    }
+
+    #[test]
+    fn history_is_ordered_newest_first_with_actors() {
+        let alice = Principal::from_slice(&[1]);
+        let bob = Principal::from_slice(&[2]);
+        let initial = GlobalSettings::default();
+        let paused = GlobalSettings { claims_paused: true, ..initial.clone() };
+        let widened = GlobalSettings { forward_window_months: initial.forward_window_months + 3, ..paused.clone() };
+        record_settings_change(alice, &initial, &paused, 100);
+        record_settings_change(bob, &paused, &widened, 200);
+        // A no-op save leaves no entry
+        record_settings_change(bob, &widened, &widened, 300);
+
+        let history = settings_history(10);
+        assert_eq!(history.len(), 2);
+        assert_eq!((history[0].seq, history[0].actor, history[0].timestamp), (1, bob, 200));
+        assert_eq!(
+            history[0].summary,
+            format!("forward_window_months: {} -> {}", initial.forward_window_months, widened.forward_window_months)
+        );
+        assert_eq!((history[1].seq, history[1].actor, history[1].timestamp), (0, alice, 100));
+        assert_eq!(history[1].summary, "claims_paused: false -> true");
+    }
+
+    #[test]
+    fn history_drops_the_oldest_changes_past_the_cap() {
+        let admin = Principal::from_slice(&[1]);
+        let mut previous = GlobalSettings::default();
+        for i in 0..MAX_SETTINGS_HISTORY + 5 {
+            let next = GlobalSettings { forward_window_months: previous.forward_window_months % 12 + 1, ..previous.clone() };
+            record_settings_change(admin, &previous, &next, i);
+            previous = next;
+        }
+
+        let stored = storage::list_settings_changes(usize::MAX);
+        assert_eq!(stored.len() as u64, MAX_SETTINGS_HISTORY);
+        assert_eq!(stored.first().map(|c| c.seq), Some(MAX_SETTINGS_HISTORY + 4));
+        assert_eq!(stored.last().map(|c| c.seq), Some(5));
+        assert_eq!(settings_history(u32::MAX).len() as u64, MAX_SETTINGS_HISTORY);
+    }
+
+    #[test]
+    fn truncation_stops_on_a_char_boundary() {
+        // 'é' is two bytes, so a 5-byte cut would split the third one
+        assert_eq!(truncate_to_bytes("éééé".to_string(), 5), "éé");
+        assert_eq!(truncate_to_bytes("short".to_string(), 512), "short");
+        assert!(truncate_to_bytes("語".repeat(400), MAX_DETAILS_BYTES).len() <= MAX_DETAILS_BYTES);
+    }
+}
@@ -37,6 +37,19 @@ pub fn touch_last_active(principal: &Principal) {
     }
 }
 
+/// Check if caller may use read-only endpoints: an active user, or an observer
+/// (returned as None). Observers hold no other rights, and a disabled user
+/// stays locked out even if they are also listed as an observer.
+pub fn require_viewer() -> ApiResult<Option<User>> {
+    let principal = require_authenticated()?;
+    match storage::get_user(&principal) {
+        Some(user) if user.status == UserStatus::Active => Ok(Some(user)),
+        Some(_) => Err(ApiError::Unauthorized),
+        None if storage::is_observer(&principal) => Ok(None),
+        None => Err(ApiError::Unauthorized),
+    }
+}
+
 /// Check if caller is an admin
 pub fn require_admin() -> ApiResult<User> {
     let user = require_authorized()?;
@@ -46,6 +59,26 @@ pub fn require_admin() -> ApiResult<User> {
     Ok(user)
 }
 
+/// Whether a user's role grants a capability
+/// Capabilities are derived from the role rather than stored, so existing
+/// admins automatically hold every capability.
+pub fn has_capability(user: &User, capability: Capability) -> bool {
+    match user.role {
+        Role::Admin => true,
+        Role::Coordinator => capability == Capability::ManageSeries,
+        Role::User => false,
+    }
+}
+
+/// Check if caller is an active user holding the given capability
+pub fn require_capability(capability: Capability) -> ApiResult<User> {
+    let user = require_authorized()?;
+    if !has_capability(&user, capability) {
+        return Err(ApiError::Unauthorized);
+    }
+    Ok(user)
+}
+
 
 /// Check if a user is available for assignment (not disabled, not on OOO)
 pub fn can_be_assigned_host(user: &User, event_start: u64, event_end: u64) -> bool {
@@ -53,15 +86,34 @@ pub fn can_be_assigned_host(user: &User, event_start: u64, event_end: u64) -> bo
         return false;
     }
     
-    // Check OOO blocks
-    for ooo in &user.out_of_office {
-        // If event overlaps with OOO block, user is unavailable
-        if event_start < ooo.end_utc && event_end > ooo.start_utc {
-            return false;
-        }
-    }
+    !is_out_of_office(user, event_start, event_end)
+}
+
+/// Whether any of the user's OOO blocks overlaps the given window
+pub fn is_out_of_office(user: &User, start: u64, end: u64) -> bool {
+    user.out_of_office.iter().any(|ooo| start < ooo.end_utc && end > ooo.start_utc)
+}
+
+/// Summarize a user's role, capabilities and claim eligibility at `now`
+pub fn describe_access(user: &User, now: u64) -> UserAccess {
+    let active = user.status == UserStatus::Active;
+    let capabilities = [Capability::ManageUsers, Capability::ManageSeries]
+        .into_iter()
+        .filter(|c| active && has_capability(user, *c))
+        .collect();
+    let current_out_of_office = user.out_of_office.iter()
+        .find(|ooo| ooo.start_utc <= now && now < ooo.end_utc)
+        .cloned();
+    let claims_paused = storage::get_settings().claims_paused && user.role != Role::Admin;
     
-    true
+    UserAccess {
+        principal: user.principal,
+        role: user.role,
+        status: user.status,
+        capabilities,
+        current_out_of_office,
+        can_claim_now: active && !claims_paused,
+    }
 }
 
 /// Check if a principal is an admin (for admin override checks)
@@ -72,21 +124,72 @@ pub fn is_admin(principal: &Principal) -> bool {
     }
 }
 
-/// Initialize the first admin (called during canister init)
-pub fn initialize_admin(principal: Principal, name: String, email: String) {
+/// Build a freshly authorized, active user with default settings
+pub fn new_user(principal: Principal, name: String, email: String, role: Role) -> User {
     let now = ic_cdk::api::time();
-    let user = User {
+    User {
         principal,
         name,
         email,
-        role: Role::Admin,
+        role,
         status: UserStatus::Active,
         out_of_office: vec![],
-        notification_settings: NotificationSettings::default(),
+        availability: vec![],
+        notification_settings: default_notification_settings(),
         last_active: now,
         sessions_hosted_count: 0,
         created_at: now,
         updated_at: now,
+        locale: None,
+    }
+}
+
+/// Notification settings for a new user: the org default if set, else the built-in one
+pub fn default_notification_settings() -> NotificationSettings {
+    storage::get_settings()
+        .org_default_notification_settings
+        .unwrap_or_default()
+}
+
+/// Failed invite redemptions allowed per principal within one window
+const MAX_FAILED_REDEEMS: u32 = 5;
+
+/// Length of the redemption rate-limit window (1 hour in nanoseconds)
+const REDEEM_WINDOW_NANOS: u64 = 60 * 60 * 1_000_000_000;
+
+/// Reject the caller if they've exhausted their failed redemption attempts
+pub fn check_redeem_rate_limit(principal: &Principal) -> ApiResult<()> {
+    let now = ic_cdk::api::time();
+    if let Some(attempts) = storage::get_redeem_attempts(principal) {
+        let window_open = now < attempts.window_start + REDEEM_WINDOW_NANOS;
+        if window_open && attempts.failed_count >= MAX_FAILED_REDEEMS {
+            return Err(ApiError::Conflict(
+                "Too many invalid invite code attempts. Please try again later.".to_string()
+            ));
+        }
+    }
+    Ok(())
+}
+
+/// Count a failed redemption, starting a new window if the previous one elapsed
+pub fn record_failed_redeem(principal: &Principal) {
+    let now = ic_cdk::api::time();
+    let attempts = match storage::get_redeem_attempts(principal) {
+        Some(a) if now < a.window_start + REDEEM_WINDOW_NANOS => RedeemAttempts {
+            failed_count: a.failed_count.saturating_add(1),
+            window_start: a.window_start,
+        },
+        _ => RedeemAttempts { failed_count: 1, window_start: now },
     };
-    storage::insert_user(user);
+    storage::insert_redeem_attempts(principal, attempts);
+}
+
+/// Clear the failure counter after a successful redemption
+pub fn reset_redeem_attempts(principal: &Principal) {
+    storage::delete_redeem_attempts(principal);
+}
+
+/// Initialize the first admin (called during canister init)
+pub fn initialize_admin(principal: Principal, name: String, email: String) {
+    storage::insert_user(new_user(principal, name, email, Role::Admin));
 }
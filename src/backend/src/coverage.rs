@@ -4,40 +4,68 @@
 //! - Assigning host to a series instance creates/updates an override
 //! - Assigning host to a one-off instance updates the instance directly
 //! - OOO and disabled users cannot be assigned (except admin override)
+//! - Host suggestions rank users whose recurring availability covers a slot
+//! - Hosts can list their own double-bookings
 
 use crate::auth;
 use crate::notifications;
+use crate::datetime;
 use crate::recurrence;
 use crate::storage;
 use crate::types::*;
+use crate::validation;
 use candid::Principal;
 
-/// Assign a host to an event instance
-/// 
-/// For series instances: Creates or updates an InstanceOverride
-/// For one-off instances: Updates the EventInstance directly
-pub fn assign_host(
+/// Reject non-admin claims on a series whose claims are paused
+fn check_series_claims_paused(series_id: Option<[u8; 16]>, caller: &Principal) -> ApiResult<()> {
+    let paused = series_id
+        .and_then(|sid| storage::get_series(&sid))
+        .map(|s| s.claims_paused)
+        .unwrap_or(false);
+    if paused && !auth::is_admin(caller) {
+        return Err(ApiError::Conflict("Claims are paused for this series".to_string()));
+    }
+    Ok(())
+}
+
+/// Reject non-admin claims on sessions starting sooner than `min_claim_lead_minutes`
+fn check_claim_lead_time(settings: &GlobalSettings, event_start: u64, now: u64, caller: &Principal) -> ApiResult<()> {
+    let Some(lead) = settings.min_claim_lead_minutes else {
+        return Ok(());
+    };
+    if auth::is_admin(caller) || event_start.saturating_sub(now) >= lead as u64 * 60 * 1_000_000_000 {
+        return Ok(());
+    }
+    Err(ApiError::Conflict(format!(
+        "Sessions must be claimed at least {} minutes before they start", lead
+    )))
+}
+
+/// Checks run before assigning a host; returns the host and the event's times
+fn check_assignable(
     series_id: Option<[u8; 16]>,
     occurrence_start: Option<u64>,
-    instance_id: [u8; 16],
-    host_principal: Principal,
-    caller: Principal,
+    instance_id: &[u8; 16],
+    host_principal: &Principal,
+    caller: &Principal,
     admin_override: bool,
-) -> ApiResult<EventInstance> {
-    let now = ic_cdk::api::time();
+    now: u64,
+) -> ApiResult<(User, u64, u64)> {
     let settings = storage::get_settings();
     
     // Check if claims are paused (admins can still assign)
-    if settings.claims_paused && !auth::is_admin(&caller) {
+    if settings.claims_paused && !auth::is_admin(caller) {
         return Err(ApiError::Conflict("Claims are currently paused".to_string()));
     }
+    check_series_claims_paused(series_id, caller)?;
     
     // Validate host exists and can be assigned
-    let mut host_user = storage::get_user(&host_principal)
+    let host_user = storage::get_user(host_principal)
         .ok_or(ApiError::NotFound)?;
     
     // Get event timing for OOO check
-    let (event_start, event_end) = get_event_timing(series_id, occurrence_start, &instance_id)?;
+    let (event_start, event_end) = get_event_timing(series_id, occurrence_start, instance_id)?;
+    check_claim_lead_time(&settings, event_start, now, caller)?;
     
     if !admin_override && !auth::can_be_assigned_host(&host_user, event_start, event_end) {
         return Err(ApiError::Conflict(
@@ -45,6 +73,56 @@ pub fn assign_host(
         ));
     }
     
+    Ok((host_user, event_start, event_end))
+}
+
+/// Whether `user` could claim the session themselves right now, and if not, why
+/// Runs the same checks as `assign_host` without assigning.
+pub fn claim_eligibility(
+    series_id: Option<[u8; 16]>,
+    occurrence_start: Option<u64>,
+    instance_id: [u8; 16],
+    user: &User,
+) -> ClaimEligibility {
+    let now = ic_cdk::api::time();
+    let admin_override = user.role == Role::Admin;
+    
+    match check_assignable(series_id, occurrence_start, &instance_id, &user.principal, &user.principal, admin_override, now) {
+        Ok(_) => ClaimEligibility { allowed: true, reason: None },
+        Err(err) => {
+            let reason = match err {
+                ApiError::Conflict(msg) | ApiError::InvalidInput(msg) | ApiError::InternalError(msg) => msg,
+                ApiError::NotFound => "Session not found".to_string(),
+                ApiError::Unauthorized => "Not authorized".to_string(),
+            };
+            ClaimEligibility { allowed: false, reason: Some(reason) }
+        }
+    }
+}
+
+/// Assign a host to an event instance
+/// 
+/// For series instances: Creates or updates an InstanceOverride
+/// For one-off instances: Updates the EventInstance directly
+pub fn assign_host(
+    series_id: Option<[u8; 16]>,
+    occurrence_start: Option<u64>,
+    instance_id: [u8; 16],
+    host_principal: Principal,
+    caller: Principal,
+    admin_override: bool,
+) -> ApiResult<EventInstance> {
+    let now = ic_cdk::api::time();
+    let (mut host_user, event_start, event_end) = check_assignable(
+        series_id,
+        occurrence_start,
+        &instance_id,
+        &host_principal,
+        &caller,
+        admin_override,
+        now,
+    )?;
+    
     // Perform assignment
     if let Some(sid) = series_id {
         // Series instance: create/update override
@@ -55,28 +133,20 @@ pub fn assign_host(
         let mut ovr = storage::get_override(&OverrideKey {
             series_id: sid,
             occurrence_start_utc: occ_start,
-        }).unwrap_or(InstanceOverride {
-            series_id: sid,
-            occurrence_start_utc: occ_start,
-            start_utc: None,
-            end_utc: None,
-            notes: None,
-            host_principal: None,
-            host_cleared: false,
-            cancelled: false,
-            updated_at: now,
-            updated_by: caller,
-        });
+        }).unwrap_or_else(|| InstanceOverride::new(sid, occ_start, caller, now));
         
         ovr.host_principal = Some(host_principal);
         ovr.host_cleared = false;
+        ovr.assigned_by = Some(caller);
+        ovr.assigned_at = Some(now);
         ovr.updated_at = now;
         ovr.updated_by = caller;
         
-        storage::insert_override(ovr);
+        storage::try_insert_override(ovr)?;
         
         // Create notification job
-        notifications::create_host_assigned_notification(&host_user, &instance_id, event_start, event_end);
+        let series = storage::get_series(&sid);
+        notifications::create_host_assigned_notification(&host_user, series.as_ref(), &instance_id, event_start, event_end);
         
     } else {
         // One-off instance: update directly
@@ -84,10 +154,12 @@ pub fn assign_host(
             .ok_or(ApiError::NotFound)?;
         
         inst.host_principal = Some(host_principal);
-        storage::insert_instance(inst);
+        inst.assigned_by = Some(caller);
+        inst.assigned_at = Some(now);
+        storage::try_insert_instance(inst)?;
         
         // Create notification job
-        notifications::create_host_assigned_notification(&host_user, &instance_id, event_start, event_end);
+        notifications::create_host_assigned_notification(&host_user, None, &instance_id, event_start, event_end);
     }
     
     // Increment sessions_hosted_count for the assigned host
@@ -114,6 +186,7 @@ pub fn unassign_host(
     if settings.claims_paused && !auth::is_admin(&caller) {
         return Err(ApiError::Conflict("Claims are currently paused".to_string()));
     }
+    check_series_claims_paused(series_id, &caller)?;
     
     // Get previous host for notification
     let (event_start, event_end) = get_event_timing(series_id, occurrence_start, &instance_id)?;
@@ -129,25 +202,16 @@ pub fn unassign_host(
         let mut ovr = storage::get_override(&OverrideKey {
             series_id: sid,
             occurrence_start_utc: occ_start,
-        }).unwrap_or(InstanceOverride {
-            series_id: sid,
-            occurrence_start_utc: occ_start,
-            start_utc: None,
-            end_utc: None,
-            notes: None,
-            host_principal: None,
-            host_cleared: false,
-            cancelled: false,
-            updated_at: now,
-            updated_by: caller,
-        });
+        }).unwrap_or_else(|| InstanceOverride::new(sid, occ_start, caller, now));
         
         ovr.host_principal = None;
         ovr.host_cleared = true;
+        ovr.assigned_by = None;
+        ovr.assigned_at = None;
         ovr.updated_at = now;
         ovr.updated_by = caller;
         
-        storage::insert_override(ovr);
+        storage::try_insert_override(ovr)?;
         
     } else {
         // One-off instance: update directly
@@ -155,13 +219,15 @@ pub fn unassign_host(
             .ok_or(ApiError::NotFound)?;
         
         inst.host_principal = None;
-        storage::insert_instance(inst);
+        inst.assigned_by = None;
+        inst.assigned_at = None;
+        storage::try_insert_instance(inst)?;
     }
     
     // Create notification for removed host
     if let Some(host_principal) = previous_host {
         if let Some(host_user) = storage::get_user(&host_principal) {
-            notifications::create_host_removed_notification(&host_user, &instance_id, event_start, event_end);
+            notifications::create_host_removed_notification(&host_user, &instance_id, series_id, event_start, event_end, true);
         }
     }
     
@@ -170,6 +236,88 @@ pub fn unassign_host(
 }
 
 
+/// Suggest hosts for a time slot
+///
+/// Returns active users whose recurring availability fully covers the slot,
+/// who are not out-of-office, and who aren't already hosting an overlapping
+/// session. Ranked by fewest sessions hosted so load is spread evenly.
+pub fn suggest_hosts(slot_start: u64, slot_end: u64) -> Vec<User> {
+    // Pull events starting up to a day before the slot so long sessions that
+    // began earlier are still considered for overlap
+    let lookback = 86400 * 1_000_000_000;
+    let events = recurrence::materialize_events(slot_start.saturating_sub(lookback), slot_end);
+    
+    let mut candidates: Vec<User> = storage::list_all_users()
+        .into_iter()
+        .filter(|u| availability_covers(u, slot_start, slot_end))
+        .filter(|u| auth::can_be_assigned_host(u, slot_start, slot_end))
+        .filter(|u| !events.iter().any(|e| {
+            e.host_principal == Some(u.principal) && e.start_utc < slot_end && e.end_utc > slot_start
+        }))
+        .collect();
+    
+    candidates.sort_by(|a, b| {
+        a.sessions_hosted_count.cmp(&b.sessions_hosted_count)
+            .then_with(|| a.name.cmp(&b.name))
+    });
+    candidates
+}
+
+/// Users who could host a given session, by name. With `include_ineligible`
+/// (admins), everyone is returned and those needing an override are flagged.
+pub fn eligible_hosts(
+    series_id: Option<[u8; 16]>,
+    occurrence_start: Option<u64>,
+    instance_id: &[u8; 16],
+    include_ineligible: bool,
+) -> ApiResult<Vec<EligibleHost>> {
+    let (event_start, event_end) = get_event_timing(series_id, occurrence_start, instance_id)?;
+    
+    let mut hosts: Vec<EligibleHost> = storage::list_all_users()
+        .into_iter()
+        .map(|u| EligibleHost {
+            requires_override: !auth::can_be_assigned_host(&u, event_start, event_end),
+            principal: u.principal,
+            name: u.name,
+            role: u.role,
+        })
+        .filter(|h| include_ineligible || !h.requires_override)
+        .collect();
+    hosts.sort_by(|a, b| a.name.cmp(&b.name));
+    Ok(hosts)
+}
+
+/// Active users with an out-of-office block overlapping the window, by name
+pub fn unavailable_hosts(window_start: u64, window_end: u64) -> Vec<User> {
+    let mut users: Vec<User> = storage::list_all_users()
+        .into_iter()
+        .filter(|u| u.status == UserStatus::Active)
+        .filter(|u| auth::is_out_of_office(u, window_start, window_end))
+        .collect();
+    users.sort_by(|a, b| a.name.cmp(&b.name));
+    users
+}
+
+/// Check whether one of the user's recurring availability blocks covers the whole slot
+/// Slots spanning midnight UTC are never covered by a single block
+fn availability_covers(user: &User, slot_start: u64, slot_end: u64) -> bool {
+    let nanos_per_day = 86400 * 1_000_000_000;
+    let nanos_per_minute = 60 * 1_000_000_000;
+    let day_start = slot_start - slot_start % nanos_per_day;
+    if slot_end > day_start + nanos_per_day {
+        return false;
+    }
+    
+    let weekday = datetime::weekday_from_nanos(slot_start);
+    let start_minute = ((slot_start - day_start) / nanos_per_minute) as u32;
+    let end_minute = (slot_end - day_start).div_ceil(nanos_per_minute) as u32;
+    
+    user.availability.iter().any(|b| {
+        b.weekday as u32 == weekday && b.start_minute_utc <= start_minute && b.end_minute_utc >= end_minute
+    })
+}
+
+
 /// Helper: Get event timing (start, end) for OOO checks
 fn get_event_timing(
     series_id: Option<[u8; 16]>,
@@ -202,7 +350,7 @@ fn get_event_timing(
 }
 
 /// Helper: Get a single event instance (materialized or from storage)
-fn get_event_instance(
+pub fn get_event_instance(
     series_id: Option<[u8; 16]>,
     occurrence_start: Option<u64>,
     instance_id: &[u8; 16],
@@ -226,11 +374,15 @@ fn get_event_instance(
         
         let start_utc = ovr.as_ref().and_then(|o| o.start_utc).unwrap_or(occ_start);
         let end_utc = ovr.as_ref().and_then(|o| o.end_utc).unwrap_or(occ_start + duration_nanos);
-        let notes = ovr.as_ref().and_then(|o| o.notes.clone()).unwrap_or(series.notes.clone());
+        let notes = recurrence::occurrence_notes(&series, ovr.as_ref());
+        let title = ovr.as_ref().and_then(|o| o.title_override.clone()).unwrap_or(series.title.clone());
+        // Same host resolution as materialize_events
         let host_principal = if ovr.as_ref().map(|o| o.host_cleared).unwrap_or(false) {
             None
         } else {
-            ovr.as_ref().and_then(|o| o.host_principal)
+            ovr.as_ref()
+                .and_then(|o| o.host_principal)
+                .or(series.default_host)
         };
         
         Ok(EventInstance {
@@ -238,15 +390,614 @@ fn get_event_instance(
             series_id: Some(sid),
             start_utc,
             end_utc,
-            title: series.title,
+            title,
             notes,
             link: series.link,
             host_principal,
             status: EventStatus::Active,
             color: series.color,
             created_at: series.created_at,
+            occurrence_start: Some(occ_start),
+            assigned_by: ovr.as_ref().and_then(|o| o.assigned_by),
+            assigned_at: ovr.as_ref().and_then(|o| o.assigned_at),
+            created_by: Some(series.created_by),
+            public: series.public,
+            capacity: series.capacity,
+            rsvp_count: storage::rsvp_count(instance_id),
+            overridden_fields: recurrence::overridden_fields(ovr.as_ref()),
+            series_title: Some(series.title),
         })
     } else {
-        storage::get_instance(instance_id).ok_or(ApiError::NotFound)
+        storage::get_instance(instance_id)
+            .map(recurrence::with_rsvp_count)
+            .ok_or(ApiError::NotFound)
+    }
+}
+
+/// Assign `host_principal` to every upcoming occurrence of a series up to
+/// `window_end`. Occurrences the host can't take (disabled or out of office)
+/// are skipped and reported; existing assignments are overwritten.
+pub fn assign_host_to_series(
+    series_id: [u8; 16],
+    host_principal: Principal,
+    window_end: u64,
+    caller: Principal,
+) -> ApiResult<SeriesAssignmentResult> {
+    let now = ic_cdk::api::time();
+    let series = storage::get_series(&series_id)
+        .ok_or(ApiError::NotFound)?;
+    let mut host_user = storage::get_user(&host_principal)
+        .ok_or(ApiError::NotFound)?;
+    
+    let mut result = SeriesAssignmentResult { assigned: vec![], skipped: vec![] };
+    let namespace = recurrence::instance_namespace();
+    
+    for occ_start in recurrence::generate_occurrences(&series, now, window_end) {
+        let key = OverrideKey { series_id, occurrence_start_utc: occ_start };
+        let existing = storage::get_override(&key);
+        if existing.as_ref().map(|o| o.cancelled).unwrap_or(false) {
+            continue;
+        }
+        
+        let instance_id = recurrence::instance_id_in(namespace.as_ref(), &series_id, occ_start);
+        let mut event = get_event_instance(Some(series_id), Some(occ_start), &instance_id)?;
+        if !auth::can_be_assigned_host(&host_user, event.start_utc, event.end_utc) {
+            result.skipped.push(event);
+            continue;
+        }
+        
+        let mut ovr = existing.unwrap_or_else(|| InstanceOverride::new(series_id, occ_start, caller, now));
+        ovr.host_principal = Some(host_principal);
+        ovr.host_cleared = false;
+        ovr.assigned_by = Some(caller);
+        ovr.assigned_at = Some(now);
+        ovr.updated_at = now;
+        ovr.updated_by = caller;
+        storage::try_insert_override(ovr)?;
+        
+        notifications::create_host_assigned_notification(&host_user, Some(&series), &instance_id, event.start_utc, event.end_utc);
+        host_user.sessions_hosted_count = host_user.sessions_hosted_count.saturating_add(1);
+        
+        event.host_principal = Some(host_principal);
+        result.assigned.push(event);
+    }
+    
+    host_user.updated_at = now;
+    storage::update_user(host_user);
+    
+    Ok(result)
+}
+
+/// Remove `host_principal` from every upcoming session they hold within the
+/// forward window, notifying them of each removal. Series that use them as
+/// default host have the default cleared so later occurrences don't pick
+/// them up again. Returns the number of sessions cleared.
+pub fn unassign_host_everywhere(host_principal: Principal, caller: Principal) -> ApiResult<u32> {
+    let now = ic_cdk::api::time();
+    let settings = storage::get_settings();
+    let window_end = recurrence::calculate_window_end(now, settings.forward_window_months);
+    let host_user = storage::get_user(&host_principal);
+    
+    let mut removed: Vec<EventInstance> = Vec::new();
+    let upcoming = recurrence::materialize_events(now, window_end);
+    let namespace = recurrence::instance_namespace();
+    
+    // Series occurrences: clear via overrides, keyed by original occurrence start
+    for mut series in storage::list_all_series() {
+        for occ_start in recurrence::generate_occurrences(&series, now, window_end) {
+            let instance_id = recurrence::instance_id_in(namespace.as_ref(), &series.series_id, occ_start);
+            let event = match upcoming.iter().find(|e| e.instance_id == instance_id) {
+                Some(e) if e.host_principal == Some(host_principal) => e.clone(),
+                _ => continue,
+            };
+            
+            let key = OverrideKey { series_id: series.series_id, occurrence_start_utc: occ_start };
+            let mut ovr = storage::get_override(&key).unwrap_or_else(|| InstanceOverride::new(series.series_id, occ_start, caller, now));
+            ovr.host_principal = None;
+            ovr.host_cleared = true;
+            ovr.assigned_by = None;
+            ovr.assigned_at = None;
+            ovr.updated_at = now;
+            ovr.updated_by = caller;
+            storage::try_insert_override(ovr)?;
+            removed.push(event);
+        }
+        
+        if series.default_host == Some(host_principal) {
+            series.default_host = None;
+            storage::insert_series(series);
+        }
+    }
+    
+    // One-off instances: edit directly
+    for mut inst in storage::list_all_instances() {
+        if inst.host_principal == Some(host_principal) && inst.end_utc > now && inst.status == EventStatus::Active {
+            inst.host_principal = None;
+            inst.assigned_by = None;
+            inst.assigned_at = None;
+            storage::try_insert_instance(inst.clone())?;
+            removed.push(inst);
+        }
+    }
+    
+    if let Some(host_user) = host_user {
+        for event in &removed {
+            notifications::create_host_removed_notification(&host_user, &event.instance_id, event.series_id, event.start_utc, event.end_utc, true);
+        }
+    }
+    
+    Ok(removed.len() as u32)
+}
+
+/// End a series so no occurrence starts at or after `effective_date`, sending
+/// cancellation notices for hosted occurrences that are dropped.
+/// Returns the number of distinct hosts notified.
+pub fn end_series_at(series_id: [u8; 16], effective_date: u64) -> ApiResult<u32> {
+    let mut series = storage::get_series(&series_id)
+        .ok_or(ApiError::NotFound)?;
+    if series.end_date.is_some_and(|end| end <= effective_date) {
+        return Err(ApiError::InvalidInput("Series already ends before that date".to_string()));
+    }
+    let now = ic_cdk::api::time();
+    if effective_date < now {
+        return Err(ApiError::InvalidInput("End date cannot be in the past".to_string()));
+    }
+    
+    // Collect occurrences before the end date hides them
+    let settings = storage::get_settings();
+    let window_end = recurrence::calculate_window_end(now, settings.forward_window_months);
+    let dropped: Vec<EventInstance> = recurrence::materialize_events(effective_date, window_end)
+        .into_iter()
+        .filter(|e| e.series_id == Some(series_id))
+        .collect();
+    
+    series.end_date = Some(effective_date);
+    storage::try_insert_series(series)?;
+    
+    let dropped_ids: Vec<[u8; 16]> = dropped.iter().map(|e| e.instance_id).collect();
+    notifications::cancel_pending_for_instances(&dropped_ids);
+    
+    let mut notified: Vec<Principal> = Vec::new();
+    for event in dropped {
+        let host = match event.host_principal.and_then(|p| storage::get_user(&p)) {
+            Some(u) => u,
+            None => continue,
+        };
+        notifications::create_instance_cancelled_notification(
+            &host,
+            &event.instance_id,
+            event.series_id,
+            &event.title,
+            event.start_utc,
+            event.end_utc,
+        );
+        if !notified.contains(&host.principal) {
+            notified.push(host.principal);
+        }
+    }
+    
+    Ok(notified.len() as u32)
+}
+
+/// Move every session `from` hosts between now and `window_end` to `to`, and
+/// make `to` the default host of any series `from` was the default for.
+/// Sessions `to` can't take (out of office) stay with `from` and are reported
+/// as skipped; series occurrences are pinned to `from` so the default-host
+/// change doesn't hand them over anyway.
+pub fn transfer_host(
+    from: Principal,
+    to: Principal,
+    window_end: u64,
+    caller: Principal,
+) -> ApiResult<HostTransferResult> {
+    let now = ic_cdk::api::time();
+    if from == to {
+        return Err(ApiError::InvalidInput("Cannot transfer sessions to the same host".to_string()));
+    }
+    let from_user = storage::get_user(&from);
+    let mut to_user = storage::get_user(&to)
+        .ok_or(ApiError::NotFound)?;
+    if to_user.status != UserStatus::Active {
+        return Err(ApiError::Conflict("New host is disabled".to_string()));
+    }
+    
+    let mut result = HostTransferResult { transferred: vec![], skipped: vec![], series_reassigned: 0 };
+    let upcoming = recurrence::materialize_events(now, window_end);
+    let namespace = recurrence::instance_namespace();
+    
+    // Series occurrences: reassign via overrides, keyed by original occurrence start
+    for mut series in storage::list_all_series() {
+        for occ_start in recurrence::generate_occurrences(&series, now, window_end) {
+            let instance_id = recurrence::instance_id_in(namespace.as_ref(), &series.series_id, occ_start);
+            let mut event = match upcoming.iter().find(|e| e.instance_id == instance_id) {
+                Some(e) if e.host_principal == Some(from) => e.clone(),
+                _ => continue,
+            };
+            let available = auth::can_be_assigned_host(&to_user, event.start_utc, event.end_utc);
+            
+            let key = OverrideKey { series_id: series.series_id, occurrence_start_utc: occ_start };
+            let mut ovr = storage::get_override(&key).unwrap_or_else(|| InstanceOverride::new(series.series_id, occ_start, caller, now));
+            if available {
+                ovr.host_principal = Some(to);
+                ovr.assigned_by = Some(caller);
+                ovr.assigned_at = Some(now);
+            } else {
+                ovr.host_principal = Some(from);
+            }
+            ovr.host_cleared = false;
+            ovr.updated_at = now;
+            ovr.updated_by = caller;
+            storage::try_insert_override(ovr)?;
+            
+            if available {
+                event.host_principal = Some(to);
+                result.transferred.push(event);
+            } else {
+                result.skipped.push(event);
+            }
+        }
+        
+        if series.default_host == Some(from) {
+            series.default_host = Some(to);
+            storage::insert_series(series);
+            result.series_reassigned += 1;
+        }
+    }
+    
+    // One-off instances: edit directly
+    for mut inst in storage::list_all_instances() {
+        if inst.host_principal != Some(from) || inst.start_utc < now || inst.start_utc >= window_end || inst.status != EventStatus::Active {
+            continue;
+        }
+        if auth::can_be_assigned_host(&to_user, inst.start_utc, inst.end_utc) {
+            inst.host_principal = Some(to);
+            inst.assigned_by = Some(caller);
+            inst.assigned_at = Some(now);
+            storage::try_insert_instance(inst.clone())?;
+            result.transferred.push(inst);
+        } else {
+            result.skipped.push(inst);
+        }
+    }
+    
+    for event in &result.transferred {
+        let series = event.series_id.and_then(|sid| storage::get_series(&sid));
+        if let Some(ref from_user) = from_user {
+            notifications::create_host_removed_notification(from_user, &event.instance_id, event.series_id, event.start_utc, event.end_utc, false);
+        }
+        notifications::create_host_assigned_notification(&to_user, series.as_ref(), &event.instance_id, event.start_utc, event.end_utc);
+    }
+    
+    to_user.sessions_hosted_count = to_user.sessions_hosted_count.saturating_add(result.transferred.len() as u32);
+    to_user.updated_at = now;
+    storage::update_user(to_user);
+    
+    Ok(result)
+}
+
+/// Pairs of active sessions in the window hosted by `host` that overlap in time
+/// Back-to-back sessions (one ends exactly when the next starts) don't conflict.
+pub fn host_conflicts(host: &Principal, window_start: u64, window_end: u64) -> Vec<SessionConflict> {
+    let mut sessions: Vec<EventInstance> = recurrence::materialize_events(window_start, window_end)
+        .into_iter()
+        .filter(|e| e.host_principal.as_ref() == Some(host) && e.status == EventStatus::Active)
+        .collect();
+    sessions.sort_by_key(|e| e.start_utc);
+    
+    // Sweep in start order, keeping the sessions still running at each start
+    let mut conflicts = Vec::new();
+    let mut running: Vec<&EventInstance> = Vec::new();
+    for session in &sessions {
+        running.retain(|r| r.end_utc > session.start_utc);
+        for earlier in &running {
+            conflicts.push(SessionConflict {
+                first: (*earlier).clone(),
+                second: session.clone(),
+            });
+        }
+        running.push(session);
+    }
+    
+    conflicts
+}
+
+/// Drop a series occurrence's override so it reverts to the series defaults
+/// (this also un-cancels it). Hosts are told about any change that affects them.
+pub fn reset_occurrence(series_id: [u8; 16], occurrence_start: u64) -> ApiResult<EventInstance> {
+    let series = storage::get_series(&series_id)
+        .ok_or(ApiError::NotFound)?;
+    if !recurrence::generate_occurrences(&series, occurrence_start, occurrence_start + 1).contains(&occurrence_start) {
+        return Err(ApiError::NotFound);
+    }
+    
+    let instance_id = recurrence::generate_instance_id(&series_id, occurrence_start);
+    let before = get_event_instance(Some(series_id), Some(occurrence_start), &instance_id).ok();
+    storage::delete_override(&OverrideKey { series_id, occurrence_start_utc: occurrence_start });
+    let after = get_event_instance(Some(series_id), Some(occurrence_start), &instance_id)?;
+    let before_host = before.as_ref().and_then(|b| b.host_principal);
+    
+    if before_host != after.host_principal {
+        if let Some(user) = before_host.and_then(|p| storage::get_user(&p)) {
+            notifications::create_host_removed_notification(&user, &instance_id, Some(series_id), after.start_utc, after.end_utc, after.host_principal.is_none());
+        }
+        if let Some(user) = after.host_principal.and_then(|p| storage::get_user(&p)) {
+            notifications::create_host_assigned_notification(&user, Some(&series), &instance_id, after.start_utc, after.end_utc);
+        }
+    } else if let (Some(before), Some(user)) = (&before, after.host_principal.and_then(|p| storage::get_user(&p))) {
+        if before.start_utc != after.start_utc || before.end_utc != after.end_utc {
+            notifications::create_instance_time_changed_notification(&user, &instance_id, Some(series_id), &after.title, after.start_utc, after.end_utc);
+        }
+    }
+    
+    Ok(after)
+}
+
+/// Set per-occurrence notes and/or title on a series occurrence
+/// `None` for a field leaves it unchanged; `Some(None)` clears the override so
+/// the occurrence falls back to the series value.
+pub fn set_occurrence_text(
+    series_id: [u8; 16],
+    occurrence_start: u64,
+    notes: Option<Option<String>>,
+    title: Option<Option<String>>,
+    caller: Principal,
+) -> ApiResult<EventInstance> {
+    let now = ic_cdk::api::time();
+    let series = storage::get_series(&series_id)
+        .ok_or(ApiError::NotFound)?;
+    
+    // The occurrence must actually be generated by the series
+    if !recurrence::generate_occurrences(&series, occurrence_start, occurrence_start + 1).contains(&occurrence_start) {
+        return Err(ApiError::NotFound);
+    }
+    
+    let existing = storage::get_override(&OverrideKey {
+        series_id,
+        occurrence_start_utc: occurrence_start,
+    });
+    check_occurrence_editable(&series, existing.as_ref())?;
+    let mut ovr = existing.unwrap_or_else(|| new_edit_override(&series, occurrence_start, caller, now));
+    
+    if let Some(notes) = notes {
+        ovr.notes = notes;
+    }
+    if let Some(title) = title {
+        ovr.title_override = title;
+    }
+    ovr.updated_at = now;
+    ovr.updated_by = caller;
+    storage::try_insert_override(ovr)?;
+    
+    let instance_id = recurrence::generate_instance_id(&series_id, occurrence_start);
+    get_event_instance(Some(series_id), Some(occurrence_start), &instance_id)
+}
+
+/// A first override for an occurrence being edited or resized, with notes
+/// pre-filled from the series' notes template. Host changes and sweeps use
+/// `InstanceOverride::new` so they leave the notes alone.
+fn new_edit_override(series: &EventSeries, occurrence_start: u64, caller: Principal, now: u64) -> InstanceOverride {
+    InstanceOverride {
+        notes: series.notes_template.clone(),
+        ..InstanceOverride::new(series.series_id, occurrence_start, caller, now)
+    }
+}
+
+/// Cancelled occurrences and paused series don't materialize, so edits to
+/// them are refused before anything is written
+fn check_occurrence_editable(series: &EventSeries, ovr: Option<&InstanceOverride>) -> ApiResult<()> {
+    if series.paused {
+        return Err(ApiError::Conflict("Series is paused".to_string()));
+    }
+    if ovr.map(|o| o.cancelled).unwrap_or(false) {
+        return Err(ApiError::Conflict("Occurrence is cancelled".to_string()));
+    }
+    Ok(())
+}
+
+/// Give a single series occurrence a custom length, keeping its (possibly
+/// overridden) start. The host is notified of the new times.
+pub fn set_occurrence_duration(
+    series_id: [u8; 16],
+    occurrence_start: u64,
+    minutes: u32,
+    caller: Principal,
+    now: u64,
+) -> ApiResult<EventInstance> {
+    let series = storage::get_series(&series_id)
+        .ok_or(ApiError::NotFound)?;
+    
+    if minutes == 0 {
+        return Err(ApiError::InvalidInput("Duration must be positive".to_string()));
+    }
+    validation::check_duration(&storage::get_settings(), minutes as u64)?;
+    
+    if !recurrence::generate_occurrences(&series, occurrence_start, occurrence_start + 1).contains(&occurrence_start) {
+        return Err(ApiError::NotFound);
+    }
+    
+    let existing = storage::get_override(&OverrideKey {
+        series_id,
+        occurrence_start_utc: occurrence_start,
+    });
+    check_occurrence_editable(&series, existing.as_ref())?;
+    let mut ovr = existing.unwrap_or_else(|| new_edit_override(&series, occurrence_start, caller, now));
+    
+    let start_utc = ovr.start_utc.unwrap_or(occurrence_start);
+    ovr.end_utc = Some(start_utc + minutes as u64 * 60 * 1_000_000_000);
+    ovr.updated_at = now;
+    ovr.updated_by = caller;
+    storage::try_insert_override(ovr)?;
+    
+    let instance_id = recurrence::generate_instance_id(&series_id, occurrence_start);
+    let event = get_event_instance(Some(series_id), Some(occurrence_start), &instance_id)?;
+    
+    if let Some(host) = event.host_principal.and_then(|p| storage::get_user(&p)) {
+        notifications::create_instance_time_changed_notification(
+            &host,
+            &event.instance_id,
+            event.series_id,
+            &event.title,
+            event.start_utc,
+            event.end_utc,
+        );
+    }
+    
+    Ok(event)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const HOUR: u64 = 3600 * 1_000_000_000;
+
+    fn host_available(id: u8, name: &str, weekday: Weekday, from_hour: u32, to_hour: u32) -> User {
+        User {
+            principal: Principal::from_slice(&[id]),
+            name: name.to_string(),
+            email: format!("{}@example.com", name.to_lowercase()),
+            role: Role::User,
+            status: UserStatus::Active,
+            out_of_office: vec![],
+            availability: vec![RecurringBlock {
+                weekday,
+                start_minute_utc: from_hour * 60,
+                end_minute_utc: to_hour * 60,
+            }],
+            notification_settings: auth::default_notification_settings(),
+            last_active: 0,
+            sessions_hosted_count: 0,
+            created_at: 0,
+            updated_at: 0,
+            locale: None,
+        }
+    }
+
+    /// Namespaced instance ids need the canister id, which only exists on-chain
+    fn without_canister_namespace() {
+        storage::update_settings(GlobalSettings { namespaced_instance_ids: false, ..Default::default() });
+    }
+
+    #[test]
+    fn monday_morning_host_is_suggested_for_monday_slot_only() {
+        without_canister_namespace();
+        storage::insert_user(host_available(1, "Ada", Weekday::Mon, 9, 12));
+        let monday = datetime::ymd_to_nanos(2024, 1, 1);
+
+        let suggested = suggest_hosts(monday + 10 * HOUR, monday + 11 * HOUR);
+        assert_eq!(suggested.len(), 1);
+        assert_eq!(suggested[0].name, "Ada");
+
+        let tuesday = monday + 24 * HOUR;
+        assert!(suggest_hosts(tuesday + 10 * HOUR, tuesday + 11 * HOUR).is_empty());
+        // Runs past the end of the availability window
+        assert!(suggest_hosts(monday + 11 * HOUR, monday + 13 * HOUR).is_empty());
+    }
+
+    fn hosted_one_off(tag: u8, host: u8, start_hour: u64, end_hour: u64) -> EventInstance {
+        let day = datetime::ymd_to_nanos(2024, 1, 1);
+        EventInstance {
+            instance_id: [tag; 16],
+            series_id: None,
+            start_utc: day + start_hour * HOUR,
+            end_utc: day + end_hour * HOUR,
+            title: format!("Session {}", tag),
+            notes: String::new(),
+            link: None,
+            host_principal: Some(Principal::from_slice(&[host])),
+            status: EventStatus::Active,
+            color: None,
+            created_at: 0,
+            occurrence_start: None,
+            assigned_by: None,
+            assigned_at: None,
+            created_by: None,
+            public: false,
+            capacity: None,
+            rsvp_count: 0,
+            overridden_fields: vec![],
+            series_title: None,
+        }
+    }
+
+    #[test]
+    fn overlapping_sessions_of_one_host_are_reported_as_a_pair() {
+        without_canister_namespace();
+        storage::insert_instance(hosted_one_off(1, 7, 9, 11));
+        storage::insert_instance(hosted_one_off(2, 7, 10, 12));
+        storage::insert_instance(hosted_one_off(3, 7, 12, 13)); // back-to-back with 2
+        storage::insert_instance(hosted_one_off(4, 8, 9, 12)); // someone else's
+
+        let day = datetime::ymd_to_nanos(2024, 1, 1);
+        let conflicts = host_conflicts(&Principal::from_slice(&[7]), day, day + 24 * HOUR);
+
+        assert_eq!(conflicts.len(), 1);
+        assert_eq!(conflicts[0].first.instance_id, [1; 16]);
+        assert_eq!(conflicts[0].second.instance_id, [2; 16]);
+    }
+
+    #[test]
+    fn notes_template_fills_resized_occurrences_but_not_claims() {
+        without_canister_namespace();
+        let admin = Principal::from_slice(&[1]);
+        let host = Principal::from_slice(&[2]);
+        let first = datetime::ymd_to_nanos(2024, 1, 1) + 15 * HOUR;
+        let series = EventSeries {
+            series_id: [9; 16],
+            title: "Office hours".to_string(),
+            notes: "Drop in any time".to_string(),
+            notes_template: Some("Moved this week".to_string()),
+            link: None,
+            frequency: Frequency::Weekly,
+            weekday: Weekday::Mon,
+            weekday_ordinal: None,
+            start_date: first,
+            end_date: None,
+            default_duration_minutes: 60,
+            color: None,
+            paused: false,
+            claims_paused: false,
+            deleted: false,
+            public: false,
+            capacity: None,
+            default_host: None,
+            reminder_hours_before_override: None,
+            created_at: 0,
+            created_by: admin,
+        };
+        storage::insert_series(series.clone());
+
+        // A claim writes a plain override with only the host set, as assign_host does
+        storage::insert_override(InstanceOverride {
+            host_principal: Some(host),
+            assigned_by: Some(host),
+            assigned_at: Some(first - HOUR),
+            ..InstanceOverride::new(series.series_id, first, host, first - HOUR)
+        });
+        let second = first + 7 * 24 * HOUR;
+        let resized = set_occurrence_duration(series.series_id, second, 90, admin, first).unwrap();
+
+        let events = recurrence::materialize_events(first, second + HOUR);
+        assert_eq!(events[0].notes, "Drop in any time");
+        assert!(!events[0].overridden_fields.contains(&OverrideField::Notes));
+        assert_eq!(resized.notes, "Moved this week");
+        assert_eq!(events[1].notes, "Moved this week");
+        assert!(events[1].overridden_fields.contains(&OverrideField::Notes));
+    }
+
+    #[test]
+    fn out_of_office_hosts_are_not_suggested() {
+        without_canister_namespace();
+        let monday = datetime::ymd_to_nanos(2024, 1, 1);
+        let mut away = host_available(2, "Grace", Weekday::Mon, 9, 12);
+        away.out_of_office = vec![OOOBlock { start_utc: monday, end_utc: monday + 24 * HOUR, reason: None }];
+        storage::insert_user(away);
+        storage::insert_user(host_available(3, "Alan", Weekday::Mon, 8, 12));
+
+        let names: Vec<String> = suggest_hosts(monday + 9 * HOUR, monday + 10 * HOUR)
+            .into_iter()
+            .map(|u| u.name)
+            .collect();
+        assert_eq!(names, vec!["Alan".to_string()]);
     }
 }
@@ -0,0 +1,457 @@
+//! Calendar date math on nanosecond UTC timestamps
+//!
+//! The single implementation of civil-date conversion used by recurrence,
+//! ICS generation, exports, and reports. All functions work in UTC, apart
+//! from `utc_offset_at`, which knows the zones offered for `org_timezone`.
+
+use crate::types::{Weekday, WeekdayOrdinal};
+
+/// Convert nanoseconds to days since epoch (for date calculations)
+pub fn nanos_to_days(nanos: u64) -> i64 {
+    (nanos / 1_000_000_000 / 86400) as i64
+}
+
+/// Convert days since epoch to nanoseconds (start of day UTC)
+pub fn days_to_nanos(days: i64) -> u64 {
+    (days as u64) * 86400 * 1_000_000_000
+}
+
+/// Get year, month, day from nanoseconds timestamp
+pub fn nanos_to_ymd(nanos: u64) -> (i32, u32, u32) {
+    // Simple algorithm: days since epoch -> date
+    let days = nanos_to_days(nanos) as i32;
+    
+    // Days since 1970-01-01
+    let mut y = 1970;
+    let mut remaining = days;
+    
+    loop {
+        let days_in_year = if is_leap_year(y) { 366 } else { 365 };
+        if remaining < days_in_year {
+            break;
+        }
+        remaining -= days_in_year;
+        y += 1;
+    }
+    
+    let mut m = 1u32;
+    loop {
+        let days_in_month = days_in_month(y, m);
+        if remaining < days_in_month as i32 {
+            break;
+        }
+        remaining -= days_in_month as i32;
+        m += 1;
+    }
+    
+    (y, m, (remaining + 1) as u32)
+}
+
+
+pub fn is_leap_year(year: i32) -> bool {
+    (year % 4 == 0 && year % 100 != 0) || (year % 400 == 0)
+}
+
+pub fn days_in_month(year: i32, month: u32) -> u32 {
+    match month {
+        1 | 3 | 5 | 7 | 8 | 10 | 12 => 31,
+        4 | 6 | 9 | 11 => 30,
+        2 => if is_leap_year(year) { 29 } else { 28 },
+        _ => 0,
+    }
+}
+
+/// Convert year/month/day to nanoseconds (start of day UTC)
+pub fn ymd_to_nanos(year: i32, month: u32, day: u32) -> u64 {
+    let mut days: i64 = 0;
+    
+    // Years from 1970
+    for y in 1970..year {
+        days += if is_leap_year(y) { 366 } else { 365 };
+    }
+    
+    // Months in current year
+    for m in 1..month {
+        days += days_in_month(year, m) as i64;
+    }
+    
+    // Days in current month
+    days += (day - 1) as i64;
+    
+    days_to_nanos(days)
+}
+
+/// Get day of week (0=Mon, 6=Sun) from nanoseconds timestamp
+pub fn weekday_from_nanos(nanos: u64) -> u32 {
+    let days = nanos_to_days(nanos);
+    // 1970-01-01 was Thursday (3)
+    ((days + 3) % 7 + 7) as u32 % 7
+}
+
+
+/// Day of week as a `Weekday` from nanoseconds timestamp
+pub fn weekday_of(nanos: u64) -> Weekday {
+    match weekday_from_nanos(nanos) {
+        0 => Weekday::Mon,
+        1 => Weekday::Tue,
+        2 => Weekday::Wed,
+        3 => Weekday::Thu,
+        4 => Weekday::Fri,
+        5 => Weekday::Sat,
+        _ => Weekday::Sun,
+    }
+}
+
+
+/// Find the nth occurrence of a weekday in a month
+/// Returns nanoseconds timestamp or None if doesn't exist
+pub fn nth_weekday_of_month(year: i32, month: u32, weekday: Weekday, ordinal: WeekdayOrdinal) -> Option<u64> {
+    let target_wd = weekday as u32;
+    
+    match ordinal {
+        WeekdayOrdinal::First | WeekdayOrdinal::Second | WeekdayOrdinal::Third | WeekdayOrdinal::Fourth => {
+            let n = ordinal as u32; // 1, 2, 3, or 4
+            let first_of_month = ymd_to_nanos(year, month, 1);
+            let first_wd = weekday_from_nanos(first_of_month);
+            
+            let days_until = (target_wd + 7 - first_wd) % 7;
+            let target_day = 1 + days_until + (n - 1) * 7;
+            
+            if target_day <= days_in_month(year, month) {
+                Some(ymd_to_nanos(year, month, target_day))
+            } else {
+                None // e.g., 5th Monday doesn't exist
+            }
+        }
+        WeekdayOrdinal::Last => {
+            let last_day = days_in_month(year, month);
+            let last_of_month = ymd_to_nanos(year, month, last_day);
+            let last_wd = weekday_from_nanos(last_of_month);
+            
+            let days_back = (last_wd + 7 - target_wd) % 7;
+            let target_day = last_day - days_back;
+            
+            Some(ymd_to_nanos(year, month, target_day))
+        }
+    }
+}
+
+
+/// Format a timestamp as ISO-8601 UTC (YYYY-MM-DDTHH:MM:SSZ)
+pub fn format_iso8601(nanos: u64) -> String {
+    let (y, m, d) = nanos_to_ymd(nanos);
+    let secs_in_day = (nanos / 1_000_000_000) % 86400;
+    format!(
+        "{:04}-{:02}-{:02}T{:02}:{:02}:{:02}Z",
+        y, m, d, secs_in_day / 3600, (secs_in_day % 3600) / 60, secs_in_day % 60
+    )
+}
+
+/// Format a timestamp as ICS datetime (YYYYMMDDTHHMMSSZ)
+pub fn format_ics_datetime(nanos: u64) -> String {
+    let (y, m, d) = nanos_to_ymd(nanos);
+    let secs_in_day = (nanos / 1_000_000_000) % 86400;
+    format!(
+        "{:04}{:02}{:02}T{:02}{:02}{:02}Z",
+        y, m, d, secs_in_day / 3600, (secs_in_day % 3600) / 60, secs_in_day % 60
+    )
+}
+
+
+/// Get the start/end nanos and label for `months_back` months ago.
+/// months_back=0 means current month.
+pub fn month_window(now: u64, months_back: u8) -> (u64, u64, String) {
+    let (mut y, mut m, _) = nanos_to_ymd(now);
+    
+    for _ in 0..months_back {
+        if m == 1 {
+            m = 12;
+            y -= 1;
+        } else {
+            m -= 1;
+        }
+    }
+    
+    let start = ymd_to_nanos(y, m, 1);
+    
+    let (ny, nm) = if m == 12 { (y + 1, 1) } else { (y, m + 1) };
+    let end = ymd_to_nanos(ny, nm, 1);
+    
+    let month_names = ["Jan","Feb","Mar","Apr","May","Jun","Jul","Aug","Sep","Oct","Nov","Dec"];
+    let label = format!("{} {}", month_names[(m - 1) as usize], y);
+    
+    (start, end, label)
+}
+
+/// Get the start/end nanos and ISO week label (e.g. "2025-W07") for the
+/// week starting on `week_start` `weeks_back` weeks ago. weeks_back=0 means current week.
+/// For non-Monday starts the label is the ISO week of the Thursday inside the window.
+pub fn week_window(now: u64, weeks_back: u8, week_start: Weekday) -> (u64, u64, String) {
+    let today = nanos_to_days(now);
+    let offset = (weekday_from_nanos(now) + 7 - week_start as u32) % 7;
+    let first = today - offset as i64 - weeks_back as i64 * 7;
+    
+    // The ISO year is the year containing the week's Thursday
+    let thursday = first + (Weekday::Thu as i64 - week_start as i64 + 7) % 7;
+    let (iso_year, _, _) = nanos_to_ymd(days_to_nanos(thursday));
+    let jan1 = nanos_to_days(ymd_to_nanos(iso_year, 1, 1));
+    let week = (thursday - jan1) / 7 + 1;
+    
+    let label = format!("{}-W{:02}", iso_year, week);
+    (days_to_nanos(first), days_to_nanos(first + 7), label)
+}
+
+
+/// A daylight-saving switch: the `ordinal` `weekday` of `month`, at `minute`
+/// minutes after midnight local standard time (may be negative)
+#[derive(Clone, Copy)]
+struct Transition {
+    month: u32,
+    ordinal: WeekdayOrdinal,
+    weekday: Weekday,
+    minute: i32,
+}
+
+/// Daylight saving (one hour ahead of standard time) from `start` until `end`
+#[derive(Clone, Copy)]
+struct DstRule {
+    start: Transition,
+    end: Transition,
+}
+
+const fn transition(month: u32, ordinal: WeekdayOrdinal, weekday: Weekday, minute: i32) -> Transition {
+    Transition { month, ordinal, weekday, minute }
+}
+
+/// US and Canada: second Sunday of March to first Sunday of November, 02:00 local
+const US_DST: Option<DstRule> = Some(DstRule {
+    start: transition(3, WeekdayOrdinal::Second, Weekday::Sun, 120),
+    end: transition(11, WeekdayOrdinal::First, Weekday::Sun, 60),
+});
+
+/// EU: last Sunday of March to last Sunday of October, 01:00 UTC
+const fn eu_dst(standard_offset: i32) -> Option<DstRule> {
+    Some(DstRule {
+        start: transition(3, WeekdayOrdinal::Last, Weekday::Sun, 60 + standard_offset),
+        end: transition(10, WeekdayOrdinal::Last, Weekday::Sun, 60 + standard_offset),
+    })
+}
+
+/// South-east Australia: first Sunday of October to first Sunday of April
+const AU_DST: Option<DstRule> = Some(DstRule {
+    start: transition(10, WeekdayOrdinal::First, Weekday::Sun, 120),
+    end: transition(4, WeekdayOrdinal::First, Weekday::Sun, 120),
+});
+
+/// New Zealand: last Sunday of September to first Sunday of April
+const NZ_DST: Option<DstRule> = Some(DstRule {
+    start: transition(9, WeekdayOrdinal::Last, Weekday::Sun, 120),
+    end: transition(4, WeekdayOrdinal::First, Weekday::Sun, 120),
+});
+
+/// Chile: first Sunday of September to first Sunday of April, 04:00/03:00 UTC
+const CHILE_DST: Option<DstRule> = Some(DstRule {
+    start: transition(9, WeekdayOrdinal::First, Weekday::Sun, 0),
+    end: transition(4, WeekdayOrdinal::First, Weekday::Sun, -60),
+});
+
+/// Egypt: last Friday of April to the end of the last Thursday of October
+const EGYPT_DST: Option<DstRule> = Some(DstRule {
+    start: transition(4, WeekdayOrdinal::Last, Weekday::Fri, 0),
+    end: transition(10, WeekdayOrdinal::Last, Weekday::Thu, 23 * 60),
+});
+
+/// IANA zones accepted for `org_timezone` (the zones the frontend offers),
+/// with their standard offset from UTC in minutes and current DST rule
+const TIMEZONES: &[(&str, i32, Option<DstRule>)] = &[
+    ("Pacific/Midway", -660, None),
+    ("Pacific/Honolulu", -600, None),
+    ("Pacific/Marquesas", -570, None),
+    ("America/Anchorage", -540, US_DST),
+    ("America/Los_Angeles", -480, US_DST),
+    ("America/Denver", -420, US_DST),
+    ("America/Phoenix", -420, None),
+    ("America/Chicago", -360, US_DST),
+    ("America/Mexico_City", -360, None),
+    ("America/New_York", -300, US_DST),
+    ("America/Bogota", -300, None),
+    ("America/Caracas", -240, None),
+    ("America/Halifax", -240, US_DST),
+    ("America/Santiago", -240, CHILE_DST),
+    ("America/St_Johns", -210, US_DST),
+    ("America/Sao_Paulo", -180, None),
+    ("America/Argentina/Buenos_Aires", -180, None),
+    ("Atlantic/South_Georgia", -120, None),
+    ("Atlantic/Azores", -60, eu_dst(-60)),
+    ("UTC", 0, None),
+    ("Europe/London", 0, eu_dst(0)),
+    ("Europe/Paris", 60, eu_dst(60)),
+    ("Europe/Berlin", 60, eu_dst(60)),
+    ("Africa/Lagos", 60, None),
+    ("Europe/Athens", 120, eu_dst(120)),
+    ("Africa/Cairo", 120, EGYPT_DST),
+    ("Africa/Johannesburg", 120, None),
+    ("Europe/Istanbul", 180, None),
+    ("Europe/Moscow", 180, None),
+    ("Asia/Riyadh", 180, None),
+    ("Africa/Nairobi", 180, None),
+    ("Asia/Tehran", 210, None),
+    ("Asia/Dubai", 240, None),
+    ("Asia/Kabul", 270, None),
+    ("Asia/Karachi", 300, None),
+    ("Asia/Kolkata", 330, None),
+    ("Asia/Kathmandu", 345, None),
+    ("Asia/Dhaka", 360, None),
+    ("Asia/Yangon", 390, None),
+    ("Asia/Bangkok", 420, None),
+    ("Asia/Jakarta", 420, None),
+    ("Asia/Shanghai", 480, None),
+    ("Asia/Singapore", 480, None),
+    ("Asia/Hong_Kong", 480, None),
+    ("Asia/Taipei", 480, None),
+    ("Australia/Perth", 480, None),
+    ("Asia/Tokyo", 540, None),
+    ("Asia/Seoul", 540, None),
+    ("Australia/Adelaide", 570, AU_DST),
+    ("Australia/Sydney", 600, AU_DST),
+    ("Pacific/Guam", 600, None),
+    ("Pacific/Noumea", 660, None),
+    ("Pacific/Auckland", 720, NZ_DST),
+    ("Pacific/Fiji", 720, None),
+    ("Pacific/Tongatapu", 780, None),
+];
+
+/// Whether `timezone` is one of the zones `utc_offset_at` knows
+pub fn is_known_timezone(timezone: &str) -> bool {
+    TIMEZONES.iter().any(|(name, _, _)| *name == timezone)
+}
+
+/// UTC instant of a DST switch in `year`, for a zone `standard_offset` minutes from UTC
+fn transition_nanos(year: i32, standard_offset: i32, t: &Transition) -> i64 {
+    let day = nth_weekday_of_month(year, t.month, t.weekday, t.ordinal)
+        .expect("first-fourth and last weekdays always exist") as i64;
+    day + (t.minute - standard_offset) as i64 * 60 * 1_000_000_000
+}
+
+/// Offset from UTC in minutes in effect in `timezone` at `nanos` (None for an
+/// unknown zone). Uses each zone's current rules for every year.
+pub fn utc_offset_at(timezone: &str, nanos: u64) -> Option<i32> {
+    let &(_, standard_offset, rule) = TIMEZONES.iter().find(|(name, _, _)| *name == timezone)?;
+    let rule = match rule {
+        Some(rule) => rule,
+        None => return Some(standard_offset),
+    };
+    
+    let local = nanos as i64 + standard_offset as i64 * 60 * 1_000_000_000;
+    let (year, _, _) = nanos_to_ymd(local.max(0) as u64);
+    let start = transition_nanos(year, standard_offset, &rule.start);
+    let end = transition_nanos(year, standard_offset, &rule.end);
+    let t = nanos as i64;
+    // Southern-hemisphere rules start late in the year and end early in the next
+    let in_dst = if start < end {
+        t >= start && t < end
+    } else {
+        t >= start || t < end
+    };
+    
+    Some(if in_dst { standard_offset + 60 } else { standard_offset })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn ymd_round_trips_across_leap_years_and_month_ends() {
+        let dates = [
+            (1970, 1, 1),
+            (1999, 12, 31),
+            (2000, 2, 29),
+            (2000, 3, 1),
+            (2023, 2, 28),
+            (2024, 2, 29),
+            (2024, 12, 31),
+            (2100, 3, 1),
+        ];
+        for (y, m, d) in dates {
+            assert_eq!(nanos_to_ymd(ymd_to_nanos(y, m, d)), (y, m, d));
+        }
+    }
+
+    #[test]
+    fn epoch_is_day_zero_and_a_thursday() {
+        assert_eq!(ymd_to_nanos(1970, 1, 1), 0);
+        assert_eq!(weekday_of(0), Weekday::Thu);
+    }
+
+    #[test]
+    fn leap_years_follow_century_rule() {
+        assert!(is_leap_year(2000));
+        assert!(is_leap_year(2024));
+        assert!(!is_leap_year(1900));
+        assert!(!is_leap_year(2023));
+        assert_eq!(days_in_month(2024, 2), 29);
+        assert_eq!(days_in_month(2023, 2), 28);
+    }
+
+    #[test]
+    fn formats_iso8601_and_ics() {
+        let t = ymd_to_nanos(2024, 3, 9) + (13 * 3600 + 5 * 60 + 7) * 1_000_000_000;
+        assert_eq!(format_iso8601(0), "1970-01-01T00:00:00Z");
+        assert_eq!(format_iso8601(t), "2024-03-09T13:05:07Z");
+        assert_eq!(format_iso8601(ymd_to_nanos(2025, 1, 1) - 1), "2024-12-31T23:59:59Z");
+        assert_eq!(format_ics_datetime(t), "20240309T130507Z");
+    }
+
+    const HOUR: u64 = 3600 * 1_000_000_000;
+
+    #[test]
+    fn sunday_belongs_to_different_weeks_by_week_start() {
+        let sunday_noon = ymd_to_nanos(2024, 1, 7) + 12 * HOUR;
+
+        let (start, end, label) = week_window(sunday_noon, 0, Weekday::Mon);
+        assert_eq!((start, end), (ymd_to_nanos(2024, 1, 1), ymd_to_nanos(2024, 1, 8)));
+        assert_eq!(label, "2024-W01");
+
+        let (start, end, label) = week_window(sunday_noon, 0, Weekday::Sun);
+        assert_eq!((start, end), (ymd_to_nanos(2024, 1, 7), ymd_to_nanos(2024, 1, 14)));
+        assert_eq!(label, "2024-W02");
+    }
+
+    #[test]
+    fn week_window_edges_and_year_boundary() {
+        // Midnight starting a week belongs to that week, not the one before
+        let monday = ymd_to_nanos(2024, 1, 8);
+        assert_eq!(week_window(monday, 0, Weekday::Mon).0, monday);
+        assert_eq!(week_window(monday - 1, 0, Weekday::Mon).0, ymd_to_nanos(2024, 1, 1));
+        assert_eq!(week_window(monday, 1, Weekday::Mon).0, ymd_to_nanos(2024, 1, 1));
+
+        let (start, _, label) = week_window(ymd_to_nanos(2021, 1, 1), 0, Weekday::Mon);
+        assert_eq!(start, ymd_to_nanos(2020, 12, 28));
+        assert_eq!(label, "2020-W53");
+    }
+
+    #[test]
+    fn us_offsets_switch_at_2am_local() {
+        let dst_day = ymd_to_nanos(2024, 3, 10);
+        assert_eq!(utc_offset_at("America/New_York", dst_day + 6 * HOUR), Some(-300)); // 01:00 EST
+        assert_eq!(utc_offset_at("America/New_York", dst_day + 8 * HOUR), Some(-240)); // 04:00 EDT
+        let back_day = ymd_to_nanos(2024, 11, 3);
+        assert_eq!(utc_offset_at("America/New_York", back_day + 5 * HOUR), Some(-240)); // 01:00 EDT
+        assert_eq!(utc_offset_at("America/New_York", back_day + 7 * HOUR), Some(-300)); // 02:00 EST
+    }
+
+    #[test]
+    fn southern_hemisphere_dst_spans_new_year() {
+        assert_eq!(utc_offset_at("Australia/Sydney", ymd_to_nanos(2024, 1, 15)), Some(660));
+        assert_eq!(utc_offset_at("Australia/Sydney", ymd_to_nanos(2024, 7, 15)), Some(600));
+    }
+
+    #[test]
+    fn zones_without_dst_keep_their_offset() {
+        assert_eq!(utc_offset_at("Asia/Tokyo", ymd_to_nanos(2024, 1, 15)), Some(540));
+        assert_eq!(utc_offset_at("Asia/Tokyo", ymd_to_nanos(2024, 7, 15)), Some(540));
+        assert_eq!(utc_offset_at("Europe/London", ymd_to_nanos(2024, 7, 15)), Some(60));
+        assert_eq!(utc_offset_at("Mars/Olympus_Mons", 0), None);
+    }
+}
@@ -3,16 +3,22 @@
 //! A lightweight scheduling system for managing office hours sessions.
 //! Built on the Internet Computer with Internet Identity authentication.
 
+mod audit;
 mod auth;
 mod coverage;
+mod datetime;
+mod maintenance;
 mod notifications;
 mod recurrence;
+mod stats;
 mod storage;
 mod types;
+mod validation;
 
 use candid::Principal;
 use ic_cdk::{init, post_upgrade, query, update};
 use ic_cdk::caller;
+use std::time::Duration;
 use types::*;
 
 // ============================================================================
@@ -28,13 +34,40 @@ fn init() {
         "Initial Admin".to_string(),
         "admin@ohscheduler.local".to_string(),
     );
+    start_housekeeping_timer();
 }
 
 /// Preserve admin on upgrade
 #[post_upgrade]
 fn post_upgrade() {
-    // Stable structures handle persistence automatically
-    // No migration needed for MVP
+    // Stable structures handle persistence automatically; timers don't
+    // survive upgrades and have to be registered again
+    start_housekeeping_timer();
+}
+
+/// Time between periodic housekeeping runs
+const HOUSEKEEPING_INTERVAL: Duration = Duration::from_secs(3600);
+
+fn start_housekeeping_timer() {
+    ic_cdk_timers::set_timer_interval(HOUSEKEEPING_INTERVAL, housekeeping);
+}
+
+/// Periodic housekeeping; does nothing unless enabled in settings
+fn housekeeping() {
+    let now = ic_cdk::api::time();
+    let settings = storage::get_settings();
+    if settings.auto_cancel_past_unclaimed {
+        maintenance::sweep_past_unclaimed(now, settings.auto_cancel_grace_hours, ic_cdk::id());
+    }
+    
+    if let Some(threshold) = settings.coverage_alert_threshold_pct {
+        let (week_start, _, _) = datetime::week_window(now, 0, settings.week_starts_on);
+        if storage::get_last_coverage_alert_week() != week_start
+            && notifications::send_coverage_alert(now, threshold) > 0
+        {
+            storage::set_last_coverage_alert_week(week_start);
+        }
+    }
 }
 
 
@@ -55,13 +88,37 @@ fn whoami() -> Principal {
     caller()
 }
 
+/// Get caller's principal together with whether (and as whom) they are authorized
+#[query]
+fn whoami_status() -> WhoamiStatus {
+    let principal = caller();
+    if principal == Principal::anonymous() {
+        return WhoamiStatus::Anonymous;
+    }
+    match storage::get_user(&principal) {
+        Some(user) if user.status == UserStatus::Active => WhoamiStatus::Authorized(user),
+        Some(_) => WhoamiStatus::Disabled(principal),
+        None => WhoamiStatus::Unauthorized(principal),
+    }
+}
+
 /// Update notification settings for the current user
 #[update]
 fn update_notification_settings(settings: NotificationSettings) -> ApiResult<()> {
     let mut user = auth::require_authorized()?;
     user.notification_settings = settings;
     user.updated_at = ic_cdk::api::time();
-    storage::update_user(user);
+    storage::try_insert_user(user)?;
+    Ok(())
+}
+
+/// Set or clear (None or blank) the current user's locale for notification text
+#[update]
+fn set_my_locale(locale: Option<String>) -> ApiResult<()> {
+    let mut user = auth::require_authorized()?;
+    user.locale = validation::normalize_locale(locale)?;
+    user.updated_at = ic_cdk::api::time();
+    storage::try_insert_user(user)?;
     Ok(())
 }
 
@@ -69,9 +126,33 @@ fn update_notification_settings(settings: NotificationSettings) -> ApiResult<()>
 #[update]
 fn set_out_of_office(blocks: Vec<OOOBlock>) -> ApiResult<()> {
     let mut user = auth::require_authorized()?;
-    user.out_of_office = blocks;
+    user.out_of_office = validation::normalize_ooo_blocks(blocks)?;
     user.updated_at = ic_cdk::api::time();
-    storage::update_user(user);
+    storage::try_insert_user(user)?;
+    Ok(())
+}
+
+/// Set recurring weekly availability windows for the current user
+#[update]
+fn set_availability(blocks: Vec<RecurringBlock>) -> ApiResult<()> {
+    let mut user = auth::require_authorized()?;
+    
+    if blocks.len() > validation::MAX_AVAILABILITY_BLOCKS {
+        return Err(ApiError::InvalidInput(format!(
+            "At most {} availability windows are allowed", validation::MAX_AVAILABILITY_BLOCKS
+        )));
+    }
+    for block in &blocks {
+        if block.start_minute_utc >= block.end_minute_utc || block.end_minute_utc > 24 * 60 {
+            return Err(ApiError::InvalidInput(
+                "Availability window must have start before end, within a single day".to_string()
+            ));
+        }
+    }
+    
+    user.availability = blocks;
+    user.updated_at = ic_cdk::api::time();
+    storage::try_insert_user(user)?;
     Ok(())
 }
 
@@ -80,58 +161,146 @@ fn set_out_of_office(blocks: Vec<OOOBlock>) -> ApiResult<()> {
 // Admin - User Management
 // ============================================================================
 
-/// List all users (admin only)
+/// List all users (requires ManageUsers)
 #[query]
 fn list_users() -> ApiResult<Vec<User>> {
-    auth::require_admin()?;
+    auth::require_capability(Capability::ManageUsers)?;
     Ok(storage::list_all_users())
 }
 
-/// List user directory (any authorized user) — returns name + principal + role + status only
-#[query]
-fn list_user_directory() -> ApiResult<Vec<UserDirectoryEntry>> {
-    auth::require_authorized()?;
-    let users = storage::list_all_users();
-    Ok(users.into_iter().map(|u| UserDirectoryEntry {
+/// Directory view of a user; out-of-office blocks (with reasons) only when `include_ooo`
+fn directory_entry(u: User, include_ooo: bool) -> UserDirectoryEntry {
+    UserDirectoryEntry {
         principal: u.principal,
         name: u.name,
         role: u.role,
         status: u.status,
-    }).collect())
+        out_of_office: if include_ooo { Some(u.out_of_office) } else { None },
+    }
+}
+
+/// List user directory (any authorized user) — returns name + principal + role + status,
+/// plus out-of-office blocks for admins
+#[query]
+fn list_user_directory() -> ApiResult<Vec<UserDirectoryEntry>> {
+    let caller = auth::require_authorized()?;
+    let include_ooo = caller.role == Role::Admin;
+    let users = storage::list_all_users();
+    Ok(users.into_iter().map(|u| directory_entry(u, include_ooo)).collect())
 }
 
-/// Authorize a new user (admin only)
+/// Authorize a new user (requires ManageUsers)
 #[update]
 fn authorize_user(principal: Principal, name: String, email: String, role: Role) -> ApiResult<User> {
-    auth::require_admin()?;
+    let admin = auth::require_capability(Capability::ManageUsers)?;
     
     if storage::user_exists(&principal) {
         return Err(ApiError::Conflict("User already exists".to_string()));
     }
+    validation::check_length("name", &name, validation::MAX_NAME_LEN)?;
+    let email = validation::validate_optional_email(&email)?;
     
-    let now = ic_cdk::api::time();
-    let user = User {
-        principal,
-        name,
-        email,
-        role,
-        status: UserStatus::Active,
-        out_of_office: vec![],
-        notification_settings: NotificationSettings::default(),
-        last_active: now,
-        sessions_hosted_count: 0,
-        created_at: now,
-        updated_at: now,
-    };
+    let user = auth::new_user(principal, name, email, role);
     
-    storage::insert_user(user.clone());
+    storage::try_insert_user(user.clone())?;
+    audit::record(
+        admin.principal,
+        AuditAction::UserAuthorized,
+        principal.to_text(),
+        format!("name={}, role={:?}", user.name, user.role),
+    );
     Ok(user)
 }
 
-/// Disable a user (admin only)
+/// Authorize many users at once (requires ManageUsers)
+/// Each entry is handled independently: existing principals and invalid
+/// entries are reported in `skipped` without affecting the others.
+#[update]
+fn authorize_users(entries: Vec<AuthorizeUserInput>) -> ApiResult<BulkAuthorizeResult> {
+    let admin = auth::require_capability(Capability::ManageUsers)?;
+    
+    let mut result = BulkAuthorizeResult { created: vec![], skipped: vec![] };
+    
+    for entry in entries {
+        let name = entry.name.trim().to_string();
+        let email = validation::validate_email(&entry.email).ok();
+        
+        let reason = if storage::user_exists(&entry.principal) {
+            Some("User already exists")
+        } else if name.is_empty() {
+            Some("Name is required")
+        } else if name.chars().count() > validation::MAX_NAME_LEN {
+            Some("Name is too long")
+        } else if email.is_none() {
+            Some("A valid email is required")
+        } else {
+            None
+        };
+        if let Some(reason) = reason {
+            result.skipped.push(SkippedUser { principal: entry.principal, reason: reason.to_string() });
+            continue;
+        }
+        let email = email.unwrap_or_default();
+        
+        let user = auth::new_user(entry.principal, name, email, entry.role);
+        if let Err(ApiError::InvalidInput(reason)) = storage::try_insert_user(user.clone()) {
+            result.skipped.push(SkippedUser { principal: entry.principal, reason });
+            continue;
+        }
+        audit::record(
+            admin.principal,
+            AuditAction::UserAuthorized,
+            user.principal.to_text(),
+            format!("name={}, role={:?} (bulk)", user.name, user.role),
+        );
+        result.created.push(user);
+    }
+    
+    Ok(result)
+}
+
+/// Let a principal that isn't a user read the calendar (requires ManageUsers)
+/// Observers can list events but not claim, RSVP or change anything.
+#[update]
+fn add_observer(principal: Principal) -> ApiResult<()> {
+    let admin = auth::require_capability(Capability::ManageUsers)?;
+    
+    if principal == Principal::anonymous() {
+        return Err(ApiError::InvalidInput("The anonymous principal cannot be an observer".to_string()));
+    }
+    if storage::user_exists(&principal) {
+        return Err(ApiError::Conflict("Principal is already a user".to_string()));
+    }
+    if !storage::insert_observer(&principal, ic_cdk::api::time()) {
+        return Err(ApiError::Conflict("Principal is already an observer".to_string()));
+    }
+    audit::record(admin.principal, AuditAction::ObserverAdded, principal.to_text(), String::new());
+    Ok(())
+}
+
+/// Revoke an observer's read access (requires ManageUsers)
+#[update]
+fn remove_observer(principal: Principal) -> ApiResult<()> {
+    let admin = auth::require_capability(Capability::ManageUsers)?;
+    
+    if !storage::delete_observer(&principal) {
+        return Err(ApiError::NotFound);
+    }
+    audit::record(admin.principal, AuditAction::ObserverRemoved, principal.to_text(), String::new());
+    Ok(())
+}
+
+/// List observer principals (requires ManageUsers)
+#[query]
+fn list_observers() -> ApiResult<Vec<Principal>> {
+    auth::require_capability(Capability::ManageUsers)?;
+    Ok(storage::list_observers())
+}
+
+/// Disable a user (requires ManageUsers)
 #[update]
 fn disable_user(principal: Principal) -> ApiResult<()> {
-    auth::require_admin()?;
+    let admin = auth::require_capability(Capability::ManageUsers)?;
     
     let mut user = storage::get_user(&principal)
         .ok_or(ApiError::NotFound)?;
@@ -139,13 +308,14 @@ fn disable_user(principal: Principal) -> ApiResult<()> {
     user.status = UserStatus::Disabled;
     user.updated_at = ic_cdk::api::time();
     storage::update_user(user);
+    audit::record(admin.principal, AuditAction::UserDisabled, principal.to_text(), String::new());
     Ok(())
 }
 
-/// Enable a user (admin only)
+/// Enable a user (requires ManageUsers)
 #[update]
 fn enable_user(principal: Principal) -> ApiResult<()> {
-    auth::require_admin()?;
+    let admin = auth::require_capability(Capability::ManageUsers)?;
     
     let mut user = storage::get_user(&principal)
         .ok_or(ApiError::NotFound)?;
@@ -153,29 +323,37 @@ fn enable_user(principal: Principal) -> ApiResult<()> {
     user.status = UserStatus::Active;
     user.updated_at = ic_cdk::api::time();
     storage::update_user(user);
+    audit::record(admin.principal, AuditAction::UserEnabled, principal.to_text(), String::new());
     Ok(())
 }
 
-/// Update user info (admin only)
+/// Update user info (requires ManageUsers)
 #[update]
 fn update_user(principal: Principal, name: String, email: String, role: Role) -> ApiResult<User> {
-    auth::require_admin()?;
+    let admin = auth::require_capability(Capability::ManageUsers)?;
     
     let mut user = storage::get_user(&principal)
         .ok_or(ApiError::NotFound)?;
     
+    validation::check_length("name", &name, validation::MAX_NAME_LEN)?;
     user.name = name;
-    user.email = email;
+    user.email = validation::validate_optional_email(&email)?;
     user.role = role;
     user.updated_at = ic_cdk::api::time();
-    storage::update_user(user.clone());
+    storage::try_insert_user(user.clone())?;
+    audit::record(
+        admin.principal,
+        AuditAction::UserUpdated,
+        principal.to_text(),
+        format!("name={}, role={:?}", user.name, user.role),
+    );
     Ok(user)
 }
 
-/// Delete a user (admin only)
+/// Delete a user (requires ManageUsers)
 #[update]
 fn delete_user(principal: Principal) -> ApiResult<()> {
-    auth::require_admin()?;
+    let admin = auth::require_capability(Capability::ManageUsers)?;
     
     // Prevent deleting yourself
     if ic_cdk::caller() == principal {
@@ -186,9 +364,18 @@ fn delete_user(principal: Principal) -> ApiResult<()> {
         return Err(ApiError::NotFound);
     }
     
+    audit::record(admin.principal, AuditAction::UserDeleted, principal.to_text(), String::new());
     Ok(())
 }
 
+/// Describe what a user can do right now (admin only, read-only)
+#[query]
+fn describe_user_access(principal: Principal) -> ApiResult<UserAccess> {
+    auth::require_admin()?;
+    let user = storage::get_user(&principal).ok_or(ApiError::NotFound)?;
+    Ok(auth::describe_access(&user, ic_cdk::api::time()))
+}
+
 
 // ============================================================================
 // Events - Public
@@ -196,49 +383,48 @@ fn delete_user(principal: Principal) -> ApiResult<()> {
 
 /// Get ICS content for an event (public, no auth required)
 #[query]
-fn get_event_ics_public(instance_id: Vec<u8>) -> Result<String, ApiError> {
-    let iid: [u8; 16] = instance_id.try_into()
-        .map_err(|_| ApiError::InvalidInput("Invalid instance_id".to_string()))?;
-
-    let settings = storage::get_settings();
-    let now = ic_cdk::api::time();
-    let window_end = recurrence::calculate_window_end(now, settings.forward_window_months);
+fn get_event_ics_public(instance_id: IdBytes) -> Result<String, ApiError> {
+    let iid = instance_id.0;
 
-    let events = recurrence::materialize_events(0, window_end);
-    let event = events.into_iter()
-        .find(|e| e.instance_id == iid)
+    let event = recurrence::find_event(&iid)
+        .or_else(|| recurrence::find_cancelled_occurrence(&iid))
+        .filter(|e| e.public)
         .ok_or(ApiError::NotFound)?;
+    let cancelled = event.status == EventStatus::Cancelled;
 
-    Ok(notifications::generate_ics(
-        &event.instance_id,
-        &event.title,
-        &event.notes,
-        event.link.as_deref(),
-        event.start_utc,
-        event.end_utc,
-        "REQUEST",
-        1,
-        event.status == EventStatus::Cancelled,
-    ))
+    Ok(notifications::generate_ics(&notifications::IcsEvent {
+        instance_id: &event.instance_id,
+        title: &event.title,
+        notes: &event.notes,
+        link: event.link.as_deref(),
+        start_utc: event.start_utc,
+        end_utc: event.end_utc,
+        method: if cancelled { "CANCEL" } else { "REQUEST" },
+        sequence: if cancelled { 2 } else { 1 },
+        cancelled,
+        reminder_hours: None,
+    }))
 }
 
-/// List events for public calendar (no auth required)
+/// List events for public calendar (no auth required); internal-only events are left out
 #[query]
 fn list_events_public(window_start: u64, window_end: u64) -> Vec<PublicEventView> {
     let events = recurrence::materialize_events(window_start, window_end);
-    events.into_iter().map(|e| {
+    events.into_iter().filter(|e| e.public).map(|e| {
         let host_name = e.host_principal
             .and_then(|p| storage::get_user(&p))
             .map(|u| u.name);
         
         PublicEventView {
-            instance_id: e.instance_id.to_vec(),
+            instance_id: e.instance_id.into(),
             title: e.title,
             notes: e.notes,
             link: e.link,
             start_utc: e.start_utc,
             end_utc: e.end_utc,
             host_name,
+            is_assigned: e.host_principal.is_some(),
+            rsvp_count: e.rsvp_count,
             status: e.status,
             color: e.color,
         }
@@ -252,26 +438,183 @@ fn list_events_public(window_start: u64, window_end: u64) -> Vec<PublicEventView
 /// List events for authenticated users
 #[query]
 fn list_events(window_start: u64, window_end: u64) -> ApiResult<Vec<EventInstance>> {
-    auth::require_authorized()?;
-    Ok(recurrence::materialize_events(window_start, window_end))
+    let user = auth::require_viewer()?;
+    let mut events = recurrence::materialize_events(window_start, window_end);
+    // Assignment attribution is for admins only
+    if user.map(|u| u.role) != Some(Role::Admin) {
+        for e in &mut events {
+            e.assigned_by = None;
+            e.assigned_at = None;
+        }
+    }
+    Ok(events)
+}
+
+/// Page through upcoming events in start order, `limit` at a time (capped at
+/// recurrence::MAX_EVENTS_PAGE_SIZE). Pass the returned cursor to get the next page.
+#[query]
+fn list_events_page(after_start_utc: Option<u64>, limit: u32) -> ApiResult<EventsPage> {
+    let user = auth::require_viewer()?;
+    let mut page = recurrence::events_page(after_start_utc, limit);
+    // Assignment attribution is for admins only
+    if user.map(|u| u.role) != Some(Role::Admin) {
+        for e in &mut page.events {
+            e.assigned_by = None;
+            e.assigned_at = None;
+        }
+    }
+    Ok(page)
+}
+
+/// List the sessions a given principal hosts in the window (admin only)
+#[query]
+fn list_events_for_host(host: Principal, window_start: u64, window_end: u64) -> ApiResult<Vec<EventInstance>> {
+    auth::require_admin()?;
+    
+    if window_start >= window_end {
+        return Err(ApiError::InvalidInput("window_end must be after window_start".to_string()));
+    }
+    
+    Ok(recurrence::materialize_events(window_start, window_end)
+        .into_iter()
+        .filter(|e| e.host_principal == Some(host))
+        .collect())
 }
 
 /// List unclaimed events within forward window
 #[query]
 fn list_unclaimed_events() -> ApiResult<Vec<EventInstance>> {
-    auth::require_authorized()?;
+    auth::require_viewer()?;
     Ok(recurrence::list_unclaimed_events())
 }
 
+/// Next upcoming occurrence of a series with no host, within the forward window
+#[query]
+fn next_unclaimed_in_series(series_id: IdBytes) -> ApiResult<Option<EventInstance>> {
+    auth::require_authorized()?;
+    
+    let sid = series_id.0;
+    if storage::get_series(&sid).is_none() {
+        return Err(ApiError::NotFound);
+    }
+    
+    Ok(recurrence::list_unclaimed_events()
+        .into_iter()
+        .find(|e| e.series_id == Some(sid)))
+}
+
+/// Previous and next (non-cancelled) occurrence starts of a series around one occurrence
+#[query]
+fn adjacent_occurrences(series_id: IdBytes, occurrence_start: u64) -> ApiResult<AdjacentOccurrences> {
+    auth::require_authorized()?;
+    
+    let sid = series_id.0;
+    let series = storage::get_series(&sid).ok_or(ApiError::NotFound)?;
+    
+    Ok(recurrence::adjacent_occurrences(&series, occurrence_start))
+}
+
+/// Raw occurrence starts the recurrence engine generates for a series in the
+/// window, before overrides or cancellations are applied (admin only)
+/// Windows are capped at a year.
+#[query]
+fn debug_occurrences(series_id: IdBytes, window_start: u64, window_end: u64) -> ApiResult<Vec<u64>> {
+    auth::require_admin()?;
+    
+    if window_start >= window_end {
+        return Err(ApiError::InvalidInput("window_end must be after window_start".to_string()));
+    }
+    if window_end - window_start > 366 * 86400 * 1_000_000_000 {
+        return Err(ApiError::InvalidInput("Window can span at most a year".to_string()));
+    }
+    
+    let sid = series_id.0;
+    let series = storage::get_series(&sid).ok_or(ApiError::NotFound)?;
+    
+    Ok(recurrence::generate_occurrences(&series, window_start, window_end))
+}
+
+/// Peak number of sessions already scheduled at the same time within a slot
+/// Lets the UI warn before creating a session that would exceed the concurrency cap
+#[query]
+fn get_slot_concurrency(start_utc: u64, end_utc: u64) -> ApiResult<u32> {
+    auth::require_authorized()?;
+    
+    if start_utc >= end_utc {
+        return Err(ApiError::InvalidInput("End time must be after start time".to_string()));
+    }
+    
+    Ok(validation::peak_concurrency(start_utc, end_utc))
+}
+
 /// Create a one-off event
+/// A host, if given, must be a known user and (for non-admins) available.
+/// Admins may set `force` to start outside business hours. Exceeding an
+/// unenforced concurrency cap is reported in `warnings`.
+#[update]
+fn create_one_off_event(input: CreateEventInput, force: bool) -> ApiResult<CreatedEvent> {
+    let user = auth::require_authorized()?;
+    create_one_off_event_for(&user, input, force)
+}
+
+/// Most events one `create_one_off_events` call may create
+const MAX_BULK_EVENTS: usize = 100;
+
+/// Create many one-off events at once (at most MAX_BULK_EVENTS)
+/// Each input is handled independently: failures are reported by index in
+/// `failed` without affecting the others.
 #[update]
-fn create_one_off_event(input: CreateEventInput) -> ApiResult<EventInstance> {
+fn create_one_off_events(inputs: Vec<CreateEventInput>) -> ApiResult<BulkCreateEventsResult> {
     let user = auth::require_authorized()?;
+    if inputs.len() > MAX_BULK_EVENTS {
+        return Err(ApiError::InvalidInput(format!(
+            "At most {} events can be created at once", MAX_BULK_EVENTS
+        )));
+    }
+    
+    let mut result = BulkCreateEventsResult { created: vec![], failed: vec![], warnings: vec![] };
+    
+    for (index, input) in inputs.into_iter().enumerate() {
+        match create_one_off_event_for(&user, input, false) {
+            Ok(created) => {
+                result.warnings.extend(created.warnings.into_iter().map(|w| format!("Event {}: {}", index, w)));
+                result.created.push(created.event);
+            }
+            Err(error) => result.failed.push(FailedEventInput { index: index as u32, error }),
+        }
+    }
+    
+    Ok(result)
+}
+
+fn create_one_off_event_for(user: &User, input: CreateEventInput, force: bool) -> ApiResult<CreatedEvent> {
     let now = ic_cdk::api::time();
     
     if input.start_utc >= input.end_utc {
         return Err(ApiError::InvalidInput("End time must be after start time".to_string()));
     }
+    validation::check_length("title", &input.title, validation::MAX_TITLE_LEN)?;
+    validation::check_length("notes", &input.notes, validation::MAX_NOTES_LEN)?;
+    
+    let settings = storage::get_settings();
+    validation::check_duration(&settings, (input.end_utc - input.start_utc) / (60 * 1_000_000_000))?;
+    let link = validation::validate_link(input.link)?;
+    validation::check_required_link(&settings, &link)?;
+    if !(force && user.role == Role::Admin) {
+        validation::check_business_hours(&settings, input.start_utc)?;
+    }
+    let host_user = match input.host_principal {
+        Some(host) => Some(storage::get_user(&host)
+            .ok_or(ApiError::InvalidInput("Host is not a known user".to_string()))?),
+        None => None,
+    };
+    // Admins may place a host regardless of availability, as with assign_host
+    if settings.validate_one_off_host && user.role != Role::Admin {
+        if let Some(ref host) = host_user {
+            validation::validate_host(&host.principal, input.start_utc, input.end_utc)?;
+        }
+    }
+    let warnings = validation::check_concurrency_cap(&settings, &[(input.start_utc, input.end_utc)])?;
     
     let instance = EventInstance {
         instance_id: recurrence::generate_uuid(),
@@ -280,41 +623,226 @@ fn create_one_off_event(input: CreateEventInput) -> ApiResult<EventInstance> {
         end_utc: input.end_utc,
         title: input.title,
         notes: input.notes,
-        link: input.link,
+        link,
         host_principal: input.host_principal,
         status: EventStatus::Active,
         color: None,
         created_at: now,
+        occurrence_start: None,
+        assigned_by: input.host_principal.map(|_| user.principal),
+        assigned_at: input.host_principal.map(|_| now),
+        created_by: Some(user.principal),
+        public: input.public.unwrap_or(true),
+        capacity: input.capacity,
+        rsvp_count: 0,
+        overridden_fields: vec![],
+        series_title: None,
     };
     
-    storage::insert_instance(instance.clone());
-    Ok(instance)
+    storage::try_insert_instance(instance.clone())?;
+    
+    if let Some(host) = host_user {
+        notifications::create_host_assigned_notification(
+            &host,
+            None,
+            &instance.instance_id,
+            instance.start_utc,
+            instance.end_utc,
+        );
+    }
+    
+    Ok(CreatedEvent { event: instance, warnings })
+}
+
+/// Update a one-off event's title, notes, link or times (admin or creator)
+/// The host is notified if the times change. As on create, admins may set
+/// `force` to move the start outside business hours.
+#[update]
+fn update_one_off_event(instance_id: IdBytes, input: UpdateInstanceInput, force: bool) -> ApiResult<EventInstance> {
+    let user = auth::require_authorized()?;
+    
+    let iid = instance_id.0;
+    
+    let mut inst = storage::get_instance(&iid)
+        .ok_or(ApiError::NotFound)?;
+    if user.role != Role::Admin && inst.created_by != Some(user.principal) {
+        return Err(ApiError::Unauthorized);
+    }
+    
+    if let Some(title) = input.title {
+        validation::check_length("title", &title, validation::MAX_TITLE_LEN)?;
+        inst.title = title;
+    }
+    if let Some(notes) = input.notes {
+        validation::check_length("notes", &notes, validation::MAX_NOTES_LEN)?;
+        inst.notes = notes;
+    }
+    if let Some(link) = input.link {
+        let link = validation::validate_link(link)?;
+        validation::check_required_link(&storage::get_settings(), &link)?;
+        inst.link = link;
+    }
+    if let Some(public) = input.public {
+        inst.public = public;
+    }
+    if let Some(capacity) = input.capacity {
+        inst.capacity = capacity;
+    }
+    
+    let start_utc = input.start_utc.unwrap_or(inst.start_utc);
+    let end_utc = input.end_utc.unwrap_or(inst.end_utc);
+    if start_utc >= end_utc {
+        return Err(ApiError::InvalidInput("End time must be after start time".to_string()));
+    }
+    let time_changed = start_utc != inst.start_utc || end_utc != inst.end_utc;
+    if time_changed {
+        validation::check_duration(&storage::get_settings(), (end_utc - start_utc) / (60 * 1_000_000_000))?;
+    }
+    if start_utc != inst.start_utc && !(force && user.role == Role::Admin) {
+        validation::check_business_hours(&storage::get_settings(), start_utc)?;
+    }
+    inst.start_utc = start_utc;
+    inst.end_utc = end_utc;
+    
+    storage::try_insert_instance(inst.clone())?;
+    
+    if time_changed {
+        if let Some(host) = inst.host_principal.and_then(|p| storage::get_user(&p)) {
+            notifications::create_instance_time_changed_notification(
+                &host,
+                &inst.instance_id,
+                inst.series_id,
+                &inst.title,
+                inst.start_utc,
+                inst.end_utc,
+            );
+        }
+    }
+    
+    Ok(recurrence::with_rsvp_count(inst))
 }
 
 
 // ============================================================================
-// Event Series (Admin only)
+// RSVPs
 // ============================================================================
 
-/// Create a recurring event series (admin only)
+/// RSVP the caller to an upcoming session; rejected when the session is full
+/// RSVPing twice is a no-op.
 #[update]
-fn create_event_series(input: CreateSeriesInput) -> ApiResult<EventSeries> {
-    let admin = auth::require_admin()?;
+fn rsvp(instance_id: IdBytes) -> ApiResult<EventInstance> {
+    let user = auth::require_authorized()?;
+    
+    let iid = instance_id.0;
+    let mut event = recurrence::find_event(&iid)
+        .filter(|e| e.status == EventStatus::Active)
+        .ok_or(ApiError::NotFound)?;
+    if event.end_utc <= ic_cdk::api::time() {
+        return Err(ApiError::InvalidInput("Session has already ended".to_string()));
+    }
+    
+    let mut rsvps = storage::get_rsvps(&iid);
+    if !rsvps.attendees.contains(&user.principal) {
+        if event.capacity.is_some_and(|cap| rsvps.attendees.len() as u32 >= cap) {
+            return Err(ApiError::Conflict("Session is full".to_string()));
+        }
+        rsvps.attendees.push(user.principal);
+        event.rsvp_count = rsvps.attendees.len() as u32;
+        storage::try_insert_rsvps(&iid, rsvps)?;
+    }
+    
+    Ok(event)
+}
+
+/// Set or clear (None or empty) the caller's private prep note for a session they host
+#[update]
+fn set_host_note(instance_id: IdBytes, note: Option<String>) -> ApiResult<()> {
+    let user = auth::require_authorized()?;
+    
+    let iid = instance_id.0;
+    let event = recurrence::find_event(&iid).ok_or(ApiError::NotFound)?;
+    if event.host_principal != Some(user.principal) {
+        return Err(ApiError::Unauthorized);
+    }
+    
+    match note.filter(|n| !n.trim().is_empty()) {
+        Some(note) => {
+            validation::check_length("note", &note, validation::MAX_NOTES_LEN)?;
+            storage::try_insert_host_note(&iid, &user.principal, HostNote {
+                note,
+                updated_at: ic_cdk::api::time(),
+            })
+        }
+        None => {
+            storage::delete_host_note(&iid, &user.principal);
+            Ok(())
+        }
+    }
+}
+
+/// Get a host's private note for a session: the caller's own, or (admins only) another host's
+#[query]
+fn get_host_note(instance_id: IdBytes, host: Option<Principal>) -> ApiResult<Option<HostNote>> {
+    let user = auth::require_authorized()?;
+    
+    let host = host.unwrap_or(user.principal);
+    if host != user.principal && user.role != Role::Admin {
+        return Err(ApiError::Unauthorized);
+    }
+    Ok(storage::get_host_note(&instance_id.0, &host))
+}
+
+/// Withdraw the caller's RSVP to a session; a no-op if they hadn't RSVPed
+#[update]
+fn cancel_rsvp(instance_id: IdBytes) -> ApiResult<EventInstance> {
+    let user = auth::require_authorized()?;
+    
+    let iid = instance_id.0;
+    let mut event = recurrence::find_event(&iid)
+        .ok_or(ApiError::NotFound)?;
+    
+    let mut rsvps = storage::get_rsvps(&iid);
+    rsvps.attendees.retain(|p| *p != user.principal);
+    event.rsvp_count = rsvps.attendees.len() as u32;
+    storage::try_insert_rsvps(&iid, rsvps)?;
+    
+    Ok(event)
+}
+
+
+// ============================================================================
+// Event Series (ManageSeries)
+// ============================================================================
+
+/// Create a recurring event series (requires ManageSeries)
+/// Unless `force` is set, creation is rejected if the series' occurrences
+/// start at the same time as an existing series'. Admins may also use `force`
+/// to start outside business hours. Exceeding an unenforced concurrency cap is
+/// reported in `warnings`.
+#[update]
+fn create_event_series(input: CreateSeriesInput, force: bool) -> ApiResult<CreatedSeries> {
+    let admin = auth::require_capability(Capability::ManageSeries)?;
     let now = ic_cdk::api::time();
     let settings = storage::get_settings();
     
-    // Validate monthly requires ordinal
-    if input.frequency == Frequency::Monthly && input.weekday_ordinal.is_none() {
-        return Err(ApiError::InvalidInput(
-            "Monthly frequency requires weekday_ordinal".to_string()
-        ));
+    validation::check_weekday_ordinal(&input.frequency, &input.weekday_ordinal)?;
+    validation::check_series_dates(input.start_date, input.end_date, now)?;
+    
+    validation::check_length("title", &input.title, validation::MAX_TITLE_LEN)?;
+    validation::check_length("notes", &input.notes, validation::MAX_NOTES_LEN)?;
+    if let Some(template) = &input.notes_template {
+        validation::check_length("notes_template", template, validation::MAX_NOTES_LEN)?;
     }
+    let link = validation::validate_link(input.link)?;
+    validation::check_required_link(&settings, &link)?;
+    let color = validation::normalize_color(input.color)?;
     
     let series = EventSeries {
         series_id: recurrence::generate_uuid(),
         title: input.title,
         notes: input.notes,
-        link: input.link,
+        notes_template: input.notes_template,
+        link,
         frequency: input.frequency,
         weekday: input.weekday,
         weekday_ordinal: input.weekday_ordinal,
@@ -322,129 +850,613 @@ fn create_event_series(input: CreateSeriesInput) -> ApiResult<EventSeries> {
         end_date: input.end_date,
         default_duration_minutes: input.default_duration_minutes
             .unwrap_or(settings.default_event_duration_minutes),
-        color: input.color,
+        color,
         paused: false,
+        claims_paused: false,
+        deleted: false,
+        public: input.public.unwrap_or(true),
+        capacity: input.capacity,
         default_host: input.default_host,
+        reminder_hours_before_override: input.reminder_hours_before_override,
         created_at: now,
         created_by: admin.principal,
     };
     
-    storage::insert_series(series.clone());
+    if !force {
+        validation::check_series_collision(&series, now)?;
+    }
+    if !(force && auth::is_admin(&admin.principal)) {
+        validation::check_business_hours(&settings, series.start_date)?;
+    }
+    validation::check_duration(&settings, series.default_duration_minutes as u64)?;
+    
+    // Preview upcoming occurrences within the forward window against the concurrency cap
+    let mut warnings = Vec::new();
+    if settings.max_concurrent_sessions.is_some() {
+        let window_end = recurrence::calculate_window_end(now, settings.forward_window_months);
+        let duration_nanos = series.default_duration_minutes as u64 * 60 * 1_000_000_000;
+        let slots: Vec<(u64, u64)> = recurrence::generate_occurrences(&series, now, window_end)
+            .into_iter()
+            .map(|occ| (occ, occ + duration_nanos))
+            .collect();
+        warnings = validation::check_concurrency_cap(&settings, &slots)?;
+    }
+    
+    storage::try_insert_series(series.clone())?;
+    notifications::notify_imminent_unclaimed(&series);
+    audit::record(
+        admin.principal,
+        AuditAction::SeriesCreated,
+        hex::encode(series.series_id),
+        format!("title={}", series.title),
+    );
+    Ok(CreatedSeries { series, warnings })
+}
+
+/// Turn a one-off event into a recurring series (requires ManageSeries)
+/// The series starts at the one-off's start, on its (UTC) weekday, with the
+/// same duration, title, notes, link and color, and the one-off becomes its
+/// first occurrence: the host (kept via an override), RSVPs and host notes move
+/// over, and the host's calendar entry is swapped for the occurrence's.
+/// Monthly series occur at midnight UTC, so only a one-off starting then on
+/// the chosen ordinal weekday can become one. As on create, admins may set
+/// `force` to start the series outside business hours.
+#[update]
+fn convert_one_off_to_series(instance_id: IdBytes, input: ConvertToSeriesInput, force: bool) -> ApiResult<EventSeries> {
+    let admin = auth::require_capability(Capability::ManageSeries)?;
+    let now = ic_cdk::api::time();
+    
+    let iid = instance_id.0;
+    let instance = storage::get_instance(&iid)
+        .ok_or(ApiError::NotFound)?;
+    
+    validation::check_weekday_ordinal(&input.frequency, &input.weekday_ordinal)?;
+    if input.end_date.is_some_and(|end| end <= instance.start_utc) {
+        return Err(ApiError::InvalidInput("end_date must be after the event start".to_string()));
+    }
+    
+    let duration_minutes = (instance.end_utc.saturating_sub(instance.start_utc) / 60_000_000_000) as u32;
+    let series = EventSeries {
+        series_id: recurrence::generate_uuid(),
+        title: instance.title.clone(),
+        notes: instance.notes.clone(),
+        notes_template: None,
+        link: instance.link.clone(),
+        frequency: input.frequency,
+        weekday: datetime::weekday_of(instance.start_utc),
+        weekday_ordinal: input.weekday_ordinal,
+        start_date: instance.start_utc,
+        end_date: input.end_date,
+        default_duration_minutes: duration_minutes.max(1),
+        color: instance.color.clone(),
+        paused: false,
+        claims_paused: false,
+        deleted: false,
+        public: instance.public,
+        capacity: instance.capacity,
+        default_host: None,
+        reminder_hours_before_override: None,
+        created_at: now,
+        created_by: admin.principal,
+    };
+    
+    // Two months always contains the first occurrence, even for monthly series
+    let search_end = series.start_date + 62 * 86400 * 1_000_000_000;
+    let first = recurrence::generate_occurrences(&series, series.start_date, search_end)
+        .into_iter()
+        .next();
+    if first != Some(instance.start_utc) {
+        return Err(ApiError::InvalidInput(
+            "The event's start is not an occurrence of this series (monthly series occur at midnight UTC)".to_string()
+        ));
+    }
+    if !(force && auth::is_admin(&admin.principal)) {
+        validation::check_business_hours(&storage::get_settings(), series.start_date)?;
+    }
+    
+    storage::try_insert_series(series.clone())?;
+    let occ_start = instance.start_utc;
+    let new_iid = recurrence::generate_instance_id(&series.series_id, occ_start);
+    
+    if let Some(host) = instance.host_principal {
+        storage::try_insert_override(InstanceOverride {
+            host_principal: Some(host),
+            assigned_by: instance.assigned_by,
+            assigned_at: instance.assigned_at,
+            ..InstanceOverride::new(series.series_id, occ_start, admin.principal, now)
+        })?;
+    }
+    
+    recurrence::move_instance_data(&std::collections::HashMap::from([(iid, new_iid)]));
+    storage::delete_instance(&iid);
+    notifications::cancel_pending_for_instances(&[iid]);
+    
+    // The host's calendar holds the one-off's UID: withdraw it and invite
+    // them to the occurrence that replaces it
+    if let Some(host) = instance.host_principal.and_then(|p| storage::get_user(&p)) {
+        notifications::create_instance_cancelled_notification(
+            &host, &iid, None, &instance.title, instance.start_utc, instance.end_utc,
+        );
+        notifications::create_host_assigned_notification(
+            &host, Some(&series), &new_iid, instance.start_utc, instance.end_utc,
+        );
+    }
+    audit::record(
+        admin.principal,
+        AuditAction::SeriesCreated,
+        hex::encode(series.series_id),
+        format!("title={} (converted from one-off {})", series.title, hex::encode(iid)),
+    );
     Ok(series)
 }
 
-/// Update a recurring event series (admin only)
+/// Update a recurring event series (requires ManageSeries)
 #[update]
-fn update_event_series(series_id: Vec<u8>, input: UpdateSeriesInput) -> ApiResult<EventSeries> {
-    auth::require_admin()?;
+fn update_event_series(series_id: IdBytes, input: UpdateSeriesInput) -> ApiResult<EventSeries> {
+    let admin = auth::require_capability(Capability::ManageSeries)?;
     
-    let sid: [u8; 16] = series_id.try_into()
-        .map_err(|_| ApiError::InvalidInput("Invalid series_id".to_string()))?;
+    let sid = series_id.0;
     
     let mut series = storage::get_series(&sid)
         .ok_or(ApiError::NotFound)?;
+    let was_paused = series.paused;
+    let upcoming_before = upcoming_series_instance_ids(&sid);
     
     if let Some(title) = input.title {
+        validation::check_length("title", &title, validation::MAX_TITLE_LEN)?;
         series.title = title;
     }
     if let Some(notes) = input.notes {
+        validation::check_length("notes", &notes, validation::MAX_NOTES_LEN)?;
         series.notes = notes;
     }
     if let Some(end_date) = input.end_date {
+        if end_date.is_some_and(|end| end < series.start_date) {
+            return Err(ApiError::InvalidInput("end_date cannot be before start_date".to_string()));
+        }
         series.end_date = end_date;
     }
     if let Some(duration) = input.default_duration_minutes {
+        validation::check_duration(&storage::get_settings(), duration as u64)?;
         series.default_duration_minutes = duration;
     }
     if let Some(color) = input.color {
-        series.color = color;
+        series.color = validation::normalize_color(color)?;
     }
     if let Some(paused) = input.paused {
         series.paused = paused;
     }
+    if let Some(claims_paused) = input.claims_paused {
+        series.claims_paused = claims_paused;
+    }
     if let Some(default_host) = input.default_host {
         series.default_host = default_host;
     }
+    if let Some(reminder) = input.reminder_hours_before_override {
+        series.reminder_hours_before_override = reminder;
+    }
+    if let Some(public) = input.public {
+        series.public = public;
+    }
+    if let Some(capacity) = input.capacity {
+        series.capacity = capacity;
+    }
+    if let Some(template) = input.notes_template {
+        if let Some(t) = &template {
+            validation::check_length("notes_template", t, validation::MAX_NOTES_LEN)?;
+        }
+        series.notes_template = template;
+    }
+    
+    storage::try_insert_series(series.clone())?;
+    if series.paused && !was_paused {
+        notifications::cancel_pending_for_series(&sid);
+    } else {
+        // Date or recurrence changes can drop occurrences
+        let upcoming_after = upcoming_series_instance_ids(&sid);
+        let dropped: Vec<[u8; 16]> = upcoming_before
+            .into_iter()
+            .filter(|id| !upcoming_after.contains(id))
+            .collect();
+        notifications::cancel_pending_for_instances(&dropped);
+    }
+    audit::record(
+        admin.principal,
+        AuditAction::SeriesUpdated,
+        hex::encode(series.series_id),
+        format!("title={}", series.title),
+    );
+    Ok(series)
+}
+
+/// Instance ids of a series' occurrences from now through the forward window
+fn upcoming_series_instance_ids(series_id: &[u8; 16]) -> Vec<[u8; 16]> {
+    let now = ic_cdk::api::time();
+    let window_end = recurrence::calculate_window_end(now, storage::get_settings().forward_window_months);
+    recurrence::materialize_events(now, window_end)
+        .into_iter()
+        .filter(|e| e.series_id == Some(*series_id))
+        .map(|e| e.instance_id)
+        .collect()
+}
+
+/// Stop a series from a date forward, notifying hosts of dropped occurrences
+/// (requires ManageSeries). Returns how many hosts were notified.
+#[update]
+fn end_series_at(series_id: IdBytes, effective_date: u64) -> ApiResult<u32> {
+    let admin = auth::require_capability(Capability::ManageSeries)?;
+    
+    let sid = series_id.0;
+    
+    let notified = coverage::end_series_at(sid, effective_date)?;
+    audit::record(
+        admin.principal,
+        AuditAction::SeriesUpdated,
+        hex::encode(sid),
+        format!("ended at {}; {} hosts notified", datetime::format_iso8601(effective_date), notified),
+    );
+    Ok(notified)
+}
+
+/// Delete a recurring event series (requires ManageSeries)
+/// The series is hidden but kept, with its overrides, until restored or purged.
+#[update]
+fn delete_event_series(series_id: IdBytes) -> ApiResult<()> {
+    let admin = auth::require_capability(Capability::ManageSeries)?;
+    
+    let sid = series_id.0;
+    
+    let mut series = storage::get_series(&sid)
+        .ok_or(ApiError::NotFound)?;
+    series.deleted = true;
+    storage::insert_series(series);
+    notifications::cancel_pending_for_series(&sid);
+    
+    audit::record(admin.principal, AuditAction::SeriesDeleted, hex::encode(sid), String::new());
+    Ok(())
+}
+
+/// Restore a soft-deleted event series (requires ManageSeries)
+#[update]
+fn restore_event_series(series_id: IdBytes) -> ApiResult<EventSeries> {
+    let admin = auth::require_capability(Capability::ManageSeries)?;
+    
+    let sid = series_id.0;
     
+    let mut series = storage::get_series_including_deleted(&sid)
+        .filter(|s| s.deleted)
+        .ok_or(ApiError::NotFound)?;
+    series.deleted = false;
     storage::insert_series(series.clone());
+    
+    audit::record(admin.principal, AuditAction::SeriesRestored, hex::encode(sid), String::new());
     Ok(series)
 }
 
-/// Delete a recurring event series (admin only)
+/// Permanently delete an event series and its overrides (admin only)
+/// Works on live and soft-deleted series alike; cannot be undone.
 #[update]
-fn delete_event_series(series_id: Vec<u8>) -> ApiResult<()> {
-    auth::require_admin()?;
+fn purge_event_series(series_id: IdBytes) -> ApiResult<()> {
+    let admin = auth::require_admin()?;
     
-    let sid: [u8; 16] = series_id.try_into()
-        .map_err(|_| ApiError::InvalidInput("Invalid series_id".to_string()))?;
+    let sid = series_id.0;
     
     if !storage::delete_series(&sid) {
         return Err(ApiError::NotFound);
     }
-    
-    // Note: Overrides for this series remain orphaned but harmless
+    let removed = storage::delete_overrides_for_series(&sid);
+    notifications::cancel_pending_for_series(&sid);
+    
+    audit::record(
+        admin.principal,
+        AuditAction::SeriesPurged,
+        hex::encode(sid),
+        format!("{} override(s) removed", removed),
+    );
     Ok(())
 }
 
-/// List all event series (admin only)
+/// Enqueue coverage-needed notifications for a series' unclaimed occurrences
+/// starting soon (requires ManageSeries). Also runs automatically on creation.
+/// Returns the number of occurrences that need coverage.
+#[update]
+fn notify_series_coverage_needed(series_id: IdBytes) -> ApiResult<u32> {
+    auth::require_capability(Capability::ManageSeries)?;
+    
+    let sid = series_id.0;
+    
+    let series = storage::get_series(&sid)
+        .ok_or(ApiError::NotFound)?;
+    
+    Ok(notifications::notify_imminent_unclaimed(&series))
+}
+
+/// List all event series (requires ManageSeries)
 #[query]
 fn list_event_series() -> ApiResult<Vec<EventSeries>> {
-    auth::require_admin()?;
+    auth::require_capability(Capability::ManageSeries)?;
     Ok(storage::list_all_series())
 }
 
+/// List every override stored for a series, each with the occurrence it
+/// produces, in occurrence order (admin only)
+#[query]
+fn get_series_overrides(series_id: IdBytes) -> ApiResult<Vec<SeriesOverrideView>> {
+    auth::require_admin()?;
+    
+    let sid = series_id.0;
+    if storage::get_series_including_deleted(&sid).is_none() {
+        return Err(ApiError::NotFound);
+    }
+    
+    let namespace = recurrence::instance_namespace();
+    Ok(storage::list_overrides_for_series(&sid)
+        .into_iter()
+        .map(|entry| {
+            let occ_start = entry.occurrence_start_utc;
+            let instance_id = recurrence::instance_id_in(namespace.as_ref(), &sid, occ_start);
+            let materialized = coverage::get_event_instance(Some(sid), Some(occ_start), &instance_id).ok();
+            SeriesOverrideView { entry, materialized }
+        })
+        .collect())
+}
+
+/// List soft-deleted event series that can still be restored (requires ManageSeries)
+#[query]
+fn list_deleted_event_series() -> ApiResult<Vec<EventSeries>> {
+    auth::require_capability(Capability::ManageSeries)?;
+    Ok(storage::list_deleted_series())
+}
+
 
 // ============================================================================
 // Coverage Queue
 // ============================================================================
 
-/// Assign a host to an event instance
-/// series_id and occurrence_start are needed for series instances
-/// instance_id is used for one-off events
-#[update]
-fn assign_host(
-    series_id: Option<Vec<u8>>,
+/// Assign a host to an event instance
+/// series_id and occurrence_start are needed for series instances
+/// instance_id is used for one-off events
+#[update]
+fn assign_host(
+    series_id: Option<IdBytes>,
+    occurrence_start: Option<u64>,
+    instance_id: IdBytes,
+    host_principal: Principal,
+) -> ApiResult<EventInstance> {
+    let user = auth::require_authorized()?;
+    auth::touch_last_active(&user.principal);
+    
+    let sid = series_id.map(|id| id.0);
+    
+    let iid = instance_id.0;
+    
+    let is_admin = user.role == Role::Admin;
+    
+    coverage::assign_host(
+        sid,
+        occurrence_start,
+        iid,
+        host_principal,
+        user.principal,
+        is_admin,
+    )
+}
+
+/// Whether the caller could claim a session right now, and if not, why
+/// Disabled users get `allowed: false` rather than an error.
+#[query]
+fn can_i_claim(
+    series_id: Option<IdBytes>,
+    occurrence_start: Option<u64>,
+    instance_id: IdBytes,
+) -> ApiResult<ClaimEligibility> {
+    let principal = auth::require_authenticated()?;
+    let user = storage::get_user(&principal).ok_or(ApiError::Unauthorized)?;
+    if user.status == UserStatus::Disabled {
+        return Ok(ClaimEligibility { allowed: false, reason: Some("Your account is disabled".to_string()) });
+    }
+    
+    let sid = series_id.map(|id| id.0);
+    
+    Ok(coverage::claim_eligibility(sid, occurrence_start, instance_id.0, &user))
+}
+
+/// Assign one host to every upcoming occurrence of a series through `window_end` (admin only)
+/// Occurrences the host can't take are skipped and returned separately.
+#[update]
+fn assign_host_to_series(series_id: IdBytes, host_principal: Principal, window_end: u64) -> ApiResult<SeriesAssignmentResult> {
+    let admin = auth::require_admin()?;
+    
+    let sid = series_id.0;
+    
+    let now = ic_cdk::api::time();
+    let max_end = recurrence::calculate_window_end(now, storage::get_settings().forward_window_months);
+    if window_end <= now || window_end > max_end {
+        return Err(ApiError::InvalidInput("window_end must be in the future and within the forward window".to_string()));
+    }
+    
+    let result = coverage::assign_host_to_series(sid, host_principal, window_end, admin.principal)?;
+    audit::record(
+        admin.principal,
+        AuditAction::SeriesUpdated,
+        hex::encode(sid),
+        format!("assigned host {} to {} occurrence(s), skipped {}", host_principal, result.assigned.len(), result.skipped.len()),
+    );
+    Ok(result)
+}
+
+/// Remove a host from all of their upcoming sessions, e.g. when they leave (admin only)
+/// Returns the number of sessions cleared.
+#[update]
+fn unassign_host_everywhere(host_principal: Principal) -> ApiResult<u32> {
+    let admin = auth::require_admin()?;
+    
+    let count = coverage::unassign_host_everywhere(host_principal, admin.principal)?;
+    audit::record(
+        admin.principal,
+        AuditAction::UserUpdated,
+        host_principal.to_text(),
+        format!("unassigned from {} upcoming session(s)", count),
+    );
+    Ok(count)
+}
+
+/// Move all of one host's upcoming sessions, and their default-host series,
+/// to another host (admin only). Sessions the new host can't take are skipped.
+#[update]
+fn transfer_host(from: Principal, to: Principal, window_end: u64) -> ApiResult<HostTransferResult> {
+    let admin = auth::require_admin()?;
+    
+    let now = ic_cdk::api::time();
+    let max_end = recurrence::calculate_window_end(now, storage::get_settings().forward_window_months);
+    if window_end <= now || window_end > max_end {
+        return Err(ApiError::InvalidInput("window_end must be in the future and within the forward window".to_string()));
+    }
+    
+    let result = coverage::transfer_host(from, to, window_end, admin.principal)?;
+    audit::record(
+        admin.principal,
+        AuditAction::UserUpdated,
+        from.to_text(),
+        format!(
+            "transferred {} session(s) and {} series to {}, skipped {}",
+            result.transferred.len(), result.series_reassigned, to, result.skipped.len()
+        ),
+    );
+    Ok(result)
+}
+
+/// Set or clear the notes for a single series occurrence (requires ManageSeries)
+#[update]
+fn set_instance_notes(series_id: IdBytes, occurrence_start: u64, notes: Option<String>) -> ApiResult<EventInstance> {
+    let admin = auth::require_capability(Capability::ManageSeries)?;
+    
+    let sid = series_id.0;
+    if let Some(notes) = &notes {
+        validation::check_length("notes", notes, validation::MAX_NOTES_LEN)?;
+    }
+    
+    coverage::set_occurrence_text(sid, occurrence_start, Some(notes), None, admin.principal)
+}
+
+/// Set or clear the title for a single series occurrence (requires ManageSeries)
+#[update]
+fn set_instance_title(series_id: IdBytes, occurrence_start: u64, title: Option<String>) -> ApiResult<EventInstance> {
+    let admin = auth::require_capability(Capability::ManageSeries)?;
+    
+    let sid = series_id.0;
+    if let Some(title) = &title {
+        validation::check_length("title", title, validation::MAX_TITLE_LEN)?;
+    }
+    
+    coverage::set_occurrence_text(sid, occurrence_start, None, Some(title), admin.principal)
+}
+
+/// Set a single series occurrence's length in minutes, keeping its start (requires ManageSeries)
+#[update]
+fn set_instance_duration(series_id: IdBytes, occurrence_start: u64, minutes: u32) -> ApiResult<EventInstance> {
+    let admin = auth::require_capability(Capability::ManageSeries)?;
+    
+    let sid = series_id.0;
+    
+    let event = coverage::set_occurrence_duration(sid, occurrence_start, minutes, admin.principal, ic_cdk::api::time())?;
+    audit::record(
+        admin.principal,
+        AuditAction::SeriesUpdated,
+        hex::encode(sid),
+        format!("occurrence at {} set to {} minutes", datetime::format_iso8601(occurrence_start), minutes),
+    );
+    Ok(event)
+}
+
+/// Revert a series occurrence to the series defaults by deleting its override (admin only)
+#[update]
+fn reset_instance(series_id: IdBytes, occurrence_start: u64) -> ApiResult<EventInstance> {
+    let admin = auth::require_admin()?;
+    
+    let sid = series_id.0;
+    
+    let event = coverage::reset_occurrence(sid, occurrence_start)?;
+    audit::record(
+        admin.principal,
+        AuditAction::SeriesUpdated,
+        hex::encode(sid),
+        format!("reset occurrence at {}", datetime::format_iso8601(occurrence_start)),
+    );
+    Ok(event)
+}
+
+/// List the caller's own hosted sessions that overlap each other in the window
+#[query]
+fn list_my_conflicts(window_start: u64, window_end: u64) -> ApiResult<Vec<SessionConflict>> {
+    let user = auth::require_authorized()?;
+    
+    if window_start >= window_end {
+        return Err(ApiError::InvalidInput("window_end must be after window_start".to_string()));
+    }
+    
+    Ok(coverage::host_conflicts(&user.principal, window_start, window_end))
+}
+
+/// Suggest hosts for a time slot, best candidates first (admin only)
+/// Only users whose recurring availability covers the slot are returned
+#[query]
+fn suggest_hosts_for(start_utc: u64, end_utc: u64) -> ApiResult<Vec<UserDirectoryEntry>> {
+    auth::require_admin()?;
+    
+    if start_utc >= end_utc {
+        return Err(ApiError::InvalidInput("End time must be after start time".to_string()));
+    }
+    
+    Ok(coverage::suggest_hosts(start_utc, end_utc).into_iter().map(|u| directory_entry(u, true)).collect())
+}
+
+/// List users who can host a specific session (any authorized user)
+/// Admins see everyone, with those needing an override flagged.
+#[query]
+fn list_eligible_hosts(
+    series_id: Option<IdBytes>,
     occurrence_start: Option<u64>,
-    instance_id: Vec<u8>,
-    host_principal: Principal,
-) -> ApiResult<EventInstance> {
+    instance_id: IdBytes,
+) -> ApiResult<Vec<EligibleHost>> {
     let user = auth::require_authorized()?;
-    auth::touch_last_active(&user.principal);
     
-    let sid = series_id
-        .map(|v| v.try_into().map_err(|_| ApiError::InvalidInput("Invalid series_id".to_string())))
-        .transpose()?;
+    let sid = series_id.map(|id| id.0);
     
-    let iid: [u8; 16] = instance_id.try_into()
-        .map_err(|_| ApiError::InvalidInput("Invalid instance_id".to_string()))?;
+    let iid = instance_id.0;
     
-    let is_admin = user.role == Role::Admin;
+    coverage::eligible_hosts(sid, occurrence_start, &iid, user.role == Role::Admin)
+}
+
+/// List active users who are out of office at any point in a window (any authorized user)
+/// Admins also get the overlapping blocks and their reasons.
+#[query]
+fn list_unavailable_hosts(window_start: u64, window_end: u64) -> ApiResult<Vec<UserDirectoryEntry>> {
+    let caller = auth::require_authorized()?;
     
-    coverage::assign_host(
-        sid,
-        occurrence_start,
-        iid,
-        host_principal,
-        user.principal,
-        is_admin,
-    )
+    if window_start >= window_end {
+        return Err(ApiError::InvalidInput("window_end must be after window_start".to_string()));
+    }
+    let include_ooo = caller.role == Role::Admin;
+    
+    Ok(coverage::unavailable_hosts(window_start, window_end).into_iter().map(|mut u| {
+        u.out_of_office.retain(|b| window_start < b.end_utc && window_end > b.start_utc);
+        directory_entry(u, include_ooo)
+    }).collect())
 }
 
 /// Unassign host from an event instance
 #[update]
 fn unassign_host(
-    series_id: Option<Vec<u8>>,
+    series_id: Option<IdBytes>,
     occurrence_start: Option<u64>,
-    instance_id: Vec<u8>,
+    instance_id: IdBytes,
 ) -> ApiResult<EventInstance> {
     let user = auth::require_authorized()?;
     
-    let sid = series_id
-        .map(|v| v.try_into().map_err(|_| ApiError::InvalidInput("Invalid series_id".to_string())))
-        .transpose()?;
+    let sid = series_id.map(|id| id.0);
     
-    let iid: [u8; 16] = instance_id.try_into()
-        .map_err(|_| ApiError::InvalidInput("Invalid instance_id".to_string()))?;
+    let iid = instance_id.0;
     
     coverage::unassign_host(
         sid,
@@ -461,12 +1473,84 @@ fn unassign_host(
 
 /// Update global settings (admin only)
 #[update]
-fn update_global_settings(settings: GlobalSettings) -> ApiResult<()> {
-    auth::require_admin()?;
-    storage::update_settings(settings);
+fn update_global_settings(settings: GlobalSettings, expected_version: u64) -> ApiResult<()> {
+    let admin = auth::require_admin()?;
+    let previous = storage::get_settings();
+    if previous.version != expected_version {
+        return Err(ApiError::Conflict(
+            "Settings were changed by someone else. Reload and try again.".to_string(),
+        ));
+    }
+    write_global_settings(&admin, previous, settings)
+}
+
+/// Update only the provided global settings fields (admin only)
+/// Safe for clients that don't know about newer fields.
+#[update]
+fn patch_global_settings(patch: GlobalSettingsPatch) -> ApiResult<()> {
+    let admin = auth::require_admin()?;
+    let previous = storage::get_settings();
+    let mut settings = previous.clone();
+    
+    macro_rules! patch {
+        ($field:ident) => {
+            if let Some(value) = patch.$field {
+                settings.$field = value;
+            }
+        };
+    }
+    
+    patch!(forward_window_months);
+    patch!(claims_paused);
+    patch!(default_event_duration_minutes);
+    patch!(org_name);
+    patch!(org_tagline);
+    patch!(org_logo_url);
+    patch!(max_concurrent_sessions);
+    patch!(enforce_concurrency_cap);
+    patch!(validate_one_off_host);
+    patch!(require_link);
+    patch!(auto_cancel_past_unclaimed);
+    patch!(auto_cancel_grace_hours);
+    patch!(business_hours);
+    patch!(min_duration_minutes);
+    patch!(max_duration_minutes);
+    patch!(namespaced_instance_ids);
+    patch!(notify_admins_on_join);
+    patch!(org_default_notification_settings);
+    patch!(week_starts_on);
+    patch!(coverage_alert_threshold_pct);
+    patch!(min_claim_lead_minutes);
+    patch!(always_email_critical_removals);
+    patch!(org_timezone);
+    patch!(notification_templates);
+    
+    write_global_settings(&admin, previous, settings)
+}
+
+/// Validate and store new settings, recording the change and bumping the version
+fn write_global_settings(admin: &User, previous: GlobalSettings, mut settings: GlobalSettings) -> ApiResult<()> {
+    validation::validate_business_hours(settings.business_hours)?;
+    validation::validate_duration_limits(&settings)?;
+    validation::validate_coverage_alert_threshold(settings.coverage_alert_threshold_pct)?;
+    validation::validate_timezone(&settings.org_timezone)?;
+    validation::validate_notification_templates(&settings.notification_templates)?;
+    settings.version = previous.version + 1;
+    let rekey = (previous.namespaced_instance_ids, settings.namespaced_instance_ids);
+    storage::try_update_settings(settings.clone())?;
+    audit::record_settings_change(admin.principal, &previous, &settings, ic_cdk::api::time());
+    recurrence::rekey_instance_ids(rekey.0, rekey.1);
+    audit::record(admin.principal, AuditAction::SettingsUpdated, "global_settings".to_string(), String::new());
     Ok(())
 }
 
+/// List recent global settings changes, newest first (admin only)
+#[query]
+fn list_settings_history(limit: u32) -> ApiResult<Vec<SettingsChange>> {
+    auth::require_admin()?;
+    Ok(audit::settings_history(limit))
+}
+
 /// Get global settings
 #[query]
 fn get_global_settings() -> ApiResult<GlobalSettings> {
@@ -474,6 +1558,16 @@ fn get_global_settings() -> ApiResult<GlobalSettings> {
     Ok(storage::get_settings())
 }
 
+/// Schema and build version of this canister (no auth required)
+#[query]
+fn get_version() -> VersionInfo {
+    VersionInfo {
+        schema_version: storage::SCHEMA_VERSION,
+        package_version: env!("CARGO_PKG_VERSION").to_string(),
+        build: option_env!("OHS_BUILD").map(|b| b.to_string()),
+    }
+}
+
 /// Get org settings for public calendar (no auth required)
 #[query]
 fn get_org_settings() -> GlobalSettings {
@@ -489,84 +1583,350 @@ fn get_coverage_history(months_back: u8) -> ApiResult<Vec<CoverageStats>> {
     let mut stats = Vec::new();
     
     for i in 0..months_back {
-        let (window_start, window_end, label) = recurrence::month_window(now, i);
-        let events = recurrence::materialize_events(window_start, window_end);
-        let total = events.len() as u32;
-        let assigned = events.iter().filter(|e| e.host_principal.is_some()).count() as u32;
-        let unassigned = total - assigned;
-        let coverage_pct = if total > 0 { (assigned as f64 / total as f64) * 100.0 } else { 0.0 };
-        
-        stats.push(CoverageStats {
-            period_label: label,
-            total_sessions: total,
-            assigned,
-            unassigned,
-            coverage_pct,
-        });
+        let (window_start, window_end, label) = datetime::month_window(now, i);
+        stats.push(stats::coverage_stats(window_start, window_end, label));
+    }
+    
+    stats.reverse(); // oldest first
+    Ok(stats)
+}
+
+/// Get coverage history for past N weeks, starting on the configured weekday (admin only)
+#[query]
+fn get_coverage_history_weekly(weeks_back: u8) -> ApiResult<Vec<CoverageStats>> {
+    auth::require_admin()?;
+    
+    let now = ic_cdk::api::time();
+    let week_start = storage::get_settings().week_starts_on;
+    let mut stats = Vec::new();
+    
+    for i in 0..weeks_back {
+        let (window_start, window_end, label) = datetime::week_window(now, i, week_start);
+        stats.push(stats::coverage_stats(window_start, window_end, label));
     }
     
     stats.reverse(); // oldest first
     Ok(stats)
 }
 
-/// Toggle pause/resume on a series (admin only)
+/// Cancel past series occurrences that ended without a host (admin only)
+/// Uses the configured grace period whether or not auto-cancel is enabled.
 #[update]
-fn toggle_series_pause(series_id: Vec<u8>) -> ApiResult<EventSeries> {
+fn sweep_past_unclaimed() -> ApiResult<u32> {
+    let admin = auth::require_admin()?;
+    let settings = storage::get_settings();
+    Ok(maintenance::sweep_past_unclaimed(ic_cdk::api::time(), settings.auto_cancel_grace_hours, admin.principal))
+}
+
+/// Run the next-week coverage alert check now, ignoring the once-per-week limit (admin only)
+/// Returns the number of admin notifications enqueued.
+#[update]
+fn check_coverage_alert() -> ApiResult<u32> {
+    auth::require_admin()?;
+    let threshold = storage::get_settings().coverage_alert_threshold_pct
+        .ok_or(ApiError::InvalidInput("No coverage alert threshold is configured".to_string()))?;
+    Ok(notifications::send_coverage_alert(ic_cdk::api::time(), threshold))
+}
+
+/// Aggregate counts and memory usage for monitoring (admin only)
+#[query]
+fn get_metrics() -> ApiResult<CanisterMetrics> {
+    auth::require_admin()?;
+    Ok(maintenance::metrics())
+}
+
+/// Summarize data that needs cleanup, with suggested actions (admin only)
+#[query]
+fn get_maintenance_report() -> ApiResult<MaintenanceReport> {
+    auth::require_admin()?;
+    Ok(maintenance::report(ic_cdk::api::time()))
+}
+
+/// List sessions with suspicious durations so they can be cleaned up (admin only)
+#[query]
+fn list_anomalous_sessions(window_start: u64, window_end: u64) -> ApiResult<Vec<AnomalousSession>> {
+    auth::require_admin()?;
+    
+    if window_start >= window_end {
+        return Err(ApiError::InvalidInput("window_end must be after window_start".to_string()));
+    }
+    
+    Ok(stats::anomalous_sessions(window_start, window_end))
+}
+
+/// Sessions hosted and hours per host within a window (admin only)
+#[query]
+fn get_host_stats(window_start: u64, window_end: u64) -> ApiResult<Vec<HostStats>> {
+    auth::require_admin()?;
+    
+    if window_start >= window_end {
+        return Err(ApiError::InvalidInput("window_end must be after window_start".to_string()));
+    }
+    
+    Ok(stats::host_stats(window_start, window_end))
+}
+
+/// Which series a host covers and how often (requires ManageSeries)
+#[query]
+fn host_series_summary(principal: Principal) -> ApiResult<Vec<HostSeriesCount>> {
+    auth::require_capability(Capability::ManageSeries)?;
+    Ok(stats::host_series_summary(&principal))
+}
+
+/// Average time between claim and start for a series' occurrences (admin only)
+#[query]
+fn get_claim_lead_times(series_id: IdBytes, window_start: u64, window_end: u64) -> ApiResult<ClaimLeadTimes> {
+    auth::require_admin()?;
+    
+    let sid = series_id.0;
+    if storage::get_series(&sid).is_none() {
+        return Err(ApiError::NotFound);
+    }
+    if window_start >= window_end {
+        return Err(ApiError::InvalidInput("window_end must be after window_start".to_string()));
+    }
+    
+    Ok(stats::claim_lead_times(&sid, window_start, window_end))
+}
+
+/// List sessions in a window that have no meeting link (admin only)
+/// Used to find sessions that don't comply with `require_link`.
+#[query]
+fn list_sessions_missing_link(window_start: u64, window_end: u64) -> ApiResult<Vec<EventInstance>> {
+    auth::require_admin()?;
+    
+    Ok(recurrence::materialize_events(window_start, window_end)
+        .into_iter()
+        .filter(|e| !validation::has_link(&e.link))
+        .collect())
+}
+
+/// Unclaimed sessions from now through `days_ahead` days (admin only)
+/// Drives the dashboard "uncovered sessions" badge.
+#[query]
+fn get_upcoming_gaps(days_ahead: u32) -> ApiResult<CoverageGaps> {
     auth::require_admin()?;
     
-    let sid: [u8; 16] = series_id.try_into()
-        .map_err(|_| ApiError::InvalidInput("Invalid series_id".to_string()))?;
+    if days_ahead == 0 || days_ahead > 366 {
+        return Err(ApiError::InvalidInput("days_ahead must be between 1 and 366".to_string()));
+    }
+    
+    let now = ic_cdk::api::time();
+    let horizon = now + days_ahead as u64 * 86400 * 1_000_000_000;
+    let events = recurrence::list_unclaimed_events_between(now, horizon);
+    
+    Ok(CoverageGaps {
+        count: events.len() as u32,
+        events,
+    })
+}
+
+/// Session counts per local day plus the busiest and quietest days (admin only)
+/// Days follow the org timezone, including daylight saving, when one is set.
+/// Otherwise `utc_offset_minutes` (e.g. -300 for EST) is applied as a fixed
+/// offset across the whole window.
+#[query]
+fn get_day_utilization(window_start: u64, window_end: u64, utc_offset_minutes: i32) -> ApiResult<DayUtilization> {
+    auth::require_admin()?;
+    
+    if window_start >= window_end {
+        return Err(ApiError::InvalidInput("window_end must be after window_start".to_string()));
+    }
+    if window_end - window_start > 366 * 86400 * 1_000_000_000 {
+        return Err(ApiError::InvalidInput("Window cannot exceed one year".to_string()));
+    }
+    if utc_offset_minutes.abs() > 14 * 60 {
+        return Err(ApiError::InvalidInput("Invalid UTC offset".to_string()));
+    }
+    
+    let timezone = storage::get_settings().org_timezone;
+    Ok(stats::day_utilization(window_start, window_end, timezone.as_deref(), utc_offset_minutes))
+}
+
+/// Toggle pause/resume on a series (requires ManageSeries)
+/// Reports how many upcoming occurrences it hides or restores; with `notify_hosts`
+/// (default false), hosts of occurrences hidden by pausing get a removal notice.
+#[update]
+fn toggle_series_pause(series_id: IdBytes, notify_hosts: Option<bool>) -> ApiResult<SeriesPauseResult> {
+    let admin = auth::require_capability(Capability::ManageSeries)?;
+    
+    let sid = series_id.0;
     
     let mut series = storage::get_series(&sid)
         .ok_or(ApiError::NotFound)?;
     
+    let now = ic_cdk::api::time();
+    let window_end = recurrence::calculate_window_end(now, storage::get_settings().forward_window_months);
+    let upcoming = || -> Vec<EventInstance> {
+        recurrence::materialize_events(now, window_end)
+            .into_iter()
+            .filter(|e| e.series_id == Some(sid))
+            .collect()
+    };
+    
+    // Paused series don't materialize, so count while the occurrences are visible
+    let pausing = !series.paused;
+    let affected = if pausing { upcoming() } else { vec![] };
     series.paused = !series.paused;
     storage::insert_series(series.clone());
-    Ok(series)
+    if pausing {
+        notifications::cancel_pending_for_series(&sid);
+    }
+    let affected = if pausing { affected } else { upcoming() };
+    let hosted: Vec<&EventInstance> = affected.iter().filter(|e| e.host_principal.is_some()).collect();
+    
+    if pausing && notify_hosts.unwrap_or(false) {
+        for event in &hosted {
+            if let Some(host) = event.host_principal.and_then(|p| storage::get_user(&p)) {
+                notifications::create_host_removed_notification(
+                    &host,
+                    &event.instance_id,
+                    event.series_id,
+                    event.start_utc,
+                    event.end_utc,
+                    true,
+                );
+            }
+        }
+    }
+    
+    audit::record(
+        admin.principal,
+        AuditAction::SeriesPauseToggled,
+        hex::encode(sid),
+        format!("paused={}, {} occurrence(s) affected, {} hosted", series.paused, affected.len(), hosted.len()),
+    );
+    Ok(SeriesPauseResult {
+        affected_occurrences: affected.len() as u32,
+        hosted_occurrences: hosted.len() as u32,
+        series,
+    })
+}
+
+/// Display name for an event's host in exports ("Unassigned" if none)
+fn export_host_name(users: &[User], host: Option<Principal>) -> String {
+    host
+        .and_then(|p| users.iter().find(|u| u.principal == p))
+        .map(|u| u.name.clone())
+        .unwrap_or_else(|| "Unassigned".to_string())
+}
+
+/// Quote a CSV field if it contains a delimiter, quote, or line break
+fn csv_escape(value: &str) -> String {
+    if value.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", value.replace('"', "\"\""))
+    } else {
+        value.to_string()
+    }
 }
 
 /// Export events as CSV (admin only)
+/// Dates and times are shifted by `offset_minutes` from UTC (e.g. -300 for EST),
+/// and the offset is noted in the time column header.
 #[query]
-fn export_events_csv(window_start: u64, window_end: u64) -> ApiResult<String> {
+fn export_events_csv(window_start: u64, window_end: u64, offset_minutes: i32) -> ApiResult<String> {
     auth::require_admin()?;
     
+    if offset_minutes.abs() > 14 * 60 {
+        return Err(ApiError::InvalidInput("Invalid UTC offset".to_string()));
+    }
+    
     let events = recurrence::materialize_events(window_start, window_end);
     let users = storage::list_all_users();
     
-    let mut csv = String::from("Date,Time (UTC),Title,Host,Status,Series,Duration (min)\n");
+    let offset_label = if offset_minutes == 0 {
+        "UTC".to_string()
+    } else {
+        let sign = if offset_minutes < 0 { '-' } else { '+' };
+        let abs = offset_minutes.unsigned_abs();
+        format!("UTC{}{:02}:{:02}", sign, abs / 60, abs % 60)
+    };
+    let offset_nanos = offset_minutes as i64 * 60 * 1_000_000_000;
+    
+    let mut csv = format!("Date,Time ({}),Title,Host,Status,Series,Duration (min),Link,Notes\n", offset_label);
     
     for e in &events {
-        let host_name = e.host_principal
-            .and_then(|p| users.iter().find(|u| u.principal == p))
-            .map(|u| u.name.clone())
-            .unwrap_or_else(|| "Unassigned".to_string());
+        let host_name = export_host_name(&users, e.host_principal);
         
         let status = if e.status == EventStatus::Active { "Active" } else { "Cancelled" };
         let is_series = if e.series_id.is_some() { "Yes" } else { "No" };
         let duration_min = (e.end_utc.saturating_sub(e.start_utc)) / 1_000_000_000 / 60;
         
-        // Format timestamp as ISO-ish date/time
-        let (y, m, d) = recurrence::nanos_to_ymd(e.start_utc);
-        let secs_in_day = (e.start_utc / 1_000_000_000) % 86400;
+        // Shift into the requested offset; the date rolls over with the time
+        let local_start = (e.start_utc as i64 + offset_nanos).max(0) as u64;
+        let (y, m, d) = datetime::nanos_to_ymd(local_start);
+        let secs_in_day = (local_start / 1_000_000_000) % 86400;
         let hour = secs_in_day / 3600;
         let min = (secs_in_day % 3600) / 60;
         
-        // Escape title for CSV
-        let title_escaped = if e.title.contains(',') || e.title.contains('"') {
-            format!("\"{}\"", e.title.replace('"', "\"\""))
-        } else {
-            e.title.clone()
-        };
-        
         csv.push_str(&format!(
-            "{:04}-{:02}-{:02},{:02}:{:02},{},{},{},{},{}\n",
-            y, m, d, hour, min, title_escaped, host_name, status, is_series, duration_min
+            "{:04}-{:02}-{:02},{:02}:{:02},{},{},{},{},{},{},{}\n",
+            y, m, d, hour, min,
+            csv_escape(&e.title),
+            csv_escape(&host_name),
+            status,
+            is_series,
+            duration_min,
+            csv_escape(e.link.as_deref().unwrap_or("")),
+            csv_escape(&e.notes),
         ));
     }
     
     Ok(csv)
 }
 
+/// Escape a string for inclusion in a JSON string literal
+fn json_escape(value: &str) -> String {
+    let mut out = String::with_capacity(value.len());
+    for c in value.chars() {
+        match c {
+            '"' => out.push_str("\\\""),
+            '\\' => out.push_str("\\\\"),
+            '\n' => out.push_str("\\n"),
+            '\r' => out.push_str("\\r"),
+            '\t' => out.push_str("\\t"),
+            c if (c as u32) < 0x20 => out.push_str(&format!("\\u{:04x}", c as u32)),
+            c => out.push(c),
+        }
+    }
+    out
+}
+
+/// Export events as a JSON array (admin only)
+/// Same events as the CSV export, with ISO-8601 UTC timestamps and host names resolved.
+#[query]
+fn export_events_json(window_start: u64, window_end: u64) -> ApiResult<String> {
+    auth::require_admin()?;
+    
+    let events = recurrence::materialize_events(window_start, window_end);
+    let users = storage::list_all_users();
+    
+    let entries: Vec<String> = events.iter().map(|e| {
+        let status = if e.status == EventStatus::Active { "Active" } else { "Cancelled" };
+        let optional = |v: Option<String>| match v {
+            Some(v) => format!("\"{}\"", json_escape(&v)),
+            None => "null".to_string(),
+        };
+        
+        format!(
+            "{{\"instance_id\":\"{}\",\"series_id\":{},\"title\":\"{}\",\"notes\":\"{}\",\"link\":{},\"start\":\"{}\",\"end\":\"{}\",\"host_principal\":{},\"host_name\":\"{}\",\"status\":\"{}\",\"color\":{}}}",
+            hex::encode(e.instance_id),
+            optional(e.series_id.map(hex::encode)),
+            json_escape(&e.title),
+            json_escape(&e.notes),
+            optional(e.link.clone()),
+            datetime::format_iso8601(e.start_utc),
+            datetime::format_iso8601(e.end_utc),
+            optional(e.host_principal.map(|p| p.to_text())),
+            json_escape(&export_host_name(&users, e.host_principal)),
+            status,
+            optional(e.color.clone()),
+        )
+    }).collect();
+    
+    Ok(format!("[{}]", entries.join(",")))
+}
+
 // ============================================================================
 // Notifications
 // ============================================================================
@@ -578,16 +1938,39 @@ fn list_pending_notifications() -> ApiResult<Vec<NotificationJob>> {
     Ok(storage::list_pending_notifications())
 }
 
+/// List notifications in a status, optionally only of one type and/or for one recipient
+/// (for specialized workers). `limit` is capped at notifications::MAX_NOTIFICATION_PAGE_SIZE
+#[query]
+fn list_notifications_filtered(
+    status: NotificationStatus,
+    notification_type: Option<NotificationType>,
+    recipient: Option<Principal>,
+    limit: u32,
+) -> ApiResult<Vec<NotificationJob>> {
+    auth::require_admin()?;
+    let limit = limit.min(notifications::MAX_NOTIFICATION_PAGE_SIZE) as usize;
+    Ok(storage::list_notifications_filtered(status, notification_type, recipient, limit))
+}
+
+/// Count notifications by status without returning them (for external worker polling)
+#[query]
+fn count_pending_notifications() -> ApiResult<NotificationCounts> {
+    auth::require_admin()?;
+    Ok(storage::count_notifications_by_status())
+}
+
 /// Mark a notification as sent (for external worker)
 #[update]
-fn mark_notification_sent(job_id: Vec<u8>) -> ApiResult<()> {
+fn mark_notification_sent(job_id: IdBytes) -> ApiResult<()> {
     auth::require_admin()?;
     
-    let jid: [u8; 16] = job_id.try_into()
-        .map_err(|_| ApiError::InvalidInput("Invalid job_id".to_string()))?;
+    let jid = job_id.0;
     
     let mut job = storage::get_notification(&jid)
         .ok_or(ApiError::NotFound)?;
+    if job.status == NotificationStatus::Cancelled {
+        return Err(ApiError::Conflict("Notification was cancelled".to_string()));
+    }
     
     job.status = NotificationStatus::Sent;
     job.sent_at = Some(ic_cdk::api::time());
@@ -595,35 +1978,58 @@ fn mark_notification_sent(job_id: Vec<u8>) -> ApiResult<()> {
     Ok(())
 }
 
+/// Flag pending notifications older than `older_than_hours` as stale (admin only)
+/// Bumps `requeued_at` so monitoring can tell fresh jobs from stuck ones.
+/// Returns the number of jobs flagged.
+#[update]
+fn requeue_stale_pending(older_than_hours: u32) -> ApiResult<u32> {
+    auth::require_admin()?;
+    
+    let now = ic_cdk::api::time();
+    let cutoff = now.saturating_sub(older_than_hours as u64 * 3600 * 1_000_000_000);
+    let count = notifications::requeue_stale_pending(cutoff, now);
+    
+    ic_cdk::println!("requeue_stale_pending: flagged {} stale pending notification(s)", count);
+    Ok(count)
+}
+
 /// Get ICS content for an event (for UI download)
 #[query]
-fn get_event_ics(instance_id: Vec<u8>) -> ApiResult<String> {
-    auth::require_authorized()?;
+fn get_event_ics(instance_id: IdBytes) -> ApiResult<String> {
+    let user = auth::require_authorized()?;
     
-    let iid: [u8; 16] = instance_id.try_into()
-        .map_err(|_| ApiError::InvalidInput("Invalid instance_id".to_string()))?;
+    let iid = instance_id.0;
     
-    // Try to find the event in materialized events
-    let settings = storage::get_settings();
-    let now = ic_cdk::api::time();
-    let window_end = recurrence::calculate_window_end(now, settings.forward_window_months);
+    // Cancelled occurrences get a CANCEL invite so hosts can remove them from their calendar
+    let event = recurrence::find_event(&iid)
+        .or_else(|| recurrence::find_cancelled_occurrence(&iid))
+        .ok_or(ApiError::NotFound)?;
+    let series = event.series_id.and_then(|sid| storage::get_series(&sid));
+    let cancelled = event.status == EventStatus::Cancelled;
+    
+    Ok(notifications::generate_ics(&notifications::IcsEvent {
+        instance_id: &event.instance_id,
+        title: &event.title,
+        notes: &event.notes,
+        link: event.link.as_deref(),
+        start_utc: event.start_utc,
+        end_utc: event.end_utc,
+        method: if cancelled { "CANCEL" } else { "REQUEST" },
+        sequence: if cancelled { 2 } else { 1 },
+        cancelled,
+        reminder_hours: notifications::effective_reminder_hours(&user, series.as_ref()),
+    }))
+}
+
+/// Look up an event by its ICS UID ("<hex>@domain"), as found in generated invites
+#[query]
+fn get_event_by_uid(uid: String) -> ApiResult<EventInstance> {
+    auth::require_authorized()?;
     
-    let events = recurrence::materialize_events(0, window_end);
-    let event = events.into_iter()
-        .find(|e| e.instance_id == iid)
+    let iid = notifications::parse_ics_uid(&uid)
         .ok_or(ApiError::NotFound)?;
     
-    Ok(notifications::generate_ics(
-        &event.instance_id,
-        &event.title,
-        &event.notes,
-        event.link.as_deref(),
-        event.start_utc,
-        event.end_utc,
-        "REQUEST",
-        1,
-        event.status == EventStatus::Cancelled,
-    ))
+    recurrence::find_event(&iid).ok_or(ApiError::NotFound)
 }
 
 // ============================================================================
@@ -634,55 +2040,109 @@ fn get_event_ics(instance_id: Vec<u8>) -> ApiResult<String> {
 // Invite Code System
 // ============================================================================
 
-/// Generate a standalone invite code (admin only).
+/// Upper bound on `max_uses` for a single multi-use invite code
+const MAX_INVITE_USES: u32 = 100;
+
+/// Default and maximum invite-code lifetimes, in days
+const DEFAULT_INVITE_VALID_DAYS: u32 = 7;
+const MAX_INVITE_VALID_DAYS: u32 = 90;
+
+/// Build a "YS-XXXX-XXXX[-XXXX-XXXX]" code from raw_rand() output.
+/// The first 16 random bytes are read as one 128-bit number and split into
+/// base-31 digits, so every character draws on the full entropy pool.
+fn format_invite_code(random_bytes: &[u8], strength: InviteCodeStrength) -> ApiResult<String> {
+    // Characters excluding ambiguous ones (0/O, 1/I/L)
+    let charset: &[u8] = b"ABCDEFGHJKMNPQRSTUVWXYZ23456789";
+    
+    let seed: [u8; 16] = random_bytes.get(..16)
+        .and_then(|b| b.try_into().ok())
+        .ok_or(ApiError::InternalError("Not enough random bytes".to_string()))?;
+    let mut n = u128::from_be_bytes(seed);
+    
+    let length = match strength {
+        InviteCodeStrength::Standard => 8,  // ~40 bits
+        InviteCodeStrength::Strong => 16,   // ~79 bits
+    };
+    
+    let mut code = String::from("YS");
+    for i in 0..length {
+        if i % 4 == 0 {
+            code.push('-');
+        }
+        code.push(charset[(n % charset.len() as u128) as usize] as char);
+        n /= charset.len() as u128;
+    }
+    Ok(code)
+}
+
+/// Generate a standalone invite code (requires ManageUsers).
 /// The code encodes a role. When redeemed, the user provides their own name/email.
+/// `max_uses` lets one code onboard a cohort (defaults to single-use).
+/// `valid_for_days` sets the expiry (defaults to 7 days).
+/// `strength` selects a longer code for multi-use or long-lived invites.
 /// Uses raw_rand() for cryptographic randomness — must be an update call.
 #[update]
-async fn generate_invite_code(role: Role) -> ApiResult<InviteCode> {
-    let admin = auth::require_admin()?;
+async fn generate_invite_code(
+    role: Role,
+    max_uses: Option<u32>,
+    valid_for_days: Option<u32>,
+    strength: Option<InviteCodeStrength>,
+) -> ApiResult<InviteCode> {
+    let admin = auth::require_capability(Capability::ManageUsers)?;
     let now = ic_cdk::api::time();
     
+    let max_uses = max_uses.unwrap_or(1);
+    if max_uses == 0 || max_uses > MAX_INVITE_USES {
+        return Err(ApiError::InvalidInput(
+            format!("max_uses must be between 1 and {}", MAX_INVITE_USES)
+        ));
+    }
+    
+    let valid_for_days = valid_for_days.unwrap_or(DEFAULT_INVITE_VALID_DAYS);
+    if valid_for_days == 0 || valid_for_days > MAX_INVITE_VALID_DAYS {
+        return Err(ApiError::InvalidInput(
+            format!("valid_for_days must be between 1 and {}", MAX_INVITE_VALID_DAYS)
+        ));
+    }
+    
     // Generate random bytes via management canister
     let (random_bytes,): (Vec<u8>,) = ic_cdk::api::management_canister::main::raw_rand()
         .await
         .map_err(|e| ApiError::InternalError(format!("Failed to generate random bytes: {:?}", e)))?;
     
-    // Characters excluding ambiguous ones (0/O, 1/I/L)
-    let charset: &[u8] = b"ABCDEFGHJKMNPQRSTUVWXYZ23456789";
-    
-    // Generate "YS-XXXX-XXXX"
-    let mut code_chars = String::from("YS-");
-    for i in 0..4 {
-        let idx = (random_bytes[i] as usize) % charset.len();
-        code_chars.push(charset[idx] as char);
-    }
-    code_chars.push('-');
-    for i in 4..8 {
-        let idx = (random_bytes[i] as usize) % charset.len();
-        code_chars.push(charset[idx] as char);
-    }
+    let code = format_invite_code(&random_bytes, strength.unwrap_or(InviteCodeStrength::Standard))?;
     
     let invite = InviteCode {
-        code: code_chars,
+        code,
         role,
         created_at: now,
         created_by: admin.principal,
-        expires_at: now + 7 * 24 * 60 * 60 * 1_000_000_000, // 7 days in nanoseconds
+        expires_at: now + valid_for_days as u64 * 24 * 60 * 60 * 1_000_000_000,
         redeemed: false,
         redeemed_by: None,
         redeemed_at: None,
         user_placeholder_principal: None,
+        max_uses,
+        use_count: 0,
+        redeemers: vec![],
+        revoked: false,
     };
     
-    storage::insert_invite_code(invite.clone());
+    storage::try_insert_invite_code(invite.clone())?;
+    audit::record(
+        admin.principal,
+        AuditAction::InviteCodeGenerated,
+        invite.code.clone(),
+        format!("role={:?}, max_uses={}", invite.role, invite.max_uses),
+    );
     Ok(invite)
 }
 
-/// Generate a personal invite code tied to a specific pre-created user (admin only).
+/// Generate a personal invite code tied to a specific pre-created user (requires ManageUsers).
 /// When redeemed, the caller's II principal replaces the placeholder principal on the existing user.
 #[update]
 async fn generate_personal_invite_code(placeholder_principal: Principal) -> ApiResult<InviteCode> {
-    let admin = auth::require_admin()?;
+    let admin = auth::require_capability(Capability::ManageUsers)?;
     let now = ic_cdk::api::time();
     
     // Validate user exists
@@ -694,21 +2154,11 @@ async fn generate_personal_invite_code(placeholder_principal: Principal) -> ApiR
         .await
         .map_err(|e| ApiError::InternalError(format!("Failed to generate random bytes: {:?}", e)))?;
     
-    let charset: &[u8] = b"ABCDEFGHJKMNPQRSTUVWXYZ23456789";
-    let mut code_chars = String::from("YS-");
-    for i in 0..4 {
-        let idx = (random_bytes[i] as usize) % charset.len();
-        code_chars.push(charset[idx] as char);
-    }
-    code_chars.push('-');
-    for i in 4..8 {
-        let idx = (random_bytes[i] as usize) % charset.len();
-        code_chars.push(charset[idx] as char);
-    }
+    let code = format_invite_code(&random_bytes, InviteCodeStrength::Standard)?;
     
     let invite = InviteCode {
-        code: code_chars,
-        role: user.role.clone(),
+        code,
+        role: user.role,
         created_at: now,
         created_by: admin.principal,
         expires_at: now + 7 * 24 * 60 * 60 * 1_000_000_000,
@@ -716,9 +2166,19 @@ async fn generate_personal_invite_code(placeholder_principal: Principal) -> ApiR
         redeemed_by: None,
         redeemed_at: None,
         user_placeholder_principal: Some(placeholder_principal),
+        max_uses: 1,
+        use_count: 0,
+        redeemers: vec![],
+        revoked: false,
     };
     
-    storage::insert_invite_code(invite.clone());
+    storage::try_insert_invite_code(invite.clone())?;
+    audit::record(
+        admin.principal,
+        AuditAction::InviteCodeGenerated,
+        invite.code.clone(),
+        format!("personal for {}", placeholder_principal.to_text()),
+    );
     Ok(invite)
 }
 
@@ -727,10 +2187,22 @@ async fn generate_personal_invite_code(placeholder_principal: Principal) -> ApiR
 ///   Name and email params are ignored since the user already has them.
 /// - Generic codes (role-only): creates a new user with the caller's principal, name, and email.
 /// Caller must be authenticated via II but does NOT need to be authorized.
+/// Failed attempts are rate-limited per principal to stop code guessing.
 #[update]
 fn redeem_invite_code(code: String, name: String, email: String) -> ApiResult<User> {
     let caller_principal = auth::require_authenticated()?;
+    auth::check_redeem_rate_limit(&caller_principal)?;
     
+    let result = redeem_invite_code_for(caller_principal, code, name, email);
+    match result {
+        Ok(_) => auth::reset_redeem_attempts(&caller_principal),
+        Err(_) => auth::record_failed_redeem(&caller_principal),
+    }
+    result
+}
+
+/// Redemption logic behind `redeem_invite_code`, after rate limiting
+fn redeem_invite_code_for(caller_principal: Principal, code: String, name: String, email: String) -> ApiResult<User> {
     // Check caller isn't already an authorized user
     if storage::user_exists(&caller_principal) {
         return Err(ApiError::Conflict("You are already an authorized user.".to_string()));
@@ -743,8 +2215,21 @@ fn redeem_invite_code(code: String, name: String, email: String) -> ApiResult<Us
     
     let now = ic_cdk::api::time();
     
-    if invite.redeemed {
-        return Err(ApiError::InvalidInput("This invite code has already been used.".to_string()));
+    if invite.revoked {
+        return Err(ApiError::InvalidInput("This invite code has been revoked.".to_string()));
+    }
+    
+    if invite.redeemers.contains(&caller_principal) {
+        return Err(ApiError::Conflict("You have already redeemed this invite code.".to_string()));
+    }
+    
+    if invite.redeemed || invite.use_count >= invite.max_uses {
+        let msg = if invite.max_uses > 1 {
+            "This invite code has reached its usage limit."
+        } else {
+            "This invite code has already been used."
+        };
+        return Err(ApiError::InvalidInput(msg.to_string()));
     }
     
     if invite.expires_at < now {
@@ -778,46 +2263,82 @@ fn redeem_invite_code(code: String, name: String, email: String) -> ApiResult<Us
     } else {
         // ── Generic invite: create a brand new user ──
         let name = name.trim().to_string();
-        let email = email.trim().to_string();
         if name.is_empty() {
             return Err(ApiError::InvalidInput("Name is required.".to_string()));
         }
-        if email.is_empty() || !email.contains('@') {
-            return Err(ApiError::InvalidInput("A valid email is required.".to_string()));
-        }
+        validation::check_length("name", &name, validation::MAX_NAME_LEN)?;
+        let email = validation::validate_email(&email)?;
         
         let new_user = User {
             principal: caller_principal,
             name,
             email,
-            role: invite.role.clone(),
+            role: invite.role,
             status: UserStatus::Active,
             out_of_office: vec![],
-            notification_settings: NotificationSettings::default(),
+            availability: vec![],
+            notification_settings: auth::default_notification_settings(),
             last_active: now,
             sessions_hosted_count: 0,
             created_at: now,
             updated_at: now,
+            locale: None,
         };
         
-        storage::insert_user(new_user.clone());
+        storage::try_insert_user(new_user.clone())?;
         new_user
     };
     
-    // Mark invite as redeemed
-    invite.redeemed = true;
+    // Record the use; the code is only exhausted once the limit is reached
+    invite.use_count += 1;
+    invite.redeemers.push(caller_principal);
+    invite.redeemed = invite.use_count >= invite.max_uses;
     invite.redeemed_by = Some(caller_principal);
     invite.redeemed_at = Some(now);
-    storage::insert_invite_code(invite);
+    storage::try_insert_invite_code(invite)?;
+    
+    notifications::notify_admins_user_joined(&user);
     
     Ok(user)
 }
 
-/// List all invite codes (admin only)
+/// Revoke an invite code so it can no longer be redeemed (requires ManageUsers).
+/// Already-exhausted codes are left untouched.
+#[update]
+fn revoke_invite_code(code: String) -> ApiResult<()> {
+    let admin = auth::require_capability(Capability::ManageUsers)?;
+    
+    let code_upper = code.trim().to_uppercase();
+    let mut invite = storage::get_invite_code(&code_upper)
+        .ok_or(ApiError::NotFound)?;
+    
+    if invite.redeemed || invite.revoked {
+        return Ok(());
+    }
+    
+    invite.revoked = true;
+    storage::try_insert_invite_code(invite)?;
+    audit::record(admin.principal, AuditAction::InviteCodeRevoked, code_upper, String::new());
+    Ok(())
+}
+
+/// List all invite codes (requires ManageUsers)
 #[query]
 fn list_invite_codes() -> ApiResult<Vec<InviteCode>> {
-    auth::require_admin()?;
+    auth::require_capability(Capability::ManageUsers)?;
     Ok(storage::list_all_invite_codes())
 }
 
+// ============================================================================
+// Audit Log
+// ============================================================================
+
+/// List admin audit log entries, newest first (admin only)
+/// `limit` is capped at audit::MAX_AUDIT_PAGE_SIZE
+#[query]
+fn list_audit_log(offset: u32, limit: u32) -> ApiResult<Vec<AuditEntry>> {
+    auth::require_admin()?;
+    Ok(audit::list(offset, limit))
+}
+
 ic_cdk::export_candid!();
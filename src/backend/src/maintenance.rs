@@ -0,0 +1,182 @@
+//! Read-only detectors for stale or leftover data
+//!
+//! Each detector returns the matching records so cleanup endpoints can act on
+//! them; `report` aggregates their counts for the admin dashboard, and
+//! `metrics` summarizes overall storage for monitoring.
+
+use crate::notifications;
+use crate::recurrence;
+use crate::storage;
+use crate::types::*;
+use candid::Principal;
+
+/// One-off sessions that ended more than this many days ago can be archived
+pub const ARCHIVE_AFTER_DAYS: u64 = 90;
+
+/// How far back the unclaimed-session sweep looks. Sweeps run hourly, so this
+/// only matters the first time the setting is turned on.
+const SWEEP_LOOKBACK_DAYS: u64 = 31;
+
+/// Overrides whose series no longer exists (soft-deleted series still count)
+pub fn orphaned_overrides() -> Vec<InstanceOverride> {
+    storage::list_all_overrides()
+        .into_iter()
+        .filter(|o| storage::get_series_including_deleted(&o.series_id).is_none())
+        .collect()
+}
+
+/// Invite codes past their expiry that could still have been redeemed
+pub fn expired_invite_codes(now: u64) -> Vec<InviteCode> {
+    storage::list_all_invite_codes()
+        .into_iter()
+        .filter(|c| !c.revoked && c.use_count < c.max_uses && c.expires_at < now)
+        .collect()
+}
+
+/// Notification jobs the worker reported as failed
+pub fn failed_notifications() -> Vec<NotificationJob> {
+    storage::list_notifications_with_status(NotificationStatus::Failed)
+}
+
+/// One-off sessions that ended more than ARCHIVE_AFTER_DAYS ago
+pub fn archivable_instances(now: u64) -> Vec<EventInstance> {
+    let cutoff = now.saturating_sub(ARCHIVE_AFTER_DAYS * 86400 * 1_000_000_000);
+    storage::list_all_instances()
+        .into_iter()
+        .filter(|i| i.end_utc < cutoff)
+        .collect()
+}
+
+/// Counts from every detector plus a suggested action for each non-zero one
+pub fn report(now: u64) -> MaintenanceReport {
+    let orphaned = orphaned_overrides().len() as u32;
+    let expired = expired_invite_codes(now).len() as u32;
+    let failed = failed_notifications().len() as u32;
+    let archivable = archivable_instances(now).len() as u32;
+    
+    let mut suggested_actions = Vec::new();
+    if orphaned > 0 {
+        suggested_actions.push(format!("Delete {} override(s) left behind by deleted series", orphaned));
+    }
+    if expired > 0 {
+        suggested_actions.push(format!("Revoke {} expired invite code(s)", expired));
+    }
+    if failed > 0 {
+        suggested_actions.push(format!("Review and retry {} failed notification(s)", failed));
+    }
+    if archivable > 0 {
+        suggested_actions.push(format!(
+            "Archive {} one-off session(s) older than {} days", archivable, ARCHIVE_AFTER_DAYS
+        ));
+    }
+    
+    MaintenanceReport {
+        orphaned_overrides: orphaned,
+        expired_invite_codes: expired,
+        failed_notifications: failed,
+        archivable_instances: archivable,
+        suggested_actions,
+    }
+}
+
+/// Record counts across storage plus stable memory usage
+pub fn metrics() -> CanisterMetrics {
+    let users = storage::list_all_users();
+    let count_users = |pred: fn(&User) -> bool| users.iter().filter(|u| pred(u)).count() as u32;
+    
+    CanisterMetrics {
+        users_active: count_users(|u| u.status == UserStatus::Active),
+        users_disabled: count_users(|u| u.status == UserStatus::Disabled),
+        admins: count_users(|u| u.role == Role::Admin),
+        coordinators: count_users(|u| u.role == Role::Coordinator),
+        series: storage::series_count(),
+        overrides: storage::override_count(),
+        one_off_instances: storage::instance_count(),
+        notifications_pending: storage::list_pending_notifications().len() as u32,
+        notifications_failed: failed_notifications().len() as u32,
+        invite_codes: storage::invite_code_count(),
+        stable_memory_bytes: storage::stable_memory_bytes(),
+    }
+}
+
+/// Cancel series occurrences that ended more than `grace_hours` ago without a
+/// host, by writing cancelled overrides. Assigned and future occurrences are
+/// never touched. Returns the number of occurrences cancelled.
+pub fn sweep_past_unclaimed(now: u64, grace_hours: u32, caller: Principal) -> u32 {
+    let cutoff = now.saturating_sub(grace_hours as u64 * 3600 * 1_000_000_000);
+    let lookback = cutoff.saturating_sub(SWEEP_LOOKBACK_DAYS * 86400 * 1_000_000_000);
+    let events = recurrence::materialize_events(lookback, cutoff);
+    let mut cancelled_ids: Vec<[u8; 16]> = Vec::new();
+    let mut count = 0;
+    let namespace = recurrence::instance_namespace();
+    
+    for series in storage::list_all_series() {
+        for occ_start in recurrence::generate_occurrences(&series, lookback, cutoff) {
+            let instance_id = recurrence::instance_id_in(namespace.as_ref(), &series.series_id, occ_start);
+            let event = match events.iter().find(|e| e.instance_id == instance_id) {
+                Some(e) => e,
+                None => continue, // already cancelled, or series paused
+            };
+            if event.host_principal.is_some() || event.end_utc > cutoff {
+                continue;
+            }
+            
+            let key = OverrideKey { series_id: series.series_id, occurrence_start_utc: occ_start };
+            let mut ovr = storage::get_override(&key).unwrap_or_else(|| InstanceOverride::new(series.series_id, occ_start, caller, now));
+            ovr.cancelled = true;
+            ovr.updated_at = now;
+            ovr.updated_by = caller;
+            if let Err(ApiError::InvalidInput(msg)) = storage::try_insert_override(ovr) {
+                ic_cdk::println!("Warning: not cancelling unclaimed occurrence: {}", msg);
+                continue;
+            }
+            cancelled_ids.push(instance_id);
+            count += 1;
+        }
+    }
+    
+    notifications::cancel_pending_for_instances(&cancelled_ids);
+    count
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const DAY: u64 = 86400 * 1_000_000_000;
+
+    fn invite(code: &str, expires_at: u64) -> InviteCode {
+        InviteCode {
+            code: code.to_string(),
+            role: Role::User,
+            created_at: 0,
+            created_by: Principal::anonymous(),
+            expires_at,
+            redeemed: false,
+            redeemed_by: None,
+            redeemed_at: None,
+            user_placeholder_principal: None,
+            max_uses: 1,
+            use_count: 0,
+            redeemers: vec![],
+            revoked: false,
+        }
+    }
+
+    #[test]
+    fn report_counts_expired_invites_and_orphaned_overrides() {
+        let now = 20_000 * DAY;
+        storage::insert_invite_code(invite("YS-OLD0-0000", now - DAY));
+        storage::insert_invite_code(invite("YS-NEW0-0000", now + DAY));
+        // No series with this id was ever stored
+        storage::insert_override(InstanceOverride::new([5; 16], now - 3 * DAY, Principal::anonymous(), now));
+
+        let report = report(now);
+
+        assert_eq!(report.orphaned_overrides, 1);
+        assert_eq!(report.expired_invite_codes, 1);
+        assert_eq!(report.failed_notifications, 0);
+        assert_eq!(report.archivable_instances, 0);
+        assert_eq!(report.suggested_actions.len(), 2);
+    }
+}
@@ -6,68 +6,86 @@
 //! - External worker (future) polls pending jobs and sends emails
 //! - For MVP, UI can provide "download .ics" from the stored payload
 
+use crate::datetime::{self, format_ics_datetime};
 use crate::recurrence;
+use crate::stats;
 use crate::storage;
 use crate::types::*;
-use candid::Principal;
-
-/// Format a timestamp as ICS datetime (YYYYMMDDTHHMMSSZ)
-fn format_ics_datetime(nanos: u64) -> String {
-    let secs = nanos / 1_000_000_000;
-    let days_since_epoch = secs / 86400;
-    let time_of_day = secs % 86400;
-    
-    let hours = time_of_day / 3600;
-    let minutes = (time_of_day % 3600) / 60;
-    let seconds = time_of_day % 60;
-    
-    // Simple date calculation
-    let mut year = 1970i32;
-    let mut remaining_days = days_since_epoch as i32;
-    
-    loop {
-        let days_in_year = if (year % 4 == 0 && year % 100 != 0) || (year % 400 == 0) { 366 } else { 365 };
-        if remaining_days < days_in_year {
-            break;
-        }
-        remaining_days -= days_in_year;
-        year += 1;
-    }
-    
-    let days_in_months = if (year % 4 == 0 && year % 100 != 0) || (year % 400 == 0) {
-        [31, 29, 31, 30, 31, 30, 31, 31, 30, 31, 30, 31]
-    } else {
-        [31, 28, 31, 30, 31, 30, 31, 31, 30, 31, 30, 31]
-    };
-    
-    let mut month = 1u32;
-    for dim in days_in_months {
-        if remaining_days < dim {
-            break;
-        }
-        remaining_days -= dim;
-        month += 1;
+
+/// Domain suffix used in ICS UIDs
+const ICS_UID_DOMAIN: &str = "ohscheduler.icp";
+
+/// ICS UID for an instance: "<hex instance_id>@ohscheduler.icp"
+pub fn ics_uid(instance_id: &[u8; 16]) -> String {
+    format!("{}@{}", hex::encode(instance_id), ICS_UID_DOMAIN)
+}
+
+/// Parse an ICS UID back into an instance id
+/// Accepts a bare hex id as well; the domain part, if present, is not checked.
+pub fn parse_ics_uid(uid: &str) -> Option<[u8; 16]> {
+    let hex_part = uid.trim().split('@').next()?;
+    hex::decode(hex_part).ok()?.try_into().ok()
+}
+
+
+/// Reminder lead time for a host's session: the series override if set,
+/// otherwise the host's own `reminder_hours_before`
+pub fn effective_reminder_hours(host: &User, series: Option<&EventSeries>) -> Option<u32> {
+    series
+        .and_then(|s| s.reminder_hours_before_override)
+        .or(host.notification_settings.reminder_hours_before)
+}
+
+
+/// Most jobs returned by one filtered notification fetch
+pub const MAX_NOTIFICATION_PAGE_SIZE: u32 = 500;
+
+/// Default ICS product identifier, used when no org name is configured
+const DEFAULT_PRODID: &str = "-//OHScheduler//ICP//EN";
+
+/// PRODID and calendar name for this deployment, from the org name if set
+fn calendar_identity() -> (String, Option<String>) {
+    let org_name = storage::get_settings().org_name
+        .map(|n| n.trim().replace(['\r', '\n'], " "))
+        .filter(|n| !n.is_empty());
+    match org_name {
+        Some(name) => (
+            format!("-//{}//OHScheduler//EN", name.replace("//", "/")),
+            Some(name.replace("\\", "\\\\").replace(",", "\\,").replace(";", "\\;")),
+        ),
+        None => (DEFAULT_PRODID.to_string(), None),
     }
-    let day = remaining_days + 1;
-    
-    format!("{:04}{:02}{:02}T{:02}{:02}{:02}Z", year, month, day, hours, minutes, seconds)
 }
 
+/// One session as rendered into an ICS calendar
+pub struct IcsEvent<'a> {
+    pub instance_id: &'a [u8; 16],
+    pub title: &'a str,
+    pub notes: &'a str,
+    pub link: Option<&'a str>,
+    pub start_utc: u64,
+    pub end_utc: u64,
+    pub method: &'a str, // "REQUEST" for new/update, "CANCEL" for cancellation
+    pub sequence: u32,
+    pub cancelled: bool,
+    pub reminder_hours: Option<u32>, // adds a VALARM this many hours before start (ignored when cancelled)
+}
 
 /// Generate iCalendar content for an event
-/// method: "REQUEST" for new/update, "CANCEL" for cancellation
-pub fn generate_ics(
-    instance_id: &[u8; 16],
-    title: &str,
-    notes: &str,
-    link: Option<&str>,
-    start_utc: u64,
-    end_utc: u64,
-    method: &str,
-    sequence: u32,
-    cancelled: bool,
-) -> String {
-    let uid = format!("{}@ohscheduler.icp", hex::encode(instance_id));
+pub fn generate_ics(event: &IcsEvent) -> String {
+    let &IcsEvent {
+        instance_id,
+        title,
+        notes,
+        link,
+        start_utc,
+        end_utc,
+        method,
+        sequence,
+        cancelled,
+        reminder_hours,
+    } = event;
+    let uid = ics_uid(instance_id);
     let now = format_ics_datetime(ic_cdk::api::time());
     let start = format_ics_datetime(start_utc);
     let end = format_ics_datetime(end_utc);
@@ -87,11 +105,19 @@ pub fn generate_ics(
         notes_escaped
     };
     
+    let (prodid, calendar_name) = calendar_identity();
     let mut ics = format!(
 r#"BEGIN:VCALENDAR
 VERSION:2.0
-PRODID:-//OHScheduler//ICP//EN
-METHOD:{}
+PRODID:{}
+METHOD:{}"#,
+        prodid, method
+    );
+    if let Some(name) = calendar_name {
+        ics.push_str(&format!("\nX-WR-CALNAME:{}", name));
+    }
+    ics.push_str(&format!(
+r#"
 BEGIN:VEVENT
 UID:{}
 SEQUENCE:{}
@@ -101,22 +127,99 @@ DTEND:{}
 SUMMARY:{}
 DESCRIPTION:{}
 STATUS:{}"#,
-        method, uid, sequence, now, start, end, title_escaped, description, status
-    );
+        uid, sequence, now, start, end, title_escaped, description, status
+    ));
     
     // Add URL property if link is present
     if let Some(url) = link {
         ics.push_str(&format!("\nURL:{}", url));
     }
     
+    if let Some(hours) = reminder_hours.filter(|_| !cancelled) {
+        ics.push_str(&format!(
+            "\nBEGIN:VALARM\nTRIGGER:-PT{}H\nACTION:DISPLAY\nDESCRIPTION:Reminder\nEND:VALARM",
+            hours
+        ));
+    }
+    
     ics.push_str("\nEND:VEVENT\nEND:VCALENDAR");
     ics
 }
 
 
+/// Title used in templates when a notification has no session title of its own
+const DEFAULT_SESSION_TITLE: &str = "Office Hours Session";
+
+/// Placeholders allowed in notification templates
+pub const TEMPLATE_PLACEHOLDERS: &[&str] = &["title", "start", "host"];
+
+/// Notification types whose builders go through `render_message`
+/// Digests, reminders and admin notices aren't about one session, so the
+/// placeholders don't apply and templates for them are rejected. The weekly
+/// coverage alert shares the CoverageNeededSoon type but builds its own text,
+/// so only the per-session notices pick up that type's template.
+pub const TEMPLATED_TYPES: &[NotificationType] = &[
+    NotificationType::HostAssigned,
+    NotificationType::HostRemoved,
+    NotificationType::InstanceTimeChanged,
+    NotificationType::InstanceCancelled,
+    NotificationType::CoverageNeededSoon,
+];
+
+/// Values substituted into a notification template
+struct TemplateVars<'a> {
+    title: &'a str,
+    start_utc: u64,
+    recipient: &'a str, // `{host}`: the user being notified, assigned host or not
+}
+
+fn render_template(template: &str, vars: &TemplateVars) -> String {
+    template
+        .replace("{title}", vars.title)
+        .replace("{start}", &datetime::format_iso8601(vars.start_utc))
+        .replace("{host}", vars.recipient)
+}
+
+/// Subject and body for a per-session notification: the org's template for
+/// this type where set, otherwise the built-in defaults
+fn render_message(
+    notification_type: NotificationType,
+    vars: &TemplateVars,
+    default_subject: String,
+    default_body: String,
+) -> (String, String) {
+    let settings = storage::get_settings();
+    let template = settings.notification_templates
+        .iter()
+        .find(|t| t.notification_type == notification_type);
+    let subject = template
+        .and_then(|t| t.subject.as_deref())
+        .map(|s| render_template(s, vars))
+        .unwrap_or(default_subject);
+    let body = template
+        .and_then(|t| t.body.as_deref())
+        .map(|b| render_template(b, vars))
+        .unwrap_or(default_body);
+    (subject, body)
+}
+
+/// Store a new job. If it's too large for stable storage (usually because of
+/// the ICS payload), retry without the payload rather than trapping.
+fn store_job(job: NotificationJob) {
+    if let Err(ApiError::InvalidInput(msg)) = storage::try_insert_notification(job.clone()) {
+        ic_cdk::println!("Warning: {}; storing notification without ICS payload", msg);
+        let job = NotificationJob { ics_payload: None, ..job };
+        if let Err(ApiError::InvalidInput(msg)) = storage::try_insert_notification(job) {
+            ic_cdk::println!("Warning: dropping notification: {}", msg);
+        }
+    }
+}
+
+
 /// Create notification job for host assignment
 pub fn create_host_assigned_notification(
     host: &User,
+    series: Option<&EventSeries>,
     instance_id: &[u8; 16],
     start_utc: u64,
     end_utc: u64,
@@ -128,16 +231,24 @@ pub fn create_host_assigned_notification(
     let now = ic_cdk::api::time();
     let job_id = recurrence::generate_uuid();
     
-    let ics = generate_ics(
+    let ics = generate_ics(&IcsEvent {
         instance_id,
-        "Office Hours Session",
-        "You have been assigned as host for this session.",
-        None,
+        title: DEFAULT_SESSION_TITLE,
+        notes: "You have been assigned as host for this session.",
+        link: None,
         start_utc,
         end_utc,
-        "REQUEST",
-        1,
-        false,
+        method: "REQUEST",
+        sequence: 1,
+        cancelled: false,
+        reminder_hours: effective_reminder_hours(host, series),
+    });
+    
+    let (subject, body_text) = render_message(
+        NotificationType::HostAssigned,
+        &TemplateVars { title: series.map(|s| s.title.as_str()).unwrap_or(DEFAULT_SESSION_TITLE), start_utc, recipient: &host.name },
+        "You've been assigned to an Office Hours session".to_string(),
+        "You have been assigned as host for an Office Hours session.\n\nPlease add the attached calendar invite to your calendar.".to_string(),
     );
     
     let job = NotificationJob {
@@ -146,43 +257,62 @@ pub fn create_host_assigned_notification(
         notification_type: NotificationType::HostAssigned,
         recipient_principal: host.principal,
         recipient_email: host.email.clone(),
-        subject: "You've been assigned to an Office Hours session".to_string(),
-        body_text: format!(
-            "You have been assigned as host for an Office Hours session.\n\nPlease add the attached calendar invite to your calendar."
-        ),
+        subject,
+        body_text,
         ics_payload: Some(ics),
         status: NotificationStatus::Pending,
         sent_at: None,
         error_message: None,
+        requeued_at: None,
+        instance_id: Some(*instance_id),
+        series_id: series.map(|s| s.series_id),
     };
     
-    storage::insert_notification(job);
+    store_job(job);
+}
+
+/// Whether to email a host who opted in (or not) to this kind of closure notice
+/// Critical notices (cancellations, removals with no replacement) override an
+/// opt-out when `always_email_critical_removals` is on.
+fn wants_closure_email(opted_in: bool, critical: bool) -> bool {
+    opted_in || (critical && storage::get_settings().always_email_critical_removals)
 }
 
 /// Create notification job for host removal
+/// `critical` marks a removal with no replacement host (as opposed to a reassignment).
 pub fn create_host_removed_notification(
     host: &User,
     instance_id: &[u8; 16],
+    series_id: Option<[u8; 16]>,
     start_utc: u64,
     end_utc: u64,
+    critical: bool,
 ) {
-    if !host.notification_settings.email_on_removed {
+    if !wants_closure_email(host.notification_settings.email_on_removed, critical) {
         return;
     }
     
     let now = ic_cdk::api::time();
     let job_id = recurrence::generate_uuid();
     
-    let ics = generate_ics(
+    let ics = generate_ics(&IcsEvent {
         instance_id,
-        "Office Hours Session - CANCELLED",
-        "You have been removed as host for this session.",
-        None,
+        title: "Office Hours Session - CANCELLED",
+        notes: "You have been removed as host for this session.",
+        link: None,
         start_utc,
         end_utc,
-        "CANCEL",
-        2,
-        true,
+        method: "CANCEL",
+        sequence: 2,
+        cancelled: true,
+        reminder_hours: None,
+    });
+    
+    let (subject, body_text) = render_message(
+        NotificationType::HostRemoved,
+        &TemplateVars { title: DEFAULT_SESSION_TITLE, start_utc, recipient: &host.name },
+        "You've been removed from an Office Hours session".to_string(),
+        "You have been removed as host for an Office Hours session.".to_string(),
     );
     
     let job = NotificationJob {
@@ -191,15 +321,18 @@ pub fn create_host_removed_notification(
         notification_type: NotificationType::HostRemoved,
         recipient_principal: host.principal,
         recipient_email: host.email.clone(),
-        subject: "You've been removed from an Office Hours session".to_string(),
-        body_text: "You have been removed as host for an Office Hours session.".to_string(),
+        subject,
+        body_text,
         ics_payload: Some(ics),
         status: NotificationStatus::Pending,
         sent_at: None,
         error_message: None,
+        requeued_at: None,
+        instance_id: Some(*instance_id),
+        series_id,
     };
     
-    storage::insert_notification(job);
+    store_job(job);
 }
 
 
@@ -207,27 +340,36 @@ pub fn create_host_removed_notification(
 pub fn create_instance_cancelled_notification(
     host: &User,
     instance_id: &[u8; 16],
+    series_id: Option<[u8; 16]>,
     title: &str,
     start_utc: u64,
     end_utc: u64,
 ) {
-    if !host.notification_settings.email_on_cancelled {
+    if !wants_closure_email(host.notification_settings.email_on_cancelled, true) {
         return;
     }
     
     let now = ic_cdk::api::time();
     let job_id = recurrence::generate_uuid();
     
-    let ics = generate_ics(
+    let ics = generate_ics(&IcsEvent {
         instance_id,
         title,
-        "This session has been cancelled.",
-        None,
+        notes: "This session has been cancelled.",
+        link: None,
         start_utc,
         end_utc,
-        "CANCEL",
-        2,
-        true,
+        method: "CANCEL",
+        sequence: 2,
+        cancelled: true,
+        reminder_hours: None,
+    });
+    
+    let (subject, body_text) = render_message(
+        NotificationType::InstanceCancelled,
+        &TemplateVars { title, start_utc, recipient: &host.name },
+        format!("Office Hours session cancelled: {}", title),
+        format!("The Office Hours session '{}' has been cancelled.", title),
     );
     
     let job = NotificationJob {
@@ -236,21 +378,25 @@ pub fn create_instance_cancelled_notification(
         notification_type: NotificationType::InstanceCancelled,
         recipient_principal: host.principal,
         recipient_email: host.email.clone(),
-        subject: format!("Office Hours session cancelled: {}", title),
-        body_text: format!("The Office Hours session '{}' has been cancelled.", title),
+        subject,
+        body_text,
         ics_payload: Some(ics),
         status: NotificationStatus::Pending,
         sent_at: None,
         error_message: None,
+        requeued_at: None,
+        instance_id: Some(*instance_id),
+        series_id,
     };
     
-    storage::insert_notification(job);
+    store_job(job);
 }
 
 /// Create notification job for instance time change
 pub fn create_instance_time_changed_notification(
     host: &User,
     instance_id: &[u8; 16],
+    series_id: Option<[u8; 16]>,
     title: &str,
     start_utc: u64,
     end_utc: u64,
@@ -262,16 +408,24 @@ pub fn create_instance_time_changed_notification(
     let now = ic_cdk::api::time();
     let job_id = recurrence::generate_uuid();
     
-    let ics = generate_ics(
+    let ics = generate_ics(&IcsEvent {
         instance_id,
         title,
-        "The time for this session has been updated.",
-        None,
+        notes: "The time for this session has been updated.",
+        link: None,
         start_utc,
         end_utc,
-        "REQUEST",
-        2,
-        false,
+        method: "REQUEST",
+        sequence: 2,
+        cancelled: false,
+        reminder_hours: None,
+    });
+    
+    let (subject, body_text) = render_message(
+        NotificationType::InstanceTimeChanged,
+        &TemplateVars { title, start_utc, recipient: &host.name },
+        format!("Office Hours session time changed: {}", title),
+        format!("The time for Office Hours session '{}' has been updated. Please see the attached calendar invite.", title),
     );
     
     let job = NotificationJob {
@@ -280,13 +434,365 @@ pub fn create_instance_time_changed_notification(
         notification_type: NotificationType::InstanceTimeChanged,
         recipient_principal: host.principal,
         recipient_email: host.email.clone(),
-        subject: format!("Office Hours session time changed: {}", title),
-        body_text: format!("The time for Office Hours session '{}' has been updated. Please see the attached calendar invite.", title),
+        subject,
+        body_text,
         ics_payload: Some(ics),
         status: NotificationStatus::Pending,
         sent_at: None,
         error_message: None,
+        requeued_at: None,
+        instance_id: Some(*instance_id),
+        series_id,
     };
     
-    storage::insert_notification(job);
+    store_job(job);
+}
+
+/// Occurrences starting within this many hours are considered to need coverage soon
+pub const COVERAGE_NEEDED_LEAD_HOURS: u64 = 48;
+
+/// Create notification job telling a user an upcoming session has no host
+pub fn create_coverage_needed_notification(
+    recipient: &User,
+    instance_id: &[u8; 16],
+    series_id: Option<[u8; 16]>,
+    title: &str,
+    start_utc: u64,
+    end_utc: u64,
+) {
+    let now = ic_cdk::api::time();
+    let job_id = recurrence::generate_uuid();
+    
+    let ics = generate_ics(&IcsEvent {
+        instance_id,
+        title,
+        notes: "This session needs a host.",
+        link: None,
+        start_utc,
+        end_utc,
+        method: "PUBLISH",
+        sequence: 1,
+        cancelled: false,
+        reminder_hours: None,
+    });
+    
+    let (y, m, d) = datetime::nanos_to_ymd(start_utc);
+    let (subject, body_text) = render_message(
+        NotificationType::CoverageNeededSoon,
+        &TemplateVars { title, start_utc, recipient: &recipient.name },
+        format!("Coverage needed: {} on {:04}-{:02}-{:02}", title, y, m, d),
+        format!(
+            "The Office Hours session '{}' on {:04}-{:02}-{:02} (UTC) has no host yet. Please claim it if you can cover it.",
+            title, y, m, d
+        ),
+    );
+    
+    let job = NotificationJob {
+        job_id,
+        created_at: now,
+        notification_type: NotificationType::CoverageNeededSoon,
+        recipient_principal: recipient.principal,
+        recipient_email: recipient.email.clone(),
+        subject,
+        body_text,
+        ics_payload: Some(ics),
+        status: NotificationStatus::Pending,
+        sent_at: None,
+        error_message: None,
+        requeued_at: None,
+        instance_id: Some(*instance_id),
+        series_id,
+    };
+    
+    store_job(job);
+}
+
+/// Enqueue coverage-needed notifications for a series' unclaimed occurrences
+/// starting within COVERAGE_NEEDED_LEAD_HOURS. Admins are always notified;
+/// other active users only if they opted into unclaimed reminders.
+/// Returns the number of occurrences that need coverage.
+pub fn notify_imminent_unclaimed(series: &EventSeries) -> u32 {
+    let unclaimed = imminent_unclaimed(series, ic_cdk::api::time());
+    if unclaimed.is_empty() {
+        return 0;
+    }
+    
+    let recipients: Vec<User> = storage::list_all_users()
+        .into_iter()
+        .filter(|u| u.status == UserStatus::Active)
+        .filter(|u| u.role == Role::Admin || u.notification_settings.email_unclaimed_reminder)
+        .collect();
+    
+    for event in &unclaimed {
+        for recipient in &recipients {
+            create_coverage_needed_notification(
+                recipient,
+                &event.instance_id,
+                event.series_id,
+                &event.title,
+                event.start_utc,
+                event.end_utc,
+            );
+        }
+    }
+    
+    unclaimed.len() as u32
+}
+
+/// A series' unclaimed occurrences starting within COVERAGE_NEEDED_LEAD_HOURS of `now`
+fn imminent_unclaimed(series: &EventSeries, now: u64) -> Vec<EventInstance> {
+    let horizon = now + COVERAGE_NEEDED_LEAD_HOURS * 3600 * 1_000_000_000;
+    recurrence::materialize_events(now, horizon)
+        .into_iter()
+        .filter(|e| e.series_id == Some(series.series_id) && e.host_principal.is_none())
+        .collect()
+}
+
+/// Enqueue a coverage warning to every active admin if coverage of the sessions
+/// starting in the next 7 days is below `threshold_pct`.
+/// The text is built here rather than through `render_message`: a
+/// CoverageNeededSoon template is written for one session and doesn't apply.
+/// Returns the number of notifications enqueued.
+pub fn send_coverage_alert(now: u64, threshold_pct: f64) -> u32 {
+    let week_end = now + 7 * 86400 * 1_000_000_000;
+    let coverage = stats::coverage_stats(now, week_end, "next 7 days".to_string());
+    if coverage.total_sessions == 0 || coverage.coverage_pct >= threshold_pct {
+        return 0;
+    }
+    
+    let admins: Vec<User> = storage::list_all_users()
+        .into_iter()
+        .filter(|u| u.status == UserStatus::Active && u.role == Role::Admin)
+        .collect();
+    
+    for admin in &admins {
+        let job = NotificationJob {
+            job_id: recurrence::generate_uuid(),
+            created_at: now,
+            notification_type: NotificationType::CoverageNeededSoon,
+            recipient_principal: admin.principal,
+            recipient_email: admin.email.clone(),
+            subject: format!("Coverage alert: {:.0}% of next week's sessions have a host", coverage.coverage_pct),
+            body_text: format!(
+                "{} of the {} Office Hours sessions in the next 7 days have no host ({:.0}% covered, alert threshold {:.0}%).",
+                coverage.unassigned, coverage.total_sessions, coverage.coverage_pct, threshold_pct
+            ),
+            ics_payload: None,
+            status: NotificationStatus::Pending,
+            sent_at: None,
+            error_message: None,
+            requeued_at: None,
+            instance_id: None,
+            series_id: None,
+        };
+        store_job(job);
+    }
+    
+    admins.len() as u32
+}
+
+/// Enqueue a notification to every active admin that `new_user` joined via an invite code
+/// Does nothing when `notify_admins_on_join` is off.
+pub fn notify_admins_user_joined(new_user: &User) {
+    if !storage::get_settings().notify_admins_on_join {
+        return;
+    }
+    let now = ic_cdk::api::time();
+    
+    let admins = storage::list_all_users()
+        .into_iter()
+        .filter(|u| u.status == UserStatus::Active && u.role == Role::Admin)
+        .filter(|u| u.principal != new_user.principal);
+    
+    for admin in admins {
+        let job = NotificationJob {
+            job_id: recurrence::generate_uuid(),
+            created_at: now,
+            notification_type: NotificationType::UserJoined,
+            recipient_principal: admin.principal,
+            recipient_email: admin.email.clone(),
+            subject: format!("New user joined: {}", new_user.name),
+            body_text: format!(
+                "{} ({}) redeemed an invite code and joined Office Hours as {:?}.",
+                new_user.name, new_user.email, new_user.role
+            ),
+            ics_payload: None,
+            status: NotificationStatus::Pending,
+            sent_at: None,
+            error_message: None,
+            requeued_at: None,
+            instance_id: None,
+            series_id: None,
+        };
+        store_job(job);
+    }
+}
+
+/// Mark pending jobs matching `about` as Cancelled so the worker doesn't send
+/// them. Cancellation and removal notices are left pending: they're the
+/// closure the host still needs. Returns the number of jobs cancelled.
+fn cancel_pending_where(about: impl Fn(&NotificationJob) -> bool) -> u32 {
+    let mut count = 0;
+    for mut job in storage::list_pending_notifications() {
+        let closure_notice = matches!(
+            job.notification_type,
+            NotificationType::InstanceCancelled | NotificationType::HostRemoved
+        );
+        if !closure_notice && about(&job) {
+            job.status = NotificationStatus::Cancelled;
+            storage::update_notification(job);
+            count += 1;
+        }
+    }
+    count
+}
+
+/// Cancel pending jobs about any of these instances (see `cancel_pending_where`)
+pub fn cancel_pending_for_instances(instance_ids: &[[u8; 16]]) -> u32 {
+    if instance_ids.is_empty() {
+        return 0;
+    }
+    cancel_pending_where(|job| job.instance_id.is_some_and(|id| instance_ids.contains(&id)))
+}
+
+/// Cancel pending jobs about any occurrence of a series (see `cancel_pending_where`)
+pub fn cancel_pending_for_series(series_id: &[u8; 16]) -> u32 {
+    cancel_pending_where(|job| job.series_id == Some(*series_id))
+}
+
+/// Flag pending jobs created before `cutoff` as stale by setting `requeued_at`
+/// to `now`. Returns the number of jobs touched. Nothing is sent from here.
+pub fn requeue_stale_pending(cutoff: u64, now: u64) -> u32 {
+    let mut count = 0;
+    
+    for mut job in storage::list_pending_notifications() {
+        if job.created_at < cutoff {
+            job.requeued_at = Some(now);
+            storage::update_notification(job);
+            count += 1;
+        }
+    }
+    
+    count
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn pending_job(tag: u8, created_at: u64) -> NotificationJob {
+        NotificationJob {
+            job_id: [tag; 16],
+            created_at,
+            notification_type: NotificationType::HostAssigned,
+            recipient_principal: candid::Principal::anonymous(),
+            recipient_email: "host@example.com".to_string(),
+            subject: "Assigned".to_string(),
+            body_text: String::new(),
+            ics_payload: None,
+            status: NotificationStatus::Pending,
+            sent_at: None,
+            error_message: None,
+            requeued_at: None,
+            instance_id: None,
+            series_id: None,
+        }
+    }
+
+    fn weekly_series(tag: u8, start_date: u64) -> EventSeries {
+        EventSeries {
+            series_id: [tag; 16],
+            title: "Office hours".to_string(),
+            notes: String::new(),
+            notes_template: None,
+            link: None,
+            frequency: Frequency::Weekly,
+            weekday: datetime::weekday_of(start_date),
+            weekday_ordinal: None,
+            start_date,
+            end_date: None,
+            default_duration_minutes: 60,
+            color: None,
+            paused: false,
+            claims_paused: false,
+            deleted: false,
+            public: false,
+            capacity: None,
+            default_host: None,
+            reminder_hours_before_override: None,
+            created_at: 0,
+            created_by: candid::Principal::anonymous(),
+        }
+    }
+
+    #[test]
+    fn only_sessions_inside_lead_time_need_coverage() {
+        storage::update_settings(GlobalSettings { namespaced_instance_ids: false, ..Default::default() });
+        let now = datetime::ymd_to_nanos(2024, 1, 1) + 12 * 3600 * 1_000_000_000;
+        let day = 86400 * 1_000_000_000;
+        let tomorrow = weekly_series(1, now + day);
+        let in_three_weeks = weekly_series(2, now + 21 * day);
+        storage::insert_series(tomorrow.clone());
+        storage::insert_series(in_three_weeks.clone());
+
+        let imminent = imminent_unclaimed(&tomorrow, now);
+        assert_eq!(imminent.len(), 1);
+        assert_eq!(imminent[0].start_utc, now + day);
+        assert!(imminent_unclaimed(&in_three_weeks, now).is_empty());
+    }
+
+    #[test]
+    fn series_reminder_override_beats_host_preference() {
+        let host = User {
+            principal: candid::Principal::from_slice(&[4]),
+            name: "Host".to_string(),
+            email: "host@example.com".to_string(),
+            role: Role::User,
+            status: UserStatus::Active,
+            out_of_office: vec![],
+            availability: vec![],
+            notification_settings: NotificationSettings {
+                reminder_hours_before: Some(1),
+                ..crate::auth::default_notification_settings()
+            },
+            last_active: 0,
+            sessions_hosted_count: 0,
+            created_at: 0,
+            updated_at: 0,
+            locale: None,
+        };
+        let mut series = weekly_series(3, 0);
+
+        assert_eq!(effective_reminder_hours(&host, Some(&series)), Some(1));
+        series.reminder_hours_before_override = Some(24);
+        assert_eq!(effective_reminder_hours(&host, Some(&series)), Some(24));
+        assert_eq!(effective_reminder_hours(&host, None), Some(1));
+    }
+
+    #[test]
+    fn ics_uid_round_trips_to_the_instance_id() {
+        let id = [0xab; 16];
+        let uid = ics_uid(&id);
+        assert_eq!(uid, format!("{}@ohscheduler.icp", "ab".repeat(16)));
+        assert_eq!(parse_ics_uid(&uid), Some(id));
+        assert_eq!(parse_ics_uid(&format!(" {} ", hex::encode(id))), Some(id));
+    }
+
+    #[test]
+    fn malformed_ics_uids_do_not_parse() {
+        assert_eq!(parse_ics_uid("not-hex@ohscheduler.icp"), None);
+        assert_eq!(parse_ics_uid("abcd@ohscheduler.icp"), None); // too short
+        assert_eq!(parse_ics_uid(""), None);
+    }
+
+    #[test]
+    fn only_jobs_older_than_cutoff_are_requeued() {
+        storage::insert_notification(pending_job(1, 100));
+        storage::insert_notification(pending_job(2, 500));
+
+        assert_eq!(requeue_stale_pending(300, 1_000), 1);
+
+        assert_eq!(storage::get_notification(&[1; 16]).unwrap().requeued_at, Some(1_000));
+        assert_eq!(storage::get_notification(&[2; 16]).unwrap().requeued_at, None);
+    }
 }
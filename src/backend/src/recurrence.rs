@@ -6,160 +6,137 @@
 //! 3. Generate deterministic instance IDs based on (series_id, occurrence_start)
 //! 4. Combine with one-off events and sort by start time
 
+use crate::datetime::{days_in_month, nanos_to_ymd, nth_weekday_of_month, weekday_from_nanos, ymd_to_nanos};
+use crate::notifications;
 use crate::storage;
 use crate::types::*;
+use candid::Principal;
 use sha2::{Digest, Sha256};
+use std::collections::{HashMap, HashSet};
 
 /// Generate a deterministic instance ID from series_id and occurrence start time
+/// When `namespaced_instance_ids` is on, the canister id is mixed in as well.
 pub fn generate_instance_id(series_id: &[u8; 16], occurrence_start: u64) -> [u8; 16] {
-    let mut hasher = Sha256::new();
-    hasher.update(series_id);
-    hasher.update(&occurrence_start.to_be_bytes());
-    let result = hasher.finalize();
-    let mut id = [0u8; 16];
-    id.copy_from_slice(&result[..16]);
-    id
+    instance_id_in(instance_namespace().as_ref(), series_id, occurrence_start)
 }
 
-/// Generate a random-ish UUID using time + counter
-/// Note: Not cryptographically secure, but sufficient for IDs
-static mut UUID_COUNTER: u64 = 0;
+/// Namespace series instance ids are currently generated in (None = legacy ids)
+/// Read once and pass to `instance_id_in` when generating many ids.
+pub fn instance_namespace() -> Option<Principal> {
+    storage::get_settings().namespaced_instance_ids.then(ic_cdk::id)
+}
 
-pub fn generate_uuid() -> [u8; 16] {
-    let time = ic_cdk::api::time();
-    let counter = unsafe {
-        UUID_COUNTER += 1;
-        UUID_COUNTER
-    };
+/// Instance id for an occurrence under an explicit namespace (None = legacy ids)
+pub fn instance_id_in(namespace: Option<&Principal>, series_id: &[u8; 16], occurrence_start: u64) -> [u8; 16] {
     let mut hasher = Sha256::new();
-    hasher.update(&time.to_be_bytes());
-    hasher.update(&counter.to_be_bytes());
+    if let Some(ns) = namespace {
+        hasher.update(ns.as_slice());
+    }
+    hasher.update(series_id);
+    hasher.update(occurrence_start.to_be_bytes());
     let result = hasher.finalize();
     let mut id = [0u8; 16];
     id.copy_from_slice(&result[..16]);
     id
 }
 
-
-/// Convert nanoseconds to days since epoch (for date calculations)
-fn nanos_to_days(nanos: u64) -> i64 {
-    (nanos / 1_000_000_000 / 86400) as i64
-}
-
-/// Convert days since epoch to nanoseconds (start of day UTC)
-fn days_to_nanos(days: i64) -> u64 {
-    (days as u64) * 86400 * 1_000_000_000
-}
-
-/// Get year, month, day from nanoseconds timestamp
-pub fn nanos_to_ymd(nanos: u64) -> (i32, u32, u32) {
-    // Simple algorithm: days since epoch -> date
-    let days = nanos_to_days(nanos) as i32;
-    
-    // Days since 1970-01-01
-    let mut y = 1970;
-    let mut remaining = days;
+/// Move data keyed by series instance id (RSVPs, host notes, and the instance
+/// ids and pending ICS UIDs on notification jobs) from the ids produced with namespacing
+/// `from_namespaced` to those produced with `to_namespaced`.
+/// Covers every occurrence from each series' start through the forward window.
+/// Returns the number of occurrences whose data moved.
+pub fn rekey_instance_ids(from_namespaced: bool, to_namespaced: bool) -> u32 {
+    if from_namespaced == to_namespaced {
+        return 0;
+    }
+    let canister = ic_cdk::id();
+    let from_ns = if from_namespaced { Some(&canister) } else { None };
+    let to_ns = if to_namespaced { Some(&canister) } else { None };
     
-    loop {
-        let days_in_year = if is_leap_year(y) { 366 } else { 365 };
-        if remaining < days_in_year {
-            break;
+    let settings = storage::get_settings();
+    let window_end = calculate_window_end(ic_cdk::api::time(), settings.forward_window_months);
+    let mut new_ids: HashMap<[u8; 16], [u8; 16]> = HashMap::new();
+    for series in storage::list_all_series().into_iter().chain(storage::list_deleted_series()) {
+        for occ_start in generate_occurrences(&series, series.start_date, window_end) {
+            new_ids.insert(
+                instance_id_in(from_ns, &series.series_id, occ_start),
+                instance_id_in(to_ns, &series.series_id, occ_start),
+            );
         }
-        remaining -= days_in_year;
-        y += 1;
     }
     
-    let mut m = 1u32;
-    loop {
-        let days_in_month = days_in_month(y, m);
-        if remaining < days_in_month as i32 {
-            break;
+    let mut moved = move_instance_data(&new_ids);
+    
+    for mut job in storage::list_all_notifications() {
+        let (old_id, new_id) = match job.instance_id.and_then(|id| new_ids.get(&id).map(|new| (id, *new))) {
+            Some(ids) => ids,
+            None => continue,
+        };
+        job.instance_id = Some(new_id);
+        if job.status == NotificationStatus::Pending {
+            // Unsent invites must carry the UID get_event_ics now serves
+            job.ics_payload = job.ics_payload
+                .map(|ics| ics.replace(&notifications::ics_uid(&old_id), &notifications::ics_uid(&new_id)));
         }
-        remaining -= days_in_month as i32;
-        m += 1;
+        moved.insert(old_id);
+        storage::update_notification(job);
     }
     
-    (y, m, (remaining + 1) as u32)
-}
-
-
-fn is_leap_year(year: i32) -> bool {
-    (year % 4 == 0 && year % 100 != 0) || (year % 400 == 0)
-}
-
-fn days_in_month(year: i32, month: u32) -> u32 {
-    match month {
-        1 | 3 | 5 | 7 | 8 | 10 | 12 => 31,
-        4 | 6 | 9 | 11 => 30,
-        2 => if is_leap_year(year) { 29 } else { 28 },
-        _ => 0,
-    }
+    moved.len() as u32
 }
 
-/// Convert year/month/day to nanoseconds (start of day UTC)
-fn ymd_to_nanos(year: i32, month: u32, day: u32) -> u64 {
-    let mut days: i64 = 0;
+/// Move the RSVPs and host notes stored under each old instance id in
+/// `new_ids` to its new id. Returns the old ids whose data moved.
+pub fn move_instance_data(new_ids: &HashMap<[u8; 16], [u8; 16]>) -> HashSet<[u8; 16]> {
+    let mut moved: HashSet<[u8; 16]> = HashSet::new();
     
-    // Years from 1970
-    for y in 1970..year {
-        days += if is_leap_year(y) { 366 } else { 365 };
+    for (old_id, new_id) in new_ids {
+        let rsvps = storage::get_rsvps(old_id);
+        if rsvps.attendees.is_empty() {
+            continue;
+        }
+        if storage::try_insert_rsvps(new_id, rsvps).is_ok() {
+            storage::delete_rsvps(old_id);
+            moved.insert(*old_id);
+        }
     }
     
-    // Months in current year
-    for m in 1..month {
-        days += days_in_month(year, m) as i64;
+    for (old_id, host, note) in storage::list_host_notes() {
+        let new_id = match new_ids.get(&old_id) {
+            Some(id) => id,
+            None => continue,
+        };
+        if storage::try_insert_host_note(new_id, &host, note).is_ok() {
+            storage::delete_host_note(&old_id, &host);
+            moved.insert(old_id);
+        }
     }
     
-    // Days in current month
-    days += (day - 1) as i64;
-    
-    days_to_nanos(days)
-}
-
-/// Get day of week (0=Mon, 6=Sun) from nanoseconds timestamp
-fn weekday_from_nanos(nanos: u64) -> u32 {
-    let days = nanos_to_days(nanos);
-    // 1970-01-01 was Thursday (3)
-    ((days + 3) % 7 + 7) as u32 % 7
+    moved
 }
 
+/// Generate a random-ish UUID using time + counter
+/// Note: Not cryptographically secure, but sufficient for IDs
+static mut UUID_COUNTER: u64 = 0;
 
-/// Find the nth occurrence of a weekday in a month
-/// Returns nanoseconds timestamp or None if doesn't exist
-fn nth_weekday_of_month(year: i32, month: u32, weekday: Weekday, ordinal: WeekdayOrdinal) -> Option<u64> {
-    let target_wd = weekday as u32;
-    
-    match ordinal {
-        WeekdayOrdinal::First | WeekdayOrdinal::Second | WeekdayOrdinal::Third | WeekdayOrdinal::Fourth => {
-            let n = ordinal as u32; // 1, 2, 3, or 4
-            let first_of_month = ymd_to_nanos(year, month, 1);
-            let first_wd = weekday_from_nanos(first_of_month);
-            
-            let days_until = (target_wd + 7 - first_wd) % 7;
-            let target_day = 1 + days_until + (n - 1) * 7;
-            
-            if target_day <= days_in_month(year, month) {
-                Some(ymd_to_nanos(year, month, target_day))
-            } else {
-                None // e.g., 5th Monday doesn't exist
-            }
-        }
-        WeekdayOrdinal::Last => {
-            let last_day = days_in_month(year, month);
-            let last_of_month = ymd_to_nanos(year, month, last_day);
-            let last_wd = weekday_from_nanos(last_of_month);
-            
-            let days_back = (last_wd + 7 - target_wd) % 7;
-            let target_day = last_day - days_back;
-            
-            Some(ymd_to_nanos(year, month, target_day))
-        }
-    }
+pub fn generate_uuid() -> [u8; 16] {
+    let time = ic_cdk::api::time();
+    let counter = unsafe {
+        UUID_COUNTER += 1;
+        UUID_COUNTER
+    };
+    let mut hasher = Sha256::new();
+    hasher.update(time.to_be_bytes());
+    hasher.update(counter.to_be_bytes());
+    let result = hasher.finalize();
+    let mut id = [0u8; 16];
+    id.copy_from_slice(&result[..16]);
+    id
 }
 
 
 /// Generate all occurrence timestamps for a series within a window
-fn generate_occurrences(
+pub fn generate_occurrences(
     series: &EventSeries,
     window_start: u64,
     window_end: u64,
@@ -247,8 +224,7 @@ pub fn calculate_window_end(from: u64, months: u8) -> u64 {
 /// Combines generated series occurrences with overrides and one-off events
 pub fn materialize_events(window_start: u64, window_end: u64) -> Vec<EventInstance> {
     let mut results = Vec::new();
-    let settings = storage::get_settings();
-    let now = ic_cdk::api::time();
+    let namespace = instance_namespace();
     
     // Generate instances from all series
     for series in storage::list_all_series() {
@@ -275,10 +251,11 @@ pub fn materialize_events(window_start: u64, window_end: u64) -> Vec<EventInstan
             }
             
             // Build instance
-            let instance_id = generate_instance_id(&series.series_id, occ_start);
+            let instance_id = instance_id_in(namespace.as_ref(), &series.series_id, occ_start);
             let start_utc = ovr.as_ref().and_then(|o| o.start_utc).unwrap_or(occ_start);
             let end_utc = ovr.as_ref().and_then(|o| o.end_utc).unwrap_or(occ_start + duration_nanos);
-            let notes = ovr.as_ref().and_then(|o| o.notes.clone()).unwrap_or(series.notes.clone());
+            let notes = occurrence_notes(&series, ovr.as_ref());
+            let title = ovr.as_ref().and_then(|o| o.title_override.clone()).unwrap_or(series.title.clone());
             
             // Host: check if explicitly cleared, otherwise use override value, then fall back to series default
             let host_principal = if ovr.as_ref().map(|o| o.host_cleared).unwrap_or(false) {
@@ -294,13 +271,22 @@ pub fn materialize_events(window_start: u64, window_end: u64) -> Vec<EventInstan
                 series_id: Some(series.series_id),
                 start_utc,
                 end_utc,
-                title: series.title.clone(),
+                title,
                 notes,
                 link: series.link.clone(),
                 host_principal,
                 status: EventStatus::Active,
                 color: series.color.clone(),
                 created_at: series.created_at,
+                occurrence_start: Some(occ_start),
+                assigned_by: ovr.as_ref().and_then(|o| o.assigned_by),
+                assigned_at: ovr.as_ref().and_then(|o| o.assigned_at),
+                created_by: Some(series.created_by),
+                public: series.public,
+                capacity: series.capacity,
+                rsvp_count: storage::rsvp_count(&instance_id),
+                overridden_fields: overridden_fields(ovr.as_ref()),
+                series_title: Some(series.title.clone()),
             });
         }
     }
@@ -308,49 +294,178 @@ pub fn materialize_events(window_start: u64, window_end: u64) -> Vec<EventInstan
     // Add one-off events within window
     for inst in storage::list_all_instances() {
         if inst.start_utc >= window_start && inst.start_utc < window_end && inst.status == EventStatus::Active {
-            results.push(inst);
+            results.push(with_rsvp_count(inst));
         }
     }
     
-    // Sort by start time
-    results.sort_by_key(|e| e.start_utc);
+    // Sort by start time, then instance id so simultaneous events keep a stable order
+    results.sort_by_key(|e| (e.start_utc, e.instance_id));
     results
 }
 
-/// Get unclaimed events within the forward window
-pub fn list_unclaimed_events() -> Vec<EventInstance> {
-    let now = ic_cdk::api::time();
+/// How far each step of the neighbor search looks, wider than the longest gap between occurrences
+const ADJACENT_SEARCH_STEP_NANOS: u64 = 62 * 86400 * 1_000_000_000;
+
+fn is_cancelled(series_id: &[u8; 16], occurrence_start: u64) -> bool {
+    storage::get_override(&OverrideKey {
+        series_id: *series_id,
+        occurrence_start_utc: occurrence_start,
+    })
+    .map(|o| o.cancelled)
+    .unwrap_or(false)
+}
+
+/// Nearest non-cancelled occurrence starts of a series before and after `occurrence_start`
+/// Looks back to the series start and forward to the end of the forward window.
+pub fn adjacent_occurrences(series: &EventSeries, occurrence_start: u64) -> AdjacentOccurrences {
+    let mut previous = None;
+    let mut window_end = occurrence_start;
+    while previous.is_none() && window_end > series.start_date {
+        let window_start = window_end.saturating_sub(ADJACENT_SEARCH_STEP_NANOS);
+        previous = generate_occurrences(series, window_start, window_end)
+            .into_iter()
+            .rev()
+            .find(|occ| !is_cancelled(&series.series_id, *occ));
+        window_end = window_start;
+    }
+    
     let settings = storage::get_settings();
-    let window_end = calculate_window_end(now, settings.forward_window_months);
+    let limit = calculate_window_end(ic_cdk::api::time(), settings.forward_window_months);
+    let limit = series.end_date.map_or(limit, |end| end.min(limit));
+    let mut next = None;
+    let mut window_start = occurrence_start + 1;
+    while next.is_none() && window_start < limit {
+        let window_end = (window_start + ADJACENT_SEARCH_STEP_NANOS).min(limit);
+        next = generate_occurrences(series, window_start, window_end)
+            .into_iter()
+            .find(|occ| !is_cancelled(&series.series_id, *occ));
+        window_start = window_end;
+    }
     
-    materialize_events(now, window_end)
-        .into_iter()
-        .filter(|e| e.host_principal.is_none())
-        .collect()
+    AdjacentOccurrences { previous, next }
 }
 
+/// Notes an occurrence shows: its override's notes, else the series notes
+pub fn occurrence_notes(series: &EventSeries, ovr: Option<&InstanceOverride>) -> String {
+    ovr.and_then(|o| o.notes.clone()).unwrap_or_else(|| series.notes.clone())
+}
 
-/// Get the start/end nanos and label for `months_back` months ago.
-/// months_back=0 means current month.
-pub fn month_window(now: u64, months_back: u8) -> (u64, u64, String) {
-    let (mut y, mut m, _) = nanos_to_ymd(now);
-    
-    for _ in 0..months_back {
-        if m == 1 {
-            m = 12;
-            y -= 1;
-        } else {
-            m -= 1;
+/// Which parts of an occurrence an override changes
+pub fn overridden_fields(ovr: Option<&InstanceOverride>) -> Vec<OverrideField> {
+    let mut fields = Vec::new();
+    if let Some(o) = ovr {
+        if o.start_utc.is_some() || o.end_utc.is_some() {
+            fields.push(OverrideField::Time);
+        }
+        if o.title_override.is_some() {
+            fields.push(OverrideField::Title);
+        }
+        if o.notes.is_some() {
+            fields.push(OverrideField::Notes);
+        }
+        if o.host_principal.is_some() || o.host_cleared {
+            fields.push(OverrideField::Host);
         }
     }
+    fields
+}
+
+/// Fill in the live RSVP count on a stored one-off
+pub fn with_rsvp_count(mut inst: EventInstance) -> EventInstance {
+    inst.rsvp_count = storage::rsvp_count(&inst.instance_id);
+    inst
+}
+
+/// Find a single event by instance id
+/// One-offs are looked up directly; series occurrences are searched from the
+/// epoch through the end of the forward window.
+pub fn find_event(instance_id: &[u8; 16]) -> Option<EventInstance> {
+    if let Some(inst) = storage::get_instance(instance_id) {
+        return Some(with_rsvp_count(inst));
+    }
+    
+    let settings = storage::get_settings();
+    let now = ic_cdk::api::time();
+    let window_end = calculate_window_end(now, settings.forward_window_months);
+    
+    materialize_events(0, window_end)
+        .into_iter()
+        .find(|e| e.instance_id == *instance_id)
+}
+
+/// Find a cancelled series occurrence by instance id
+/// Cancelled occurrences aren't materialized, so `find_event` never returns them.
+pub fn find_cancelled_occurrence(instance_id: &[u8; 16]) -> Option<EventInstance> {
+    let namespace = instance_namespace();
+    let ovr = storage::list_all_overrides()
+        .into_iter()
+        .filter(|o| o.cancelled)
+        .find(|o| instance_id_in(namespace.as_ref(), &o.series_id, o.occurrence_start_utc) == *instance_id)?;
+    let series = storage::get_series(&ovr.series_id).filter(|s| !s.deleted)?;
+    let duration_nanos = (series.default_duration_minutes as u64) * 60 * 1_000_000_000;
+    let occ_start = ovr.occurrence_start_utc;
     
-    let start = ymd_to_nanos(y, m, 1);
+    Some(EventInstance {
+        instance_id: *instance_id,
+        series_id: Some(series.series_id),
+        start_utc: ovr.start_utc.unwrap_or(occ_start),
+        end_utc: ovr.end_utc.unwrap_or(occ_start + duration_nanos),
+        title: ovr.title_override.clone().unwrap_or(series.title.clone()),
+        notes: occurrence_notes(&series, Some(&ovr)),
+        link: series.link.clone(),
+        host_principal: if ovr.host_cleared { None } else { ovr.host_principal.or(series.default_host) },
+        status: EventStatus::Cancelled,
+        color: series.color.clone(),
+        created_at: series.created_at,
+        occurrence_start: Some(occ_start),
+        assigned_by: ovr.assigned_by,
+        assigned_at: ovr.assigned_at,
+        created_by: Some(series.created_by),
+        public: series.public,
+        capacity: series.capacity,
+        rsvp_count: storage::rsvp_count(instance_id),
+        overridden_fields: overridden_fields(Some(&ovr)),
+        series_title: Some(series.title),
+    })
+}
+
+/// Most events returned by one `events_page` call
+pub const MAX_EVENTS_PAGE_SIZE: u32 = 200;
+
+/// Up to `limit` events starting after `after_start_utc` (or from now) within the
+/// forward window. A page never splits events sharing a start time, so it may run
+/// slightly over `limit`; that keeps the start-time cursor from skipping any.
+pub fn events_page(after_start_utc: Option<u64>, limit: u32) -> EventsPage {
+    let now = ic_cdk::api::time();
+    let window_end = calculate_window_end(now, storage::get_settings().forward_window_months);
+    let window_start = after_start_utc.map(|a| a.saturating_add(1)).unwrap_or(now);
+    let limit = limit.clamp(1, MAX_EVENTS_PAGE_SIZE) as usize;
     
-    let (ny, nm) = if m == 12 { (y + 1, 1) } else { (y, m + 1) };
-    let end = ymd_to_nanos(ny, nm, 1);
+    let mut events = materialize_events(window_start, window_end);
+    if events.len() <= limit {
+        return EventsPage { events, next_cursor: None };
+    }
+    let last_start = events[limit - 1].start_utc;
+    let cut = events.iter().position(|e| e.start_utc > last_start).unwrap_or(events.len());
+    let next_cursor = (cut < events.len()).then_some(last_start);
+    events.truncate(cut);
     
-    let month_names = ["Jan","Feb","Mar","Apr","May","Jun","Jul","Aug","Sep","Oct","Nov","Dec"];
-    let label = format!("{} {}", month_names[(m - 1) as usize], y);
+    EventsPage { events, next_cursor }
+}
+
+/// Get unclaimed events within the forward window
+pub fn list_unclaimed_events() -> Vec<EventInstance> {
+    let now = ic_cdk::api::time();
+    let settings = storage::get_settings();
+    let window_end = calculate_window_end(now, settings.forward_window_months);
     
-    (start, end, label)
+    list_unclaimed_events_between(now, window_end)
+}
+
+/// Get unclaimed events within an arbitrary window
+pub fn list_unclaimed_events_between(window_start: u64, window_end: u64) -> Vec<EventInstance> {
+    materialize_events(window_start, window_end)
+        .into_iter()
+        .filter(|e| e.host_principal.is_none())
+        .collect()
 }
@@ -0,0 +1,374 @@
+//! Reporting helpers built on top of materialized events
+//!
+//! Everything here is read-only: events are materialized for the requested
+//! window and then bucketed. Local days follow the org timezone (with its
+//! daylight-saving rule) when one is set, else a caller-supplied UTC offset.
+
+use crate::datetime;
+use crate::recurrence;
+use crate::storage;
+use crate::types::*;
+use candid::Principal;
+
+const NANOS_PER_DAY: i64 = 86400 * 1_000_000_000;
+const NANOS_PER_MINUTE: i64 = 60 * 1_000_000_000;
+
+/// Sessions shorter than this are flagged as anomalous, unless a minimum
+/// duration is configured in settings
+pub const ANOMALY_MIN_DURATION_MINUTES: u64 = 5;
+/// Sessions longer than this are flagged as anomalous, unless a maximum
+/// duration is configured in settings
+pub const ANOMALY_MAX_DURATION_MINUTES: u64 = 8 * 60;
+
+/// Local day index (days since 1970-01-01 in local time) for a UTC timestamp
+fn local_day_index(nanos: u64, utc_offset_minutes: i32) -> i64 {
+    let local = nanos as i64 + utc_offset_minutes as i64 * NANOS_PER_MINUTE;
+    local.div_euclid(NANOS_PER_DAY)
+}
+
+fn weekday_from_day_index(day: i64) -> Weekday {
+    // 1970-01-01 was a Thursday
+    match (day + 3).rem_euclid(7) {
+        0 => Weekday::Mon,
+        1 => Weekday::Tue,
+        2 => Weekday::Wed,
+        3 => Weekday::Thu,
+        4 => Weekday::Fri,
+        5 => Weekday::Sat,
+        _ => Weekday::Sun,
+    }
+}
+
+fn day_label(day: i64) -> String {
+    let (y, m, d) = datetime::nanos_to_ymd((day.max(0) * NANOS_PER_DAY) as u64);
+    format!("{:04}-{:02}-{:02}", y, m, d)
+}
+
+/// Count sessions per local day across the window
+///
+/// Days are local to `timezone` when given (see `datetime::utc_offset_at`),
+/// otherwise to the fixed `utc_offset_minutes`.
+pub fn day_utilization(
+    window_start: u64,
+    window_end: u64,
+    timezone: Option<&str>,
+    utc_offset_minutes: i32,
+) -> DayUtilization {
+    let starts: Vec<u64> = recurrence::materialize_events(window_start, window_end)
+        .iter()
+        .map(|e| e.start_utc)
+        .collect();
+    let offset_at = |nanos: u64| {
+        timezone
+            .and_then(|tz| datetime::utc_offset_at(tz, nanos))
+            .unwrap_or(utc_offset_minutes)
+    };
+    bucket_days(&starts, window_start, window_end, offset_at)
+}
+
+/// Count session starts per local day, with `offset_at` giving the UTC offset
+/// in effect at an instant
+///
+/// Every local day touched by the window is reported, including days with no
+/// sessions, so the quietest day is meaningful. Ties for busiest/quietest go
+/// to the earliest day.
+fn bucket_days(starts: &[u64], window_start: u64, window_end: u64, offset_at: impl Fn(u64) -> i32) -> DayUtilization {
+    let day_of = |nanos: u64| local_day_index(nanos, offset_at(nanos));
+    let first_day = day_of(window_start);
+    let last_day = day_of(window_end.saturating_sub(1));
+
+    let mut counts = vec![0u32; (last_day - first_day + 1).max(0) as usize];
+    for &start in starts {
+        let idx = day_of(start) - first_day;
+        if let Some(c) = counts.get_mut(idx as usize) {
+            *c += 1;
+        }
+    }
+
+    let days: Vec<DayCount> = counts.iter().enumerate().map(|(i, &session_count)| {
+        let day = first_day + i as i64;
+        DayCount {
+            date: day_label(day),
+            weekday: weekday_from_day_index(day),
+            session_count,
+        }
+    }).collect();
+
+    let busiest = days.iter()
+        .fold(None::<&DayCount>, |best, d| match best {
+            Some(b) if b.session_count >= d.session_count => Some(b),
+            _ => Some(d),
+        })
+        .cloned();
+    let quietest = days.iter()
+        .fold(None::<&DayCount>, |best, d| match best {
+            Some(b) if b.session_count <= d.session_count => Some(b),
+            _ => Some(d),
+        })
+        .cloned();
+
+    DayUtilization { days, busiest, quietest }
+}
+
+/// Assigned vs unassigned session counts for one reporting period
+pub fn coverage_stats(window_start: u64, window_end: u64, period_label: String) -> CoverageStats {
+    let events = recurrence::materialize_events(window_start, window_end);
+    let total = events.len() as u32;
+    let assigned = events.iter().filter(|e| e.host_principal.is_some()).count() as u32;
+    let unassigned = total - assigned;
+    let coverage_pct = if total > 0 { (assigned as f64 / total as f64) * 100.0 } else { 0.0 };
+    
+    CoverageStats {
+        period_label,
+        total_sessions: total,
+        assigned,
+        unassigned,
+        coverage_pct,
+    }
+}
+
+/// Average lead time between claim and session start for a series' occurrences
+/// originally scheduled within the window
+///
+/// Claim time is the override's `assigned_at`. Occurrences without a host
+/// assignment (never claimed, covered only by the series' default host, or
+/// assigned before assignment times were recorded) and cancelled occurrences
+/// are excluded. Claims made after the session started
+/// count as zero lead time.
+pub fn claim_lead_times(series_id: &[u8; 16], window_start: u64, window_end: u64) -> ClaimLeadTimes {
+    let leads: Vec<u64> = storage::list_overrides_for_series(series_id)
+        .into_iter()
+        .filter(|o| o.occurrence_start_utc >= window_start && o.occurrence_start_utc < window_end)
+        .filter(|o| o.host_principal.is_some() && !o.cancelled)
+        .filter_map(|o| {
+            let start = o.start_utc.unwrap_or(o.occurrence_start_utc);
+            o.assigned_at.map(|at| start.saturating_sub(at) / 1_000_000_000)
+        })
+        .collect();
+    
+    let average_lead_seconds = if leads.is_empty() {
+        None
+    } else {
+        Some(leads.iter().sum::<u64>() / leads.len() as u64)
+    };
+    
+    ClaimLeadTimes {
+        claimed_count: leads.len() as u32,
+        average_lead_seconds,
+    }
+}
+
+/// Sessions and hours per host across the window, busiest first
+///
+/// Unassigned sessions are reported as a pseudo-entry with no host. Hosts no
+/// longer in the user table are listed by principal text.
+pub fn host_stats(window_start: u64, window_end: u64) -> Vec<HostStats> {
+    let events = recurrence::materialize_events(window_start, window_end);
+    let users = storage::list_all_users();
+    
+    let mut stats: Vec<HostStats> = Vec::new();
+    for e in &events {
+        let hours = e.end_utc.saturating_sub(e.start_utc) as f64 / 3600.0 / 1_000_000_000.0;
+        match stats.iter_mut().find(|s| s.host == e.host_principal) {
+            Some(entry) => {
+                entry.session_count += 1;
+                entry.total_hours += hours;
+            }
+            None => {
+                let name = match e.host_principal {
+                    Some(p) => users.iter()
+                        .find(|u| u.principal == p)
+                        .map(|u| u.name.clone())
+                        .unwrap_or_else(|| p.to_text()),
+                    None => "Unassigned".to_string(),
+                };
+                stats.push(HostStats {
+                    host: e.host_principal,
+                    name,
+                    session_count: 1,
+                    total_hours: hours,
+                });
+            }
+        }
+    }
+    
+    stats.sort_by(|a, b| b.session_count.cmp(&a.session_count).then_with(|| a.name.cmp(&b.name)));
+    stats
+}
+
+/// Per series, how many occurrences `host` is explicitly assigned to, most first
+///
+/// Counts non-cancelled overrides naming the host, plus active one-off sessions
+/// (reported as a pseudo-entry with no series). Series defaults are not counted.
+pub fn host_series_summary(host: &Principal) -> Vec<HostSeriesCount> {
+    let mut counts: Vec<HostSeriesCount> = Vec::new();
+    
+    for ovr in storage::list_all_overrides() {
+        if ovr.cancelled || ovr.host_cleared || ovr.host_principal != Some(*host) {
+            continue;
+        }
+        match counts.iter_mut().find(|c| c.series_id == Some(ovr.series_id)) {
+            Some(entry) => entry.count += 1,
+            None => {
+                let series = match storage::get_series(&ovr.series_id) {
+                    Some(s) => s,
+                    None => continue, // deleted series
+                };
+                counts.push(HostSeriesCount {
+                    series_id: Some(series.series_id),
+                    title: series.title,
+                    count: 1,
+                });
+            }
+        }
+    }
+    
+    let one_offs = storage::list_all_instances()
+        .into_iter()
+        .filter(|i| i.status == EventStatus::Active && i.host_principal == Some(*host))
+        .count() as u32;
+    if one_offs > 0 {
+        counts.push(HostSeriesCount {
+            series_id: None,
+            title: "One-off sessions".to_string(),
+            count: one_offs,
+        });
+    }
+    
+    counts.sort_by(|a, b| b.count.cmp(&a.count).then_with(|| a.title.cmp(&b.title)));
+    counts
+}
+
+/// Sessions in the window whose duration is zero, negative, or outside
+/// the configured duration limits (or the default anomaly bounds)
+pub fn anomalous_sessions(window_start: u64, window_end: u64) -> Vec<AnomalousSession> {
+    let settings = storage::get_settings();
+    let min_minutes = settings.min_duration_minutes.map(|m| m as u64).unwrap_or(ANOMALY_MIN_DURATION_MINUTES);
+    let max_minutes = settings.max_duration_minutes.map(|m| m as u64).unwrap_or(ANOMALY_MAX_DURATION_MINUTES);
+    
+    recurrence::materialize_events(window_start, window_end)
+        .into_iter()
+        .filter_map(|event| {
+            let reason = if event.end_utc == event.start_utc {
+                "Zero duration".to_string()
+            } else if event.end_utc < event.start_utc {
+                "Ends before it starts".to_string()
+            } else {
+                let minutes = (event.end_utc - event.start_utc) / NANOS_PER_MINUTE as u64;
+                if minutes < min_minutes {
+                    format!("Shorter than {} minutes ({} min)", min_minutes, minutes)
+                } else if minutes > max_minutes {
+                    format!("Longer than {} minutes ({} min)", max_minutes, minutes)
+                } else {
+                    return None;
+                }
+            };
+            Some(AnomalousSession { event, reason })
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const HOUR: u64 = 3600 * 1_000_000_000;
+
+    #[test]
+    fn busiest_day_has_the_most_sessions() {
+        let monday = datetime::ymd_to_nanos(2024, 1, 1);
+        let mut starts = vec![monday + 9 * HOUR, monday + 11 * HOUR, monday + 14 * HOUR];
+        starts.extend((1..7).map(|d| monday + d * 24 * HOUR + 10 * HOUR));
+
+        let util = bucket_days(&starts, monday, monday + 7 * 24 * HOUR, |_| 0);
+
+        assert_eq!(util.days.len(), 7);
+        let busiest = util.busiest.unwrap();
+        assert_eq!(busiest.date, "2024-01-01");
+        assert_eq!(busiest.weekday, Weekday::Mon);
+        assert_eq!(busiest.session_count, 3);
+        assert_eq!(util.quietest.unwrap().date, "2024-01-02");
+    }
+
+    #[test]
+    fn claim_made_48h_ahead_averages_48h() {
+        let series_id = [7; 16];
+        let admin = candid::Principal::from_slice(&[1]);
+        let host = candid::Principal::from_slice(&[2]);
+        let start = datetime::ymd_to_nanos(2024, 5, 6) + 15 * HOUR;
+
+        let claimed = InstanceOverride {
+            host_principal: Some(host),
+            assigned_by: Some(host),
+            assigned_at: Some(start - 48 * HOUR),
+            ..InstanceOverride::new(series_id, start, host, start - 48 * HOUR)
+        };
+        let retitled = InstanceOverride {
+            title_override: Some("Exam review".to_string()),
+            ..InstanceOverride::new(series_id, start + 24 * 7 * HOUR, admin, start)
+        };
+        storage::insert_override(claimed);
+        storage::insert_override(retitled);
+
+        let leads = claim_lead_times(&series_id, start - HOUR, start + 30 * 24 * HOUR);
+        assert_eq!(leads.claimed_count, 1);
+        assert_eq!(leads.average_lead_seconds, Some(48 * 3600));
+    }
+
+    #[test]
+    fn ten_hour_and_zero_length_one_offs_are_anomalous() {
+        storage::update_settings(GlobalSettings { namespaced_instance_ids: false, ..Default::default() });
+        let day = datetime::ymd_to_nanos(2024, 5, 6);
+        for (tag, start_hour, minutes) in [(1u8, 8, 600u64), (2, 9, 0), (3, 10, 60)] {
+            let start_utc = day + start_hour * HOUR;
+            storage::insert_instance(EventInstance {
+                instance_id: [tag; 16],
+                series_id: None,
+                start_utc,
+                end_utc: start_utc + minutes * NANOS_PER_MINUTE as u64,
+                title: String::new(),
+                notes: String::new(),
+                link: None,
+                host_principal: None,
+                status: EventStatus::Active,
+                color: None,
+                created_at: 0,
+                occurrence_start: None,
+                assigned_by: None,
+                assigned_at: None,
+                created_by: None,
+                public: false,
+                capacity: None,
+                rsvp_count: 0,
+                overridden_fields: vec![],
+                series_title: None,
+            });
+        }
+
+        let anomalies = anomalous_sessions(day, day + 24 * HOUR);
+        let flagged: Vec<([u8; 16], &str)> = anomalies.iter()
+            .map(|a| (a.event.instance_id, a.reason.as_str()))
+            .collect();
+        assert_eq!(flagged, vec![
+            ([1; 16], "Longer than 480 minutes (600 min)"),
+            ([2; 16], "Zero duration"),
+        ]);
+    }
+
+    #[test]
+    fn buckets_follow_dst_in_the_org_timezone() {
+        // 04:30 UTC on 11 March 2024 is 00:30 EDT on the 11th, but 23:30 on
+        // the 10th under a fixed EST offset
+        let start = datetime::ymd_to_nanos(2024, 3, 11) + 4 * HOUR + HOUR / 2;
+        let window_start = datetime::ymd_to_nanos(2024, 3, 10) + 5 * HOUR;
+        let window_end = window_start + 2 * 24 * HOUR;
+
+        let dst = bucket_days(&[start], window_start, window_end, |t| {
+            datetime::utc_offset_at("America/New_York", t).unwrap()
+        });
+        let fixed = bucket_days(&[start], window_start, window_end, |_| -300);
+
+        assert_eq!(dst.busiest.unwrap().date, "2024-03-11");
+        assert_eq!(fixed.busiest.unwrap().date, "2024-03-10");
+    }
+}
@@ -8,18 +8,29 @@
 //! - Memory 4: GlobalSettings (StableCell)
 //! - Memory 5: NotificationJobs (Uuid -> NotificationJob)
 //! - Memory 6: InviteCodes (InviteCodeKey -> InviteCode)
+//! - Memory 7: AuditLog (seq u64 -> AuditEntry), append-only
+//! - Memory 8: SettingsHistory (seq u64 -> SettingsChange), capped
+//! - Memory 9: RedeemAttempts (Principal -> RedeemAttempts)
+//! - Memory 10: Rsvps (Uuid instance_id -> RsvpList)
+//! - Memory 11: HostNotes ((Uuid instance_id, host Principal) -> HostNote)
+//! - Memory 12: Observers (Principal -> added_at)
+//! - Memory 13: LastCoverageAlertWeek (StableCell u64)
 
 use crate::types::*;
 use candid::Principal;
 use ic_stable_structures::{
     memory_manager::{MemoryId, MemoryManager, VirtualMemory},
-    storable::Blob,
-    DefaultMemoryImpl, StableBTreeMap, StableCell,
+    storable::{Blob, Bound},
+    DefaultMemoryImpl, StableBTreeMap, StableCell, Storable,
 };
 use std::cell::RefCell;
 
 type Memory = VirtualMemory<DefaultMemoryImpl>;
 
+/// Version of the stored data encoding. Bump whenever a `Storable` type's
+/// encoding changes (new field, new migration branch, new memory).
+pub const SCHEMA_VERSION: u32 = 12;
+
 const USERS_MEM_ID: MemoryId = MemoryId::new(0);
 const SERIES_MEM_ID: MemoryId = MemoryId::new(1);
 const OVERRIDES_MEM_ID: MemoryId = MemoryId::new(2);
@@ -27,6 +38,13 @@ const INSTANCES_MEM_ID: MemoryId = MemoryId::new(3);
 const SETTINGS_MEM_ID: MemoryId = MemoryId::new(4);
 const NOTIFICATIONS_MEM_ID: MemoryId = MemoryId::new(5);
 const INVITE_CODES_MEM_ID: MemoryId = MemoryId::new(6);
+const AUDIT_LOG_MEM_ID: MemoryId = MemoryId::new(7);
+const SETTINGS_HISTORY_MEM_ID: MemoryId = MemoryId::new(8);
+const REDEEM_ATTEMPTS_MEM_ID: MemoryId = MemoryId::new(9);
+const RSVPS_MEM_ID: MemoryId = MemoryId::new(10);
+const HOST_NOTES_MEM_ID: MemoryId = MemoryId::new(11);
+const OBSERVERS_MEM_ID: MemoryId = MemoryId::new(12);
+const LAST_COVERAGE_ALERT_WEEK_MEM_ID: MemoryId = MemoryId::new(13);
 
 
 thread_local! {
@@ -75,6 +93,50 @@ thread_local! {
             MEMORY_MANAGER.with(|m| m.borrow().get(INVITE_CODES_MEM_ID))
         )
     );
+
+    static AUDIT_LOG: RefCell<StableBTreeMap<u64, AuditEntry, Memory>> = RefCell::new(
+        StableBTreeMap::init(
+            MEMORY_MANAGER.with(|m| m.borrow().get(AUDIT_LOG_MEM_ID))
+        )
+    );
+
+    static SETTINGS_HISTORY: RefCell<StableBTreeMap<u64, SettingsChange, Memory>> = RefCell::new(
+        StableBTreeMap::init(
+            MEMORY_MANAGER.with(|m| m.borrow().get(SETTINGS_HISTORY_MEM_ID))
+        )
+    );
+
+    static REDEEM_ATTEMPTS: RefCell<StableBTreeMap<Blob<29>, RedeemAttempts, Memory>> = RefCell::new(
+        StableBTreeMap::init(
+            MEMORY_MANAGER.with(|m| m.borrow().get(REDEEM_ATTEMPTS_MEM_ID))
+        )
+    );
+
+    static RSVPS: RefCell<StableBTreeMap<Uuid, RsvpList, Memory>> = RefCell::new(
+        StableBTreeMap::init(
+            MEMORY_MANAGER.with(|m| m.borrow().get(RSVPS_MEM_ID))
+        )
+    );
+
+    static HOST_NOTES: RefCell<StableBTreeMap<(Uuid, Blob<29>), HostNote, Memory>> = RefCell::new(
+        StableBTreeMap::init(
+            MEMORY_MANAGER.with(|m| m.borrow().get(HOST_NOTES_MEM_ID))
+        )
+    );
+
+    static OBSERVERS: RefCell<StableBTreeMap<Blob<29>, u64, Memory>> = RefCell::new(
+        StableBTreeMap::init(
+            MEMORY_MANAGER.with(|m| m.borrow().get(OBSERVERS_MEM_ID))
+        )
+    );
+
+    /// Start of the week a coverage alert was last sent in, so it fires once per week
+    static LAST_COVERAGE_ALERT_WEEK: RefCell<StableCell<u64, Memory>> = RefCell::new(
+        StableCell::init(
+            MEMORY_MANAGER.with(|m| m.borrow().get(LAST_COVERAGE_ALERT_WEEK_MEM_ID)),
+            0
+        ).expect("Failed to initialize coverage alert week")
+    );
 }
 
 
@@ -87,6 +149,21 @@ fn principal_to_blob(p: &Principal) -> Blob<29> {
     Blob::try_from(bytes).expect("Principal too large")
 }
 
+/// Check that a value fits its Storable bound before it reaches a map
+/// Inserting an oversized value traps the canister; the `try_insert_*`
+/// functions below use this to return an error instead.
+fn check_size<V: Storable>(value: &V, what: &str) -> ApiResult<()> {
+    if let Bound::Bounded { max_size, .. } = V::BOUND {
+        let size = value.to_bytes().len();
+        if size > max_size as usize {
+            return Err(ApiError::InvalidInput(format!(
+                "{} is too large to store ({} bytes, limit {})", what, size, max_size
+            )));
+        }
+    }
+    Ok(())
+}
+
 // ============================================================================
 // User Storage
 // ============================================================================
@@ -105,6 +182,12 @@ pub fn update_user(user: User) {
     insert_user(user);
 }
 
+pub fn try_insert_user(user: User) -> ApiResult<()> {
+    check_size(&user, "User")?;
+    insert_user(user);
+    Ok(())
+}
+
 pub fn list_all_users() -> Vec<User> {
     USERS.with(|users| {
         users.borrow().iter().map(|(_, u)| u).collect()
@@ -124,7 +207,12 @@ pub fn delete_user(principal: &Principal) -> bool {
 // EventSeries Storage
 // ============================================================================
 
+/// Get a series, treating soft-deleted ones as absent
 pub fn get_series(series_id: &[u8; 16]) -> Option<EventSeries> {
+    get_series_including_deleted(series_id).filter(|s| !s.deleted)
+}
+
+pub fn get_series_including_deleted(series_id: &[u8; 16]) -> Option<EventSeries> {
     SERIES.with(|s| s.borrow().get(&Uuid::new(*series_id)))
 }
 
@@ -134,12 +222,23 @@ pub fn insert_series(series: EventSeries) {
     });
 }
 
+pub fn try_insert_series(series: EventSeries) -> ApiResult<()> {
+    check_size(&series, "Series")?;
+    insert_series(series);
+    Ok(())
+}
+
 pub fn delete_series(series_id: &[u8; 16]) -> bool {
     SERIES.with(|s| s.borrow_mut().remove(&Uuid::new(*series_id)).is_some())
 }
 
+/// All series except soft-deleted ones
 pub fn list_all_series() -> Vec<EventSeries> {
-    SERIES.with(|s| s.borrow().iter().map(|(_, v)| v).collect())
+    SERIES.with(|s| s.borrow().iter().map(|(_, v)| v).filter(|v| !v.deleted).collect())
+}
+
+pub fn list_deleted_series() -> Vec<EventSeries> {
+    SERIES.with(|s| s.borrow().iter().map(|(_, v)| v).filter(|v| v.deleted).collect())
 }
 
 // ============================================================================
@@ -160,6 +259,12 @@ pub fn insert_override(ovr: InstanceOverride) {
     });
 }
 
+pub fn try_insert_override(ovr: InstanceOverride) -> ApiResult<()> {
+    check_size(&ovr, "Instance override")?;
+    insert_override(ovr);
+    Ok(())
+}
+
 pub fn list_overrides_for_series(series_id: &[u8; 16]) -> Vec<InstanceOverride> {
     let start_key = OverrideKey {
         series_id: *series_id,
@@ -177,6 +282,29 @@ pub fn list_overrides_for_series(series_id: &[u8; 16]) -> Vec<InstanceOverride>
     })
 }
 
+pub fn delete_override(key: &OverrideKey) -> bool {
+    OVERRIDES.with(|o| o.borrow_mut().remove(key).is_some())
+}
+
+/// Remove every override for a series, returning how many were removed
+pub fn delete_overrides_for_series(series_id: &[u8; 16]) -> u32 {
+    let keys: Vec<OverrideKey> = list_overrides_for_series(series_id)
+        .into_iter()
+        .map(|o| OverrideKey { series_id: o.series_id, occurrence_start_utc: o.occurrence_start_utc })
+        .collect();
+    OVERRIDES.with(|o| {
+        let mut map = o.borrow_mut();
+        for key in &keys {
+            map.remove(key);
+        }
+    });
+    keys.len() as u32
+}
+
+pub fn list_all_overrides() -> Vec<InstanceOverride> {
+    OVERRIDES.with(|o| o.borrow().iter().map(|(_, v)| v).collect())
+}
+
 
 // ============================================================================
 // One-Off EventInstance Storage
@@ -192,6 +320,12 @@ pub fn insert_instance(inst: EventInstance) {
     });
 }
 
+pub fn try_insert_instance(inst: EventInstance) -> ApiResult<()> {
+    check_size(&inst, "Event")?;
+    insert_instance(inst);
+    Ok(())
+}
+
 pub fn delete_instance(instance_id: &[u8; 16]) -> bool {
     INSTANCES.with(|i| i.borrow_mut().remove(&Uuid::new(*instance_id)).is_some())
 }
@@ -214,6 +348,22 @@ pub fn update_settings(settings: GlobalSettings) {
     });
 }
 
+pub fn try_update_settings(settings: GlobalSettings) -> ApiResult<()> {
+    check_size(&settings, "Settings")?;
+    update_settings(settings);
+    Ok(())
+}
+
+pub fn get_last_coverage_alert_week() -> u64 {
+    LAST_COVERAGE_ALERT_WEEK.with(|w| *w.borrow().get())
+}
+
+pub fn set_last_coverage_alert_week(week_start: u64) {
+    LAST_COVERAGE_ALERT_WEEK.with(|w| {
+        w.borrow_mut().set(week_start).expect("Failed to update coverage alert week");
+    });
+}
+
 // ============================================================================
 // NotificationJob Storage
 // ============================================================================
@@ -228,6 +378,12 @@ pub fn insert_notification(job: NotificationJob) {
     });
 }
 
+pub fn try_insert_notification(job: NotificationJob) -> ApiResult<()> {
+    check_size(&job, "Notification")?;
+    insert_notification(job);
+    Ok(())
+}
+
 pub fn list_pending_notifications() -> Vec<NotificationJob> {
     NOTIFICATIONS.with(|n| {
         n.borrow()
@@ -238,6 +394,54 @@ pub fn list_pending_notifications() -> Vec<NotificationJob> {
     })
 }
 
+pub fn count_notifications_by_status() -> NotificationCounts {
+    NOTIFICATIONS.with(|n| {
+        let mut counts = NotificationCounts::default();
+        for (_, job) in n.borrow().iter() {
+            match job.status {
+                NotificationStatus::Pending => counts.pending += 1,
+                NotificationStatus::Sent => counts.sent += 1,
+                NotificationStatus::Failed => counts.failed += 1,
+                NotificationStatus::Cancelled => counts.cancelled += 1,
+            }
+        }
+        counts
+    })
+}
+
+pub fn list_notifications_with_status(status: NotificationStatus) -> Vec<NotificationJob> {
+    NOTIFICATIONS.with(|n| {
+        n.borrow()
+            .iter()
+            .filter(|(_, job)| job.status == status)
+            .map(|(_, job)| job)
+            .collect()
+    })
+}
+
+/// Jobs in `status` matching the optional type and recipient, at most `limit`
+pub fn list_notifications_filtered(
+    status: NotificationStatus,
+    notification_type: Option<NotificationType>,
+    recipient: Option<Principal>,
+    limit: usize,
+) -> Vec<NotificationJob> {
+    NOTIFICATIONS.with(|n| {
+        n.borrow()
+            .iter()
+            .map(|(_, job)| job)
+            .filter(|job| job.status == status)
+            .filter(|job| notification_type.is_none_or(|t| job.notification_type == t))
+            .filter(|job| recipient.is_none_or(|p| job.recipient_principal == p))
+            .take(limit)
+            .collect()
+    })
+}
+
+pub fn list_all_notifications() -> Vec<NotificationJob> {
+    NOTIFICATIONS.with(|n| n.borrow().iter().map(|(_, job)| job).collect())
+}
+
 pub fn update_notification(job: NotificationJob) {
     insert_notification(job);
 }
@@ -256,6 +460,215 @@ pub fn insert_invite_code(invite: InviteCode) {
     });
 }
 
+pub fn try_insert_invite_code(invite: InviteCode) -> ApiResult<()> {
+    check_size(&invite, "Invite code")?;
+    insert_invite_code(invite);
+    Ok(())
+}
+
 pub fn list_all_invite_codes() -> Vec<InviteCode> {
     INVITE_CODES.with(|c| c.borrow().iter().map(|(_, v)| v).collect())
 }
+
+// ============================================================================
+// RedeemAttempts Storage
+// ============================================================================
+
+pub fn get_redeem_attempts(principal: &Principal) -> Option<RedeemAttempts> {
+    REDEEM_ATTEMPTS.with(|r| r.borrow().get(&principal_to_blob(principal)))
+}
+
+pub fn insert_redeem_attempts(principal: &Principal, attempts: RedeemAttempts) {
+    REDEEM_ATTEMPTS.with(|r| {
+        r.borrow_mut().insert(principal_to_blob(principal), attempts);
+    });
+}
+
+pub fn delete_redeem_attempts(principal: &Principal) {
+    REDEEM_ATTEMPTS.with(|r| {
+        r.borrow_mut().remove(&principal_to_blob(principal));
+    });
+}
+
+// ============================================================================
+// Rsvp Storage
+// ============================================================================
+
+pub fn get_rsvps(instance_id: &[u8; 16]) -> RsvpList {
+    RSVPS.with(|r| r.borrow().get(&Uuid::new(*instance_id))).unwrap_or_default()
+}
+
+pub fn rsvp_count(instance_id: &[u8; 16]) -> u32 {
+    get_rsvps(instance_id).attendees.len() as u32
+}
+
+/// Store an instance's RSVPs, removing the entry once it's empty
+pub fn try_insert_rsvps(instance_id: &[u8; 16], rsvps: RsvpList) -> ApiResult<()> {
+    if rsvps.attendees.is_empty() {
+        delete_rsvps(instance_id);
+        return Ok(());
+    }
+    check_size(&rsvps, "RSVP list")?;
+    RSVPS.with(|r| {
+        r.borrow_mut().insert(Uuid::new(*instance_id), rsvps);
+    });
+    Ok(())
+}
+
+pub fn delete_rsvps(instance_id: &[u8; 16]) -> bool {
+    RSVPS.with(|r| r.borrow_mut().remove(&Uuid::new(*instance_id)).is_some())
+}
+
+// ============================================================================
+// Observer Storage
+// ============================================================================
+
+pub fn is_observer(principal: &Principal) -> bool {
+    OBSERVERS.with(|o| o.borrow().contains_key(&principal_to_blob(principal)))
+}
+
+/// Add an observer; returns false if they already were one
+pub fn insert_observer(principal: &Principal, added_at: u64) -> bool {
+    OBSERVERS.with(|o| {
+        let mut map = o.borrow_mut();
+        let key = principal_to_blob(principal);
+        if map.contains_key(&key) {
+            return false;
+        }
+        map.insert(key, added_at);
+        true
+    })
+}
+
+pub fn delete_observer(principal: &Principal) -> bool {
+    OBSERVERS.with(|o| o.borrow_mut().remove(&principal_to_blob(principal)).is_some())
+}
+
+pub fn list_observers() -> Vec<Principal> {
+    OBSERVERS.with(|o| o.borrow().iter().map(|(k, _)| Principal::from_slice(k.as_slice())).collect())
+}
+
+// ============================================================================
+// HostNote Storage
+// ============================================================================
+
+pub fn get_host_note(instance_id: &[u8; 16], host: &Principal) -> Option<HostNote> {
+    HOST_NOTES.with(|h| h.borrow().get(&(Uuid::new(*instance_id), principal_to_blob(host))))
+}
+
+pub fn try_insert_host_note(instance_id: &[u8; 16], host: &Principal, note: HostNote) -> ApiResult<()> {
+    check_size(&note, "Host note")?;
+    HOST_NOTES.with(|h| {
+        h.borrow_mut().insert((Uuid::new(*instance_id), principal_to_blob(host)), note);
+    });
+    Ok(())
+}
+
+pub fn delete_host_note(instance_id: &[u8; 16], host: &Principal) -> bool {
+    HOST_NOTES.with(|h| h.borrow_mut().remove(&(Uuid::new(*instance_id), principal_to_blob(host))).is_some())
+}
+
+/// Every host note with the instance id and host it is stored under
+pub fn list_host_notes() -> Vec<([u8; 16], Principal, HostNote)> {
+    HOST_NOTES.with(|h| {
+        h.borrow()
+            .iter()
+            .map(|((id, host), note)| (*id.as_bytes(), Principal::from_slice(host.as_slice()), note))
+            .collect()
+    })
+}
+
+// ============================================================================
+// AuditLog Storage
+// ============================================================================
+
+/// Append an entry, assigning it the next sequence number
+pub fn append_audit_entry(mut entry: AuditEntry) {
+    AUDIT_LOG.with(|log| {
+        let mut log = log.borrow_mut();
+        entry.seq = log.last_key_value().map(|(k, _)| k + 1).unwrap_or(0);
+        log.insert(entry.seq, entry);
+    });
+}
+
+pub fn try_append_audit_entry(entry: AuditEntry) -> ApiResult<()> {
+    check_size(&entry, "Audit entry")?;
+    append_audit_entry(entry);
+    Ok(())
+}
+
+/// List audit entries newest first, skipping `offset` and returning at most `limit`
+pub fn list_audit_entries(offset: usize, limit: usize) -> Vec<AuditEntry> {
+    AUDIT_LOG.with(|log| {
+        log.borrow()
+            .iter()
+            .rev()
+            .skip(offset)
+            .take(limit)
+            .map(|(_, v)| v)
+            .collect()
+    })
+}
+
+// ============================================================================
+// SettingsHistory Storage
+// ============================================================================
+
+/// Append a change, dropping the oldest entries beyond `cap`
+pub fn append_settings_change(mut change: SettingsChange, cap: u64) {
+    SETTINGS_HISTORY.with(|h| {
+        let mut h = h.borrow_mut();
+        change.seq = h.last_key_value().map(|(k, _)| k + 1).unwrap_or(0);
+        h.insert(change.seq, change);
+        while h.len() > cap {
+            match h.first_key_value() {
+                Some((k, _)) => { h.remove(&k); }
+                None => break,
+            }
+        }
+    });
+}
+
+pub fn try_append_settings_change(change: SettingsChange, cap: u64) -> ApiResult<()> {
+    check_size(&change, "Settings change")?;
+    append_settings_change(change, cap);
+    Ok(())
+}
+
+/// List settings changes newest first, at most `limit`
+pub fn list_settings_changes(limit: usize) -> Vec<SettingsChange> {
+    SETTINGS_HISTORY.with(|h| {
+        h.borrow()
+            .iter()
+            .rev()
+            .take(limit)
+            .map(|(_, v)| v)
+            .collect()
+    })
+}
+
+
+// ============================================================================
+// Counts (for metrics)
+// ============================================================================
+
+pub fn series_count() -> u64 {
+    SERIES.with(|s| s.borrow().len())
+}
+
+pub fn override_count() -> u64 {
+    OVERRIDES.with(|o| o.borrow().len())
+}
+
+pub fn instance_count() -> u64 {
+    INSTANCES.with(|i| i.borrow().len())
+}
+
+pub fn invite_code_count() -> u64 {
+    INVITE_CODES.with(|c| c.borrow().len())
+}
+
+/// Stable memory in use by the canister, in bytes
+pub fn stable_memory_bytes() -> u64 {
+    ic_cdk::api::stable::stable_size() * 65536
+}
@@ -13,6 +13,23 @@ use std::borrow::Cow;
 pub enum Role {
     Admin,
     User,
+    /// Manages series and scheduling but not users
+    Coordinator,
+}
+
+/// Length of generated invite codes: Standard is "YS-XXXX-XXXX",
+/// Strong is "YS-XXXX-XXXX-XXXX-XXXX"
+#[derive(CandidType, Deserialize, Clone, Copy, Debug, PartialEq, Eq)]
+pub enum InviteCodeStrength {
+    Standard,
+    Strong,
+}
+
+/// Fine-grained permissions granted by a role (see auth::has_capability)
+#[derive(CandidType, Deserialize, Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Capability {
+    ManageUsers,
+    ManageSeries,
 }
 
 #[derive(CandidType, Deserialize, Clone, Copy, Debug, PartialEq, Eq)]
@@ -54,6 +71,15 @@ pub enum EventStatus {
     Cancelled,
 }
 
+/// Part of a series occurrence that an override changes from the series defaults
+#[derive(CandidType, Deserialize, Clone, Copy, Debug, PartialEq, Eq)]
+pub enum OverrideField {
+    Time,
+    Title,
+    Notes,
+    Host,
+}
+
 #[derive(CandidType, Deserialize, Clone, Copy, Debug, PartialEq, Eq)]
 pub enum NotificationType {
     HostAssigned,
@@ -64,6 +90,7 @@ pub enum NotificationType {
     CoverageNeededSoon,
     DailyDigest,
     WeeklyDigest,
+    UserJoined,
 }
 
 #[derive(CandidType, Deserialize, Clone, Copy, Debug, PartialEq, Eq)]
@@ -71,6 +98,27 @@ pub enum NotificationStatus {
     Pending,
     Sent,
     Failed,
+    Cancelled, // its session went away before it was sent
+}
+
+#[derive(CandidType, Deserialize, Clone, Copy, Debug, PartialEq, Eq)]
+pub enum AuditAction {
+    UserAuthorized,
+    UserUpdated,
+    UserDisabled,
+    UserEnabled,
+    UserDeleted,
+    SeriesCreated,
+    SeriesUpdated,
+    SeriesDeleted,
+    SeriesPauseToggled,
+    SeriesRestored,
+    SeriesPurged,
+    SettingsUpdated,
+    InviteCodeGenerated,
+    InviteCodeRevoked,
+    ObserverAdded,
+    ObserverRemoved,
 }
 
 // ============================================================================
@@ -81,9 +129,18 @@ pub enum NotificationStatus {
 pub struct OOOBlock {
     pub start_utc: u64,
     pub end_utc: u64,
+    pub reason: Option<String>, // admin-only context; absent in older records, which decode as None
 }
 
+/// Weekly recurring window, in minutes since midnight UTC on `weekday`
 #[derive(CandidType, Deserialize, Clone, Debug)]
+pub struct RecurringBlock {
+    pub weekday: Weekday,
+    pub start_minute_utc: u32,
+    pub end_minute_utc: u32,
+}
+
+#[derive(CandidType, Deserialize, Clone, Debug, PartialEq)]
 pub struct NotificationSettings {
     pub email_on_assigned: bool,
     pub email_on_removed: bool,
@@ -115,11 +172,14 @@ pub struct User {
     pub role: Role,
     pub status: UserStatus,
     pub out_of_office: Vec<OOOBlock>,
+    pub availability: Vec<RecurringBlock>,
     pub notification_settings: NotificationSettings,
     pub last_active: u64,
     pub sessions_hosted_count: u32,
     pub created_at: u64,
     pub updated_at: u64,
+    /// BCP 47 language tag for notification text (None = English)
+    pub locale: Option<String>,
 }
 
 /// Lightweight user info for directory listing (no sensitive fields)
@@ -129,6 +189,7 @@ pub struct UserDirectoryEntry {
     pub name: String,
     pub role: Role,
     pub status: UserStatus,
+    pub out_of_office: Option<Vec<OOOBlock>>, // only filled in for admins
 }
 
 #[derive(CandidType, Deserialize, Clone, Debug)]
@@ -136,6 +197,7 @@ pub struct EventSeries {
     pub series_id: [u8; 16],
     pub title: String,
     pub notes: String,
+    pub notes_template: Option<String>, // pre-fills notes when an occurrence is first edited or resized
     pub link: Option<String>,
     pub frequency: Frequency,
     pub weekday: Weekday,
@@ -145,7 +207,12 @@ pub struct EventSeries {
     pub default_duration_minutes: u32,
     pub color: Option<String>,
     pub paused: bool,
+    pub claims_paused: bool, // still shown, but only admins can assign/unassign hosts
+    pub deleted: bool, // soft-deleted: hidden everywhere until restored or purged
+    pub public: bool, // shown on the public calendar; internal-only when false
+    pub capacity: Option<u32>, // attendee limit per occurrence; None = unlimited
     pub default_host: Option<Principal>,
+    pub reminder_hours_before_override: Option<u32>, // takes precedence over hosts' own reminder_hours_before
     pub created_at: u64,
     pub created_by: Principal,
 }
@@ -163,6 +230,15 @@ pub struct EventInstance {
     pub status: EventStatus,
     pub color: Option<String>,
     pub created_at: u64,
+    pub occurrence_start: Option<u64>, // series occurrences only: the override key, unaffected by time overrides
+    pub assigned_by: Option<Principal>, // who set the current host, if known
+    pub assigned_at: Option<u64>,
+    pub created_by: Option<Principal>, // series creator for occurrences; unknown for older one-offs
+    pub public: bool, // shown on the public calendar; occurrences inherit their series' flag
+    pub capacity: Option<u32>, // attendee limit; occurrences inherit their series' capacity
+    pub rsvp_count: u32, // filled in when read; not meaningful in storage
+    pub overridden_fields: Vec<OverrideField>, // series occurrences only; empty when unmodified
+    pub series_title: Option<String>, // the parent series' title, even when this occurrence's title is overridden
 }
 
 
@@ -180,13 +256,50 @@ pub struct InstanceOverride {
     pub start_utc: Option<u64>,
     pub end_utc: Option<u64>,
     pub notes: Option<String>,
+    pub title_override: Option<String>,
     pub host_principal: Option<Principal>,
     pub host_cleared: bool,
+    pub assigned_by: Option<Principal>, // who set host_principal
+    pub assigned_at: Option<u64>,
     pub cancelled: bool,
     pub updated_at: u64,
     pub updated_by: Principal,
 }
 
+impl InstanceOverride {
+    /// An override that changes nothing yet
+    pub fn new(series_id: [u8; 16], occurrence_start_utc: u64, by: Principal, now: u64) -> Self {
+        Self {
+            series_id,
+            occurrence_start_utc,
+            start_utc: None,
+            end_utc: None,
+            notes: None,
+            title_override: None,
+            host_principal: None,
+            host_cleared: false,
+            assigned_by: None,
+            assigned_at: None,
+            cancelled: false,
+            updated_at: now,
+            updated_by: by,
+        }
+    }
+}
+
+/// Custom subject and/or body for one per-session notification type (see
+/// `notifications::TEMPLATED_TYPES`). `{title}`, `{start}` (ISO 8601, UTC) and
+/// `{host}` are substituted; unset parts keep the built-in text. `{host}` is
+/// the name of the user being notified, who for coverage-needed notices is a
+/// prospective host rather than an assigned one. A CoverageNeededSoon template
+/// shapes the per-session notices only, not the weekly admin coverage alert.
+#[derive(CandidType, Deserialize, Clone, Debug, PartialEq)]
+pub struct NotificationTemplate {
+    pub notification_type: NotificationType,
+    pub subject: Option<String>,
+    pub body: Option<String>,
+}
+
 #[derive(CandidType, Deserialize, Clone, Debug)]
 pub struct GlobalSettings {
     pub forward_window_months: u8,
@@ -195,6 +308,48 @@ pub struct GlobalSettings {
     pub org_name: Option<String>,
     pub org_tagline: Option<String>,
     pub org_logo_url: Option<String>,
+    /// Soft cap on simultaneous sessions (None = unlimited)
+    pub max_concurrent_sessions: Option<u32>,
+    /// Reject sessions over the cap instead of only warning
+    pub enforce_concurrency_cap: bool,
+    /// Require a one-off's host to be an active user free for the event window
+    pub validate_one_off_host: bool,
+    /// Require every new session and series to have a meeting link
+    pub require_link: bool,
+    /// Periodically cancel series occurrences that ended without a host
+    pub auto_cancel_past_unclaimed: bool,
+    /// Hours after an occurrence ends before it may be auto-cancelled
+    pub auto_cancel_grace_hours: u32,
+    /// Allowed session start times as (open, close) minutes of the UTC day;
+    /// close < open wraps past midnight
+    pub business_hours: Option<(u32, u32)>,
+    /// Shortest allowed session, in minutes
+    pub min_duration_minutes: Option<u32>,
+    /// Longest allowed session, in minutes
+    pub max_duration_minutes: Option<u32>,
+    /// Mix this canister's id into series instance ids so they (and ICS UIDs)
+    /// differ between canisters. Off for canisters created before it existed,
+    /// since turning it on changes every series instance id.
+    pub namespaced_instance_ids: bool,
+    /// Notify admins when someone joins by redeeming an invite code
+    pub notify_admins_on_join: bool,
+    /// Notification settings given to newly created users (None = built-in defaults)
+    pub org_default_notification_settings: Option<NotificationSettings>,
+    /// First day of the week for weekly buckets (Monday = ISO weeks)
+    pub week_starts_on: Weekday,
+    /// Bumped on every settings write; updates must name the version they edited
+    pub version: u64,
+    /// Alert admins when next-week coverage falls below this percentage
+    pub coverage_alert_threshold_pct: Option<f64>,
+    /// Minimum minutes before a session starts that non-admins may claim it
+    pub min_claim_lead_minutes: Option<u32>,
+    /// Email hosts about cancellations and removals without a replacement even
+    /// if they opted out; routine reassignments still honor their settings
+    pub always_email_critical_removals: bool,
+    /// IANA timezone the public calendar displays in (None = viewer's own)
+    pub org_timezone: Option<String>,
+    /// Org overrides for per-session notification text, at most one per type
+    pub notification_templates: Vec<NotificationTemplate>,
 }
 
 impl Default for GlobalSettings {
@@ -206,6 +361,25 @@ impl Default for GlobalSettings {
             org_name: None,
             org_tagline: None,
             org_logo_url: None,
+            max_concurrent_sessions: None,
+            enforce_concurrency_cap: false,
+            validate_one_off_host: true,
+            require_link: false,
+            auto_cancel_past_unclaimed: false,
+            auto_cancel_grace_hours: 24,
+            business_hours: None,
+            min_duration_minutes: None,
+            max_duration_minutes: None,
+            namespaced_instance_ids: true,
+            notify_admins_on_join: true,
+            org_default_notification_settings: None,
+            week_starts_on: Weekday::Mon,
+            version: 0,
+            coverage_alert_threshold_pct: None,
+            min_claim_lead_minutes: None,
+            always_email_critical_removals: true,
+            org_timezone: None,
+            notification_templates: vec![],
         }
     }
 }
@@ -224,12 +398,101 @@ pub struct NotificationJob {
     pub status: NotificationStatus,
     pub sent_at: Option<u64>,
     pub error_message: Option<String>,
+    /// Last time an admin flagged this job as stale while still pending
+    pub requeued_at: Option<u64>,
+    /// Event this job is about, so workers can group and de-dupe by event
+    pub instance_id: Option<[u8; 16]>,
+    pub series_id: Option<[u8; 16]>,
+}
+
+/// One page of materialized events, in start order
+#[derive(CandidType, Deserialize, Clone, Debug)]
+pub struct EventsPage {
+    pub events: Vec<EventInstance>,
+    pub next_cursor: Option<u64>, // pass as `after_start_utc` for the next page; None = no more events
+}
+
+/// Number of notification jobs in each status
+#[derive(CandidType, Deserialize, Clone, Debug, Default)]
+pub struct NotificationCounts {
+    pub pending: u64,
+    pub sent: u64,
+    pub failed: u64,
+    pub cancelled: u64,
+}
+
+/// A single admin action recorded for compliance/auditing
+#[derive(CandidType, Deserialize, Clone, Debug)]
+pub struct AuditEntry {
+    pub seq: u64,
+    pub actor: Principal,
+    pub action: AuditAction,
+    pub target: String,
+    pub timestamp: u64,
+    pub details: String,
+}
+
+/// One change to the global settings, with a human-readable diff
+#[derive(CandidType, Deserialize, Clone, Debug)]
+pub struct SettingsChange {
+    pub seq: u64,
+    pub actor: Principal,
+    pub timestamp: u64,
+    pub summary: String,
 }
 
 // ============================================================================
 // API Input/Output Types
 // ============================================================================
 
+#[derive(CandidType, Deserialize, Clone, Debug)]
+pub struct AuthorizeUserInput {
+    pub principal: Principal,
+    pub name: String,
+    pub email: String,
+    pub role: Role,
+}
+
+#[derive(CandidType, Deserialize, Clone, Debug)]
+pub struct SkippedUser {
+    pub principal: Principal,
+    pub reason: String,
+}
+
+#[derive(CandidType, Deserialize, Clone, Debug)]
+pub struct BulkAuthorizeResult {
+    pub created: Vec<User>,
+    pub skipped: Vec<SkippedUser>,
+}
+
+#[derive(CandidType, Deserialize, Clone, Debug)]
+pub struct FailedEventInput {
+    pub index: u32,
+    pub error: ApiError,
+}
+
+#[derive(CandidType, Deserialize, Clone, Debug)]
+pub struct BulkCreateEventsResult {
+    pub created: Vec<EventInstance>,
+    pub failed: Vec<FailedEventInput>,
+    pub warnings: Vec<String>, // prefixed with the input index they concern
+}
+
+/// A newly created one-off with any non-blocking warnings (e.g. an
+/// unenforced concurrency cap being exceeded)
+#[derive(CandidType, Deserialize, Clone, Debug)]
+pub struct CreatedEvent {
+    pub event: EventInstance,
+    pub warnings: Vec<String>,
+}
+
+/// A newly created series with any non-blocking warnings
+#[derive(CandidType, Deserialize, Clone, Debug)]
+pub struct CreatedSeries {
+    pub series: EventSeries,
+    pub warnings: Vec<String>,
+}
+
 #[derive(CandidType, Deserialize, Clone, Debug)]
 pub struct CreateEventInput {
     pub title: String,
@@ -238,6 +501,8 @@ pub struct CreateEventInput {
     pub start_utc: u64,
     pub end_utc: u64,
     pub host_principal: Option<Principal>,
+    pub public: Option<bool>, // defaults to true
+    pub capacity: Option<u32>,
 }
 
 #[derive(CandidType, Deserialize, Clone, Debug)]
@@ -253,9 +518,22 @@ pub struct CreateSeriesInput {
     pub default_duration_minutes: Option<u32>,
     pub color: Option<String>,
     pub default_host: Option<Principal>,
+    pub reminder_hours_before_override: Option<u32>,
+    pub public: Option<bool>, // defaults to true
+    pub capacity: Option<u32>,
+    pub notes_template: Option<String>,
 }
 
 
+/// Recurrence for turning a one-off into a series; everything else is
+/// taken from the one-off
+#[derive(CandidType, Deserialize, Clone, Debug)]
+pub struct ConvertToSeriesInput {
+    pub frequency: Frequency,
+    pub weekday_ordinal: Option<WeekdayOrdinal>,
+    pub end_date: Option<u64>,
+}
+
 #[derive(CandidType, Deserialize, Clone, Debug)]
 pub struct UpdateSeriesInput {
     pub title: Option<String>,
@@ -264,7 +542,41 @@ pub struct UpdateSeriesInput {
     pub default_duration_minutes: Option<u32>,
     pub color: Option<Option<String>>,  // None = don't change, Some(None) = clear, Some(Some(x)) = set to x
     pub paused: Option<bool>,
+    pub claims_paused: Option<bool>,
     pub default_host: Option<Option<Principal>>,  // None = don't change, Some(None) = clear, Some(Some(p)) = set
+    pub reminder_hours_before_override: Option<Option<u32>>,  // None = don't change, Some(None) = clear
+    pub public: Option<bool>,
+    pub capacity: Option<Option<u32>>,  // None = don't change, Some(None) = unlimited
+    pub notes_template: Option<Option<String>>,  // None = don't change, Some(None) = clear
+}
+
+/// Partial settings update; only provided fields are changed
+#[derive(CandidType, Deserialize, Clone, Debug)]
+pub struct GlobalSettingsPatch {
+    pub forward_window_months: Option<u8>,
+    pub claims_paused: Option<bool>,
+    pub default_event_duration_minutes: Option<u32>,
+    pub org_name: Option<Option<String>>,  // None = don't change, Some(None) = clear
+    pub org_tagline: Option<Option<String>>,  // None = don't change, Some(None) = clear
+    pub org_logo_url: Option<Option<String>>,  // None = don't change, Some(None) = clear
+    pub max_concurrent_sessions: Option<Option<u32>>,  // None = don't change, Some(None) = clear
+    pub enforce_concurrency_cap: Option<bool>,
+    pub validate_one_off_host: Option<bool>,
+    pub require_link: Option<bool>,
+    pub auto_cancel_past_unclaimed: Option<bool>,
+    pub auto_cancel_grace_hours: Option<u32>,
+    pub business_hours: Option<Option<(u32, u32)>>,  // None = don't change, Some(None) = clear
+    pub min_duration_minutes: Option<Option<u32>>,  // None = don't change, Some(None) = clear
+    pub max_duration_minutes: Option<Option<u32>>,  // None = don't change, Some(None) = clear
+    pub namespaced_instance_ids: Option<bool>,
+    pub notify_admins_on_join: Option<bool>,
+    pub org_default_notification_settings: Option<Option<NotificationSettings>>,  // None = don't change, Some(None) = clear
+    pub week_starts_on: Option<Weekday>,
+    pub coverage_alert_threshold_pct: Option<Option<f64>>,  // None = don't change, Some(None) = clear
+    pub min_claim_lead_minutes: Option<Option<u32>>,
+    pub always_email_critical_removals: Option<bool>,
+    pub org_timezone: Option<Option<String>>,
+    pub notification_templates: Option<Vec<NotificationTemplate>>,
 }
 
 #[derive(CandidType, Deserialize, Clone, Debug)]
@@ -272,6 +584,10 @@ pub struct UpdateInstanceInput {
     pub start_utc: Option<u64>,
     pub end_utc: Option<u64>,
     pub notes: Option<String>,
+    pub title: Option<String>,
+    pub link: Option<Option<String>>,  // None = don't change, Some(None) = clear
+    pub public: Option<bool>,
+    pub capacity: Option<Option<u32>>,  // None = don't change, Some(None) = unlimited
 }
 
 #[derive(CandidType, Deserialize, Clone, Debug)]
@@ -283,20 +599,214 @@ pub struct CoverageStats {
     pub coverage_pct: f64,
 }
 
+/// Unclaimed sessions coming up within a horizon
+#[derive(CandidType, Deserialize, Clone, Debug)]
+pub struct CoverageGaps {
+    pub count: u32,
+    pub events: Vec<EventInstance>,
+}
+
+/// Outcome of assigning one host across a series
+#[derive(CandidType, Deserialize, Clone, Debug)]
+pub struct SeriesAssignmentResult {
+    pub assigned: Vec<EventInstance>,
+    pub skipped: Vec<EventInstance>, // host is disabled or out of office
+}
+
+/// Outcome of pausing or resuming a series
+#[derive(CandidType, Deserialize, Clone, Debug)]
+pub struct SeriesPauseResult {
+    pub series: EventSeries,
+    pub affected_occurrences: u32, // upcoming occurrences (within the forward window) hidden or restored
+    pub hosted_occurrences: u32,   // how many of those have a host
+}
+
+/// A stored override alongside the occurrence it produces
+#[derive(CandidType, Deserialize, Clone, Debug)]
+pub struct SeriesOverrideView {
+    pub entry: InstanceOverride,
+    pub materialized: Option<EventInstance>, // None when the occurrence is cancelled or the series is deleted
+}
+
+/// A possible host for a specific session
+#[derive(CandidType, Deserialize, Clone, Debug)]
+pub struct EligibleHost {
+    pub principal: Principal,
+    pub name: String,
+    pub role: Role,
+    pub requires_override: bool, // disabled or out of office; only an admin can assign them
+}
+
+/// Neighboring occurrence starts of a series around a given occurrence
+#[derive(CandidType, Deserialize, Clone, Debug)]
+pub struct AdjacentOccurrences {
+    pub previous: Option<u64>,
+    pub next: Option<u64>,
+}
+
+/// Caller identity and authorization status, resolved in one call
+#[derive(CandidType, Deserialize, Clone, Debug)]
+pub enum WhoamiStatus {
+    Anonymous,
+    Unauthorized(Principal), // authenticated but not whitelisted
+    Authorized(User),
+    Disabled(Principal),
+}
+
+/// What a user can currently do, for admin support diagnostics
+#[derive(CandidType, Deserialize, Clone, Debug)]
+pub struct UserAccess {
+    pub principal: Principal,
+    pub role: Role,
+    pub status: UserStatus,
+    pub capabilities: Vec<Capability>,
+    pub current_out_of_office: Option<OOOBlock>, // block covering now, if any
+    pub can_claim_now: bool,                      // active, and claims not globally paused (admins bypass)
+}
+
+/// Whether the caller may claim a session, and why not if they can't
+#[derive(CandidType, Deserialize, Clone, Debug)]
+pub struct ClaimEligibility {
+    pub allowed: bool,
+    pub reason: Option<String>,
+}
+
+/// Outcome of moving one host's sessions to another
+#[derive(CandidType, Deserialize, Clone, Debug)]
+pub struct HostTransferResult {
+    pub transferred: Vec<EventInstance>,
+    pub skipped: Vec<EventInstance>, // new host is unavailable; left with the original host
+    pub series_reassigned: u32, // series whose default host was moved
+}
+
+/// Two sessions with the same host that overlap; `first` starts no later than `second`
+#[derive(CandidType, Deserialize, Clone, Debug)]
+pub struct SessionConflict {
+    pub first: EventInstance,
+    pub second: EventInstance,
+}
+
+/// Aggregate counts for monitoring; contains no per-record data
+#[derive(CandidType, Deserialize, Clone, Debug)]
+pub struct CanisterMetrics {
+    pub users_active: u32,
+    pub users_disabled: u32,
+    pub admins: u32,
+    pub coordinators: u32,
+    pub series: u64,
+    pub overrides: u64,
+    pub one_off_instances: u64,
+    pub notifications_pending: u32,
+    pub notifications_failed: u32,
+    pub invite_codes: u64,
+    pub stable_memory_bytes: u64,
+}
+
+/// Counts of leftover or stale records, with suggested cleanup actions
+#[derive(CandidType, Deserialize, Clone, Debug)]
+pub struct MaintenanceReport {
+    pub orphaned_overrides: u32,
+    pub expired_invite_codes: u32,
+    pub failed_notifications: u32,
+    pub archivable_instances: u32,
+    pub suggested_actions: Vec<String>,
+}
+
+/// A session flagged by data-quality checks, with the reason it was flagged
+#[derive(CandidType, Deserialize, Clone, Debug)]
+pub struct AnomalousSession {
+    pub event: EventInstance,
+    pub reason: String,
+}
+
+/// Sessions hosted by one host within a window
+/// `host` is None for the unassigned pseudo-entry.
+#[derive(CandidType, Deserialize, Clone, Debug)]
+pub struct HostStats {
+    pub host: Option<Principal>,
+    pub name: String,
+    pub session_count: u32,
+    pub total_hours: f64,
+}
+
+/// How often one host has hosted a series
+/// `series_id` is None for the one-off sessions pseudo-entry.
+#[derive(CandidType, Deserialize, Clone, Debug)]
+pub struct HostSeriesCount {
+    pub series_id: Option<[u8; 16]>,
+    pub title: String,
+    pub count: u32,
+}
+
+/// How far ahead of start a series' occurrences were claimed
+#[derive(CandidType, Deserialize, Clone, Debug)]
+pub struct ClaimLeadTimes {
+    pub claimed_count: u32,
+    pub average_lead_seconds: Option<u64>, // None when nothing was claimed
+}
+
+#[derive(CandidType, Deserialize, Clone, Debug)]
+pub struct DayCount {
+    pub date: String, // YYYY-MM-DD local to the org timezone (or requested offset)
+    pub weekday: Weekday,
+    pub session_count: u32,
+}
+
+#[derive(CandidType, Deserialize, Clone, Debug)]
+pub struct DayUtilization {
+    pub days: Vec<DayCount>,
+    pub busiest: Option<DayCount>,
+    pub quietest: Option<DayCount>,
+}
+
+/// Canister build and data schema versions
+#[derive(CandidType, Deserialize, Clone, Debug)]
+pub struct VersionInfo {
+    pub schema_version: u32,
+    pub package_version: String,
+    pub build: Option<String>, // OHS_BUILD at compile time, e.g. a commit or wasm hash
+}
+
 /// For API responses, a simplified event view
 #[derive(CandidType, Deserialize, Clone, Debug)]
 pub struct PublicEventView {
-    pub instance_id: Vec<u8>,
+    pub instance_id: IdBytes,
     pub title: String,
     pub notes: String,
     pub link: Option<String>,
     pub start_utc: u64,
     pub end_utc: u64,
     pub host_name: Option<String>,
+    pub is_assigned: bool, // true even if the host's name can't be shown
+    pub rsvp_count: u32,
     pub status: EventStatus,
     pub color: Option<String>,
 }
 
+/// A 16-byte id (series, instance or job) as passed over the API
+/// Travels as a plain blob, so callers sending raw bytes keep working; blobs
+/// of the wrong length are rejected while decoding the call.
+#[derive(CandidType, Deserialize, Clone, Copy, Debug, PartialEq, Eq)]
+#[serde(try_from = "Vec<u8>")]
+pub struct IdBytes(pub [u8; 16]);
+
+impl TryFrom<Vec<u8>> for IdBytes {
+    type Error = String;
+    
+    fn try_from(bytes: Vec<u8>) -> Result<Self, Self::Error> {
+        let len = bytes.len();
+        bytes.try_into()
+            .map(IdBytes)
+            .map_err(|_| format!("Invalid id: expected 16 bytes, got {}", len))
+    }
+}
+
+impl From<[u8; 16]> for IdBytes {
+    fn from(id: [u8; 16]) -> Self {
+        IdBytes(id)
+    }
+}
+
 #[derive(CandidType, Deserialize, Clone, Debug)]
 pub enum ApiError {
     Unauthorized,
@@ -320,25 +830,97 @@ pub struct InviteCode {
     pub created_at: u64,
     pub created_by: Principal,
     pub expires_at: u64,
+    /// True once the code is exhausted (use_count reached max_uses)
     pub redeemed: bool,
+    /// Most recent redeemer
     pub redeemed_by: Option<Principal>,
     pub redeemed_at: Option<u64>,
     pub user_placeholder_principal: Option<Principal>,
+    pub max_uses: u32,
+    pub use_count: u32,
+    /// Every principal that has redeemed this code (prevents double redemption)
+    pub redeemers: Vec<Principal>,
+    pub revoked: bool,
 }
 
-const MAX_INVITE_CODE_SIZE: u32 = 512;
+const MAX_INVITE_CODE_SIZE: u32 = 4096;
 
 impl Storable for InviteCode {
-    fn to_bytes(&self) -> Cow<[u8]> {
+    fn to_bytes(&self) -> Cow<'_, [u8]> {
         Cow::Owned(Encode!(self).unwrap())
     }
 
-    fn from_bytes(bytes: Cow<[u8]>) -> Self {
-        // Try current format first (has role + user_placeholder_principal)
+    fn from_bytes(bytes: Cow<'_, [u8]>) -> Self {
+        // Try current format first (has revoked)
         if let Ok(code) = Decode!(bytes.as_ref(), Self) {
             return code;
         }
         
+        // Migration: multi-use codes (max_uses + use_count + redeemers) without revoked
+        #[derive(CandidType, Deserialize)]
+        struct MultiUseInviteCode {
+            code: String,
+            role: Role,
+            created_at: u64,
+            created_by: Principal,
+            expires_at: u64,
+            redeemed: bool,
+            redeemed_by: Option<Principal>,
+            redeemed_at: Option<u64>,
+            user_placeholder_principal: Option<Principal>,
+            max_uses: u32,
+            use_count: u32,
+            redeemers: Vec<Principal>,
+        }
+        if let Ok(multi) = Decode!(bytes.as_ref(), MultiUseInviteCode) {
+            return InviteCode {
+                code: multi.code,
+                role: multi.role,
+                created_at: multi.created_at,
+                created_by: multi.created_by,
+                expires_at: multi.expires_at,
+                redeemed: multi.redeemed,
+                redeemed_by: multi.redeemed_by,
+                redeemed_at: multi.redeemed_at,
+                user_placeholder_principal: multi.user_placeholder_principal,
+                max_uses: multi.max_uses,
+                use_count: multi.use_count,
+                redeemers: multi.redeemers,
+                revoked: false,
+            };
+        }
+        
+        // Migration: single-use codes with role + user_placeholder_principal
+        #[derive(CandidType, Deserialize)]
+        struct SingleUseInviteCode {
+            code: String,
+            role: Role,
+            created_at: u64,
+            created_by: Principal,
+            expires_at: u64,
+            redeemed: bool,
+            redeemed_by: Option<Principal>,
+            redeemed_at: Option<u64>,
+            user_placeholder_principal: Option<Principal>,
+        }
+        if let Ok(single) = Decode!(bytes.as_ref(), SingleUseInviteCode) {
+            return InviteCode {
+                code: single.code,
+                role: single.role,
+                created_at: single.created_at,
+                created_by: single.created_by,
+                expires_at: single.expires_at,
+                redeemed: single.redeemed,
+                redeemed_by: single.redeemed_by,
+                redeemed_at: single.redeemed_at,
+                user_placeholder_principal: single.user_placeholder_principal,
+                max_uses: 1,
+                use_count: if single.redeemed { 1 } else { 0 },
+                redeemers: single.redeemed_by.into_iter().collect(),
+                revoked: false,
+            };
+        }
+        
         // Migration: mid-version had role but no user_placeholder_principal
         #[derive(CandidType, Deserialize)]
         struct MidInviteCode {
@@ -362,6 +944,10 @@ impl Storable for InviteCode {
                 redeemed_by: mid.redeemed_by,
                 redeemed_at: mid.redeemed_at,
                 user_placeholder_principal: None,
+                max_uses: 1,
+                use_count: if mid.redeemed { 1 } else { 0 },
+                redeemers: mid.redeemed_by.into_iter().collect(),
+                revoked: false,
             };
         }
 
@@ -388,6 +974,10 @@ impl Storable for InviteCode {
             redeemed_by: old.redeemed_by,
             redeemed_at: old.redeemed_at,
             user_placeholder_principal: Some(old.user_placeholder_principal),
+            max_uses: 1,
+            use_count: if old.redeemed { 1 } else { 0 },
+            redeemers: old.redeemed_by.into_iter().collect(),
+            revoked: false,
         }
     }
 
@@ -397,21 +987,86 @@ impl Storable for InviteCode {
     };
 }
 
-/// Stable-storage key wrapper for invite codes (max 15 chars like "YS-XXXX-XXXX")
+/// Attendees who have RSVPed to one session, in RSVP order
+#[derive(CandidType, Deserialize, Clone, Debug, Default)]
+pub struct RsvpList {
+    pub attendees: Vec<Principal>,
+}
+
+impl Storable for RsvpList {
+    fn to_bytes(&self) -> Cow<'_, [u8]> {
+        Cow::Owned(Encode!(self).unwrap())
+    }
+
+    fn from_bytes(bytes: Cow<'_, [u8]>) -> Self {
+        Decode!(bytes.as_ref(), Self).unwrap()
+    }
+
+    const BOUND: Bound = Bound::Bounded {
+        max_size: MAX_RSVP_LIST_SIZE,
+        is_fixed_size: false,
+    };
+}
+
+/// A host's private prep note for one session
+#[derive(CandidType, Deserialize, Clone, Debug)]
+pub struct HostNote {
+    pub note: String,
+    pub updated_at: u64,
+}
+
+impl Storable for HostNote {
+    fn to_bytes(&self) -> Cow<'_, [u8]> {
+        Cow::Owned(Encode!(self).unwrap())
+    }
+
+    fn from_bytes(bytes: Cow<'_, [u8]>) -> Self {
+        Decode!(bytes.as_ref(), Self).unwrap()
+    }
+
+    const BOUND: Bound = Bound::Bounded {
+        max_size: MAX_HOST_NOTE_SIZE,
+        is_fixed_size: false,
+    };
+}
+
+/// Failed invite redemption attempts for one principal within the current window
+#[derive(CandidType, Deserialize, Clone, Debug)]
+pub struct RedeemAttempts {
+    pub failed_count: u32,
+    pub window_start: u64,
+}
+
+impl Storable for RedeemAttempts {
+    fn to_bytes(&self) -> Cow<'_, [u8]> {
+        Cow::Owned(Encode!(self).unwrap())
+    }
+
+    fn from_bytes(bytes: Cow<'_, [u8]>) -> Self {
+        Decode!(bytes.as_ref(), Self).unwrap()
+    }
+
+    const BOUND: Bound = Bound::Bounded {
+        max_size: 64,
+        is_fixed_size: false,
+    };
+}
+
+/// Stable-storage key wrapper for invite codes (up to 22 chars like "YS-XXXX-XXXX-XXXX-XXXX")
 #[derive(Clone, Debug, PartialEq, Eq, PartialOrd, Ord)]
 pub struct InviteCodeKey(pub String);
 
 impl Storable for InviteCodeKey {
-    fn to_bytes(&self) -> Cow<[u8]> {
+    fn to_bytes(&self) -> Cow<'_, [u8]> {
         Cow::Owned(self.0.as_bytes().to_vec())
     }
 
-    fn from_bytes(bytes: Cow<[u8]>) -> Self {
+    fn from_bytes(bytes: Cow<'_, [u8]>) -> Self {
         Self(String::from_utf8(bytes.to_vec()).unwrap())
     }
 
     const BOUND: Bound = Bound::Bounded {
-        max_size: 20,
+        max_size: 32,
         is_fixed_size: false,
     };
 }
@@ -421,24 +1076,95 @@ impl Storable for InviteCodeKey {
 // Storable Implementations for ic-stable-structures
 // ============================================================================
 
-const MAX_USER_SIZE: u32 = 2048;
-const MAX_SERIES_SIZE: u32 = 1024;
-const MAX_INSTANCE_SIZE: u32 = 1024;
-const MAX_OVERRIDE_SIZE: u32 = 512;
+// Room for the longest name, email and locale plus MAX_OOO_BLOCKS reasons and
+// MAX_AVAILABILITY_BLOCKS windows (4 bytes per char) and encoding overhead
+const MAX_USER_SIZE: u32 = 24 * 1024;
+// Sized for the longest title/notes/link accepted by validation (4 bytes per char) plus encoding overhead
+const MAX_SERIES_SIZE: u32 = 12 * 1024;
+const MAX_INSTANCE_SIZE: u32 = 12 * 1024;
+const MAX_OVERRIDE_SIZE: u32 = 12 * 1024;
 const MAX_NOTIFICATION_SIZE: u32 = 4096;
-const MAX_SETTINGS_SIZE: u32 = 512;
+const MAX_SETTINGS_SIZE: u32 = 32 * 1024; // room for notification templates
+const MAX_AUDIT_ENTRY_SIZE: u32 = 1024;
+const MAX_SETTINGS_CHANGE_SIZE: u32 = 2048;
+const MAX_RSVP_LIST_SIZE: u32 = 32 * 1024;
+const MAX_HOST_NOTE_SIZE: u32 = 10 * 1024;
 
 impl Storable for User {
-    fn to_bytes(&self) -> Cow<[u8]> {
+    fn to_bytes(&self) -> Cow<'_, [u8]> {
         Cow::Owned(Encode!(self).unwrap())
     }
 
-    fn from_bytes(bytes: Cow<[u8]>) -> Self {
+    fn from_bytes(bytes: Cow<'_, [u8]>) -> Self {
         match Decode!(bytes.as_ref(), Self) {
             Ok(u) => u,
             Err(_) => {
-                // Try decoding as old User format (without last_active, sessions_hosted_count)
-                // by decoding into a partial struct and filling defaults
+                // V3: before locale
+                #[derive(CandidType, Deserialize)]
+                struct V3User {
+                    principal: Principal,
+                    name: String,
+                    email: String,
+                    role: Role,
+                    status: UserStatus,
+                    out_of_office: Vec<OOOBlock>,
+                    availability: Vec<RecurringBlock>,
+                    notification_settings: NotificationSettings,
+                    last_active: u64,
+                    sessions_hosted_count: u32,
+                    created_at: u64,
+                    updated_at: u64,
+                }
+                if let Ok(v3) = Decode!(bytes.as_ref(), V3User) {
+                    return User {
+                        principal: v3.principal,
+                        name: v3.name,
+                        email: v3.email,
+                        role: v3.role,
+                        status: v3.status,
+                        out_of_office: v3.out_of_office,
+                        availability: v3.availability,
+                        notification_settings: v3.notification_settings,
+                        last_active: v3.last_active,
+                        sessions_hosted_count: v3.sessions_hosted_count,
+                        created_at: v3.created_at,
+                        updated_at: v3.updated_at,
+                        locale: None,
+                    };
+                }
+                // V2: has last_active + sessions_hosted_count but no availability
+                #[derive(CandidType, Deserialize)]
+                struct V2User {
+                    principal: Principal,
+                    name: String,
+                    email: String,
+                    role: Role,
+                    status: UserStatus,
+                    out_of_office: Vec<OOOBlock>,
+                    notification_settings: NotificationSettings,
+                    last_active: u64,
+                    sessions_hosted_count: u32,
+                    created_at: u64,
+                    updated_at: u64,
+                }
+                if let Ok(v2) = Decode!(bytes.as_ref(), V2User) {
+                    return User {
+                        principal: v2.principal,
+                        name: v2.name,
+                        email: v2.email,
+                        role: v2.role,
+                        status: v2.status,
+                        out_of_office: v2.out_of_office,
+                        availability: vec![],
+                        notification_settings: v2.notification_settings,
+                        last_active: v2.last_active,
+                        sessions_hosted_count: v2.sessions_hosted_count,
+                        created_at: v2.created_at,
+                        updated_at: v2.updated_at,
+                        locale: None,
+                    };
+                }
+                // V1: without last_active, sessions_hosted_count
                 #[derive(CandidType, Deserialize)]
                 struct OldUser {
                     principal: Principal,
@@ -459,11 +1185,13 @@ impl Storable for User {
                     role: old.role,
                     status: old.status,
                     out_of_office: old.out_of_office,
+                    availability: vec![],
                     notification_settings: old.notification_settings,
                     last_active: 0,
                     sessions_hosted_count: 0,
                     created_at: old.created_at,
                     updated_at: old.updated_at,
+                    locale: None,
                 }
             }
         }
@@ -476,17 +1204,17 @@ impl Storable for User {
 }
 
 impl Storable for EventSeries {
-    fn to_bytes(&self) -> Cow<[u8]> {
+    fn to_bytes(&self) -> Cow<'_, [u8]> {
         Cow::Owned(Encode!(self).unwrap())
     }
 
-    fn from_bytes(bytes: Cow<[u8]>) -> Self {
+    fn from_bytes(bytes: Cow<'_, [u8]>) -> Self {
         match Decode!(bytes.as_ref(), Self) {
             Ok(s) => s,
             Err(_) => {
-                // V3: has color+paused but no default_host
+                // V9: has capacity but no notes template
                 #[derive(CandidType, Deserialize)]
-                struct V3EventSeries {
+                struct V9EventSeries {
                     series_id: [u8; 16],
                     title: String,
                     notes: String,
@@ -499,16 +1227,295 @@ impl Storable for EventSeries {
                     default_duration_minutes: u32,
                     color: Option<String>,
                     paused: bool,
+                    claims_paused: bool,
+                    deleted: bool,
+                    public: bool,
+                    capacity: Option<u32>,
+                    default_host: Option<Principal>,
+                    reminder_hours_before_override: Option<u32>,
                     created_at: u64,
                     created_by: Principal,
                 }
-                if let Ok(v3) = Decode!(bytes.as_ref(), V3EventSeries) {
+                if let Ok(v9) = Decode!(bytes.as_ref(), V9EventSeries) {
                     return EventSeries {
-                        series_id: v3.series_id,
-                        title: v3.title,
-                        notes: v3.notes,
-                        link: v3.link,
-                        frequency: v3.frequency,
+                        series_id: v9.series_id,
+                        title: v9.title,
+                        notes: v9.notes,
+                        link: v9.link,
+                        frequency: v9.frequency,
+                        weekday: v9.weekday,
+                        weekday_ordinal: v9.weekday_ordinal,
+                        start_date: v9.start_date,
+                        end_date: v9.end_date,
+                        default_duration_minutes: v9.default_duration_minutes,
+                        color: v9.color,
+                        paused: v9.paused,
+                        claims_paused: v9.claims_paused,
+                        deleted: v9.deleted,
+                        public: v9.public,
+                        capacity: v9.capacity,
+                        default_host: v9.default_host,
+                        reminder_hours_before_override: v9.reminder_hours_before_override,
+                        created_at: v9.created_at,
+                        created_by: v9.created_by,
+                        notes_template: None,
+                    };
+                }
+                // V8: has public flag but no capacity
+                #[derive(CandidType, Deserialize)]
+                struct V8EventSeries {
+                    series_id: [u8; 16],
+                    title: String,
+                    notes: String,
+                    link: Option<String>,
+                    frequency: Frequency,
+                    weekday: Weekday,
+                    weekday_ordinal: Option<WeekdayOrdinal>,
+                    start_date: u64,
+                    end_date: Option<u64>,
+                    default_duration_minutes: u32,
+                    color: Option<String>,
+                    paused: bool,
+                    claims_paused: bool,
+                    deleted: bool,
+                    public: bool,
+                    default_host: Option<Principal>,
+                    reminder_hours_before_override: Option<u32>,
+                    created_at: u64,
+                    created_by: Principal,
+                }
+                if let Ok(v8) = Decode!(bytes.as_ref(), V8EventSeries) {
+                    return EventSeries {
+                        series_id: v8.series_id,
+                        title: v8.title,
+                        notes: v8.notes,
+                        link: v8.link,
+                        frequency: v8.frequency,
+                        weekday: v8.weekday,
+                        weekday_ordinal: v8.weekday_ordinal,
+                        start_date: v8.start_date,
+                        end_date: v8.end_date,
+                        default_duration_minutes: v8.default_duration_minutes,
+                        color: v8.color,
+                        paused: v8.paused,
+                        claims_paused: v8.claims_paused,
+                        deleted: v8.deleted,
+                        public: v8.public,
+                        capacity: None,
+                        default_host: v8.default_host,
+                        reminder_hours_before_override: v8.reminder_hours_before_override,
+                        created_at: v8.created_at,
+                        created_by: v8.created_by,
+                        notes_template: None,
+                    };
+                }
+                // V7: has soft delete but no public flag
+                #[derive(CandidType, Deserialize)]
+                struct V7EventSeries {
+                    series_id: [u8; 16],
+                    title: String,
+                    notes: String,
+                    link: Option<String>,
+                    frequency: Frequency,
+                    weekday: Weekday,
+                    weekday_ordinal: Option<WeekdayOrdinal>,
+                    start_date: u64,
+                    end_date: Option<u64>,
+                    default_duration_minutes: u32,
+                    color: Option<String>,
+                    paused: bool,
+                    claims_paused: bool,
+                    deleted: bool,
+                    default_host: Option<Principal>,
+                    reminder_hours_before_override: Option<u32>,
+                    created_at: u64,
+                    created_by: Principal,
+                }
+                if let Ok(v7) = Decode!(bytes.as_ref(), V7EventSeries) {
+                    return EventSeries {
+                        series_id: v7.series_id,
+                        title: v7.title,
+                        notes: v7.notes,
+                        link: v7.link,
+                        frequency: v7.frequency,
+                        weekday: v7.weekday,
+                        weekday_ordinal: v7.weekday_ordinal,
+                        start_date: v7.start_date,
+                        end_date: v7.end_date,
+                        default_duration_minutes: v7.default_duration_minutes,
+                        color: v7.color,
+                        paused: v7.paused,
+                        claims_paused: v7.claims_paused,
+                        deleted: v7.deleted,
+                        public: true,
+                        capacity: None,
+                        default_host: v7.default_host,
+                        reminder_hours_before_override: v7.reminder_hours_before_override,
+                        created_at: v7.created_at,
+                        created_by: v7.created_by,
+                        notes_template: None,
+                    };
+                }
+                // V6: has per-series claims pause but no soft delete
+                #[derive(CandidType, Deserialize)]
+                struct V6EventSeries {
+                    series_id: [u8; 16],
+                    title: String,
+                    notes: String,
+                    link: Option<String>,
+                    frequency: Frequency,
+                    weekday: Weekday,
+                    weekday_ordinal: Option<WeekdayOrdinal>,
+                    start_date: u64,
+                    end_date: Option<u64>,
+                    default_duration_minutes: u32,
+                    color: Option<String>,
+                    paused: bool,
+                    claims_paused: bool,
+                    default_host: Option<Principal>,
+                    reminder_hours_before_override: Option<u32>,
+                    created_at: u64,
+                    created_by: Principal,
+                }
+                if let Ok(v6) = Decode!(bytes.as_ref(), V6EventSeries) {
+                    return EventSeries {
+                        series_id: v6.series_id,
+                        title: v6.title,
+                        notes: v6.notes,
+                        link: v6.link,
+                        frequency: v6.frequency,
+                        weekday: v6.weekday,
+                        weekday_ordinal: v6.weekday_ordinal,
+                        start_date: v6.start_date,
+                        end_date: v6.end_date,
+                        default_duration_minutes: v6.default_duration_minutes,
+                        color: v6.color,
+                        paused: v6.paused,
+                        claims_paused: v6.claims_paused,
+                        deleted: false,
+                        public: true,
+                        capacity: None,
+                        default_host: v6.default_host,
+                        reminder_hours_before_override: v6.reminder_hours_before_override,
+                        created_at: v6.created_at,
+                        created_by: v6.created_by,
+                        notes_template: None,
+                    };
+                }
+                // V5: has reminder override but no per-series claims pause
+                #[derive(CandidType, Deserialize)]
+                struct V5EventSeries {
+                    series_id: [u8; 16],
+                    title: String,
+                    notes: String,
+                    link: Option<String>,
+                    frequency: Frequency,
+                    weekday: Weekday,
+                    weekday_ordinal: Option<WeekdayOrdinal>,
+                    start_date: u64,
+                    end_date: Option<u64>,
+                    default_duration_minutes: u32,
+                    color: Option<String>,
+                    paused: bool,
+                    default_host: Option<Principal>,
+                    reminder_hours_before_override: Option<u32>,
+                    created_at: u64,
+                    created_by: Principal,
+                }
+                if let Ok(v5) = Decode!(bytes.as_ref(), V5EventSeries) {
+                    return EventSeries {
+                        series_id: v5.series_id,
+                        title: v5.title,
+                        notes: v5.notes,
+                        link: v5.link,
+                        frequency: v5.frequency,
+                        weekday: v5.weekday,
+                        weekday_ordinal: v5.weekday_ordinal,
+                        start_date: v5.start_date,
+                        end_date: v5.end_date,
+                        default_duration_minutes: v5.default_duration_minutes,
+                        color: v5.color,
+                        paused: v5.paused,
+                        claims_paused: false,
+                        deleted: false,
+                        public: true,
+                        capacity: None,
+                        default_host: v5.default_host,
+                        reminder_hours_before_override: v5.reminder_hours_before_override,
+                        created_at: v5.created_at,
+                        created_by: v5.created_by,
+                        notes_template: None,
+                    };
+                }
+                // V4: has default_host but no reminder override
+                #[derive(CandidType, Deserialize)]
+                struct V4EventSeries {
+                    series_id: [u8; 16],
+                    title: String,
+                    notes: String,
+                    link: Option<String>,
+                    frequency: Frequency,
+                    weekday: Weekday,
+                    weekday_ordinal: Option<WeekdayOrdinal>,
+                    start_date: u64,
+                    end_date: Option<u64>,
+                    default_duration_minutes: u32,
+                    color: Option<String>,
+                    paused: bool,
+                    default_host: Option<Principal>,
+                    created_at: u64,
+                    created_by: Principal,
+                }
+                if let Ok(v4) = Decode!(bytes.as_ref(), V4EventSeries) {
+                    return EventSeries {
+                        series_id: v4.series_id,
+                        title: v4.title,
+                        notes: v4.notes,
+                        link: v4.link,
+                        frequency: v4.frequency,
+                        weekday: v4.weekday,
+                        weekday_ordinal: v4.weekday_ordinal,
+                        start_date: v4.start_date,
+                        end_date: v4.end_date,
+                        default_duration_minutes: v4.default_duration_minutes,
+                        color: v4.color,
+                        paused: v4.paused,
+                        claims_paused: false,
+                        deleted: false,
+                        public: true,
+                        capacity: None,
+                        default_host: v4.default_host,
+                        reminder_hours_before_override: None,
+                        created_at: v4.created_at,
+                        created_by: v4.created_by,
+                        notes_template: None,
+                    };
+                }
+                // V3: has color+paused but no default_host
+                #[derive(CandidType, Deserialize)]
+                struct V3EventSeries {
+                    series_id: [u8; 16],
+                    title: String,
+                    notes: String,
+                    link: Option<String>,
+                    frequency: Frequency,
+                    weekday: Weekday,
+                    weekday_ordinal: Option<WeekdayOrdinal>,
+                    start_date: u64,
+                    end_date: Option<u64>,
+                    default_duration_minutes: u32,
+                    color: Option<String>,
+                    paused: bool,
+                    created_at: u64,
+                    created_by: Principal,
+                }
+                if let Ok(v3) = Decode!(bytes.as_ref(), V3EventSeries) {
+                    return EventSeries {
+                        series_id: v3.series_id,
+                        title: v3.title,
+                        notes: v3.notes,
+                        link: v3.link,
+                        frequency: v3.frequency,
                         weekday: v3.weekday,
                         weekday_ordinal: v3.weekday_ordinal,
                         start_date: v3.start_date,
@@ -516,9 +1523,15 @@ impl Storable for EventSeries {
                         default_duration_minutes: v3.default_duration_minutes,
                         color: v3.color,
                         paused: v3.paused,
+                        claims_paused: false,
+                        deleted: false,
+                        public: true,
+                        capacity: None,
                         default_host: None,
+                        reminder_hours_before_override: None,
                         created_at: v3.created_at,
                         created_by: v3.created_by,
+                        notes_template: None,
                     };
                 }
                 // V2: has color but no paused, no default_host
@@ -552,9 +1565,15 @@ impl Storable for EventSeries {
                         default_duration_minutes: mid.default_duration_minutes,
                         color: mid.color,
                         paused: false,
+                        claims_paused: false,
+                        deleted: false,
+                        public: true,
+                        capacity: None,
                         default_host: None,
+                        reminder_hours_before_override: None,
                         created_at: mid.created_at,
                         created_by: mid.created_by,
+                        notes_template: None,
                     };
                 }
                 // V1: no color, no paused, no default_host
@@ -587,9 +1606,15 @@ impl Storable for EventSeries {
                     default_duration_minutes: old.default_duration_minutes,
                     color: None,
                     paused: false,
+                    claims_paused: false,
+                    deleted: false,
+                    public: true,
+                    capacity: None,
                     default_host: None,
+                    reminder_hours_before_override: None,
                     created_at: old.created_at,
                     created_by: old.created_by,
+                    notes_template: None,
                 }
             }
         }
@@ -603,14 +1628,315 @@ impl Storable for EventSeries {
 
 
 impl Storable for EventInstance {
-    fn to_bytes(&self) -> Cow<[u8]> {
+    fn to_bytes(&self) -> Cow<'_, [u8]> {
         Cow::Owned(Encode!(self).unwrap())
     }
 
-    fn from_bytes(bytes: Cow<[u8]>) -> Self {
+    fn from_bytes(bytes: Cow<'_, [u8]>) -> Self {
         match Decode!(bytes.as_ref(), Self) {
             Ok(i) => i,
             Err(_) => {
+                // V8: has overridden fields but no series title
+                #[derive(CandidType, Deserialize)]
+                struct V8EventInstance {
+                    instance_id: [u8; 16],
+                    series_id: Option<[u8; 16]>,
+                    start_utc: u64,
+                    end_utc: u64,
+                    title: String,
+                    notes: String,
+                    link: Option<String>,
+                    host_principal: Option<Principal>,
+                    status: EventStatus,
+                    color: Option<String>,
+                    created_at: u64,
+                    occurrence_start: Option<u64>,
+                    assigned_by: Option<Principal>,
+                    assigned_at: Option<u64>,
+                    created_by: Option<Principal>,
+                    public: bool,
+                    capacity: Option<u32>,
+                    rsvp_count: u32,
+                    overridden_fields: Vec<OverrideField>,
+                }
+                if let Ok(v8) = Decode!(bytes.as_ref(), V8EventInstance) {
+                    return EventInstance {
+                        instance_id: v8.instance_id,
+                        series_id: v8.series_id,
+                        start_utc: v8.start_utc,
+                        end_utc: v8.end_utc,
+                        title: v8.title,
+                        notes: v8.notes,
+                        link: v8.link,
+                        host_principal: v8.host_principal,
+                        status: v8.status,
+                        color: v8.color,
+                        created_at: v8.created_at,
+                        occurrence_start: v8.occurrence_start,
+                        assigned_by: v8.assigned_by,
+                        assigned_at: v8.assigned_at,
+                        created_by: v8.created_by,
+                        public: v8.public,
+                        capacity: v8.capacity,
+                        rsvp_count: v8.rsvp_count,
+                        overridden_fields: v8.overridden_fields,
+                        series_title: None,
+                    };
+                }
+                // V7: has capacity and rsvp_count but no overridden_fields
+                #[derive(CandidType, Deserialize)]
+                struct V7EventInstance {
+                    instance_id: [u8; 16],
+                    series_id: Option<[u8; 16]>,
+                    start_utc: u64,
+                    end_utc: u64,
+                    title: String,
+                    notes: String,
+                    link: Option<String>,
+                    host_principal: Option<Principal>,
+                    status: EventStatus,
+                    color: Option<String>,
+                    created_at: u64,
+                    occurrence_start: Option<u64>,
+                    assigned_by: Option<Principal>,
+                    assigned_at: Option<u64>,
+                    created_by: Option<Principal>,
+                    public: bool,
+                    capacity: Option<u32>,
+                    rsvp_count: u32,
+                }
+                if let Ok(v7) = Decode!(bytes.as_ref(), V7EventInstance) {
+                    return EventInstance {
+                        instance_id: v7.instance_id,
+                        series_id: v7.series_id,
+                        start_utc: v7.start_utc,
+                        end_utc: v7.end_utc,
+                        title: v7.title,
+                        notes: v7.notes,
+                        link: v7.link,
+                        host_principal: v7.host_principal,
+                        status: v7.status,
+                        color: v7.color,
+                        created_at: v7.created_at,
+                        occurrence_start: v7.occurrence_start,
+                        assigned_by: v7.assigned_by,
+                        assigned_at: v7.assigned_at,
+                        created_by: v7.created_by,
+                        public: v7.public,
+                        capacity: v7.capacity,
+                        rsvp_count: v7.rsvp_count,
+                        overridden_fields: vec![],
+                        series_title: None,
+                    };
+                }
+                // V6: has public flag but no capacity or rsvp_count
+                #[derive(CandidType, Deserialize)]
+                struct V6EventInstance {
+                    instance_id: [u8; 16],
+                    series_id: Option<[u8; 16]>,
+                    start_utc: u64,
+                    end_utc: u64,
+                    title: String,
+                    notes: String,
+                    link: Option<String>,
+                    host_principal: Option<Principal>,
+                    status: EventStatus,
+                    color: Option<String>,
+                    created_at: u64,
+                    occurrence_start: Option<u64>,
+                    assigned_by: Option<Principal>,
+                    assigned_at: Option<u64>,
+                    created_by: Option<Principal>,
+                    public: bool,
+                }
+                if let Ok(v6) = Decode!(bytes.as_ref(), V6EventInstance) {
+                    return EventInstance {
+                        instance_id: v6.instance_id,
+                        series_id: v6.series_id,
+                        start_utc: v6.start_utc,
+                        end_utc: v6.end_utc,
+                        title: v6.title,
+                        notes: v6.notes,
+                        link: v6.link,
+                        host_principal: v6.host_principal,
+                        status: v6.status,
+                        color: v6.color,
+                        created_at: v6.created_at,
+                        occurrence_start: v6.occurrence_start,
+                        assigned_by: v6.assigned_by,
+                        assigned_at: v6.assigned_at,
+                        created_by: v6.created_by,
+                        public: v6.public,
+                        capacity: None,
+                        rsvp_count: 0,
+                        overridden_fields: vec![],
+                        series_title: None,
+                    };
+                }
+                // V5: has created_by but no public flag
+                #[derive(CandidType, Deserialize)]
+                struct V5EventInstance {
+                    instance_id: [u8; 16],
+                    series_id: Option<[u8; 16]>,
+                    start_utc: u64,
+                    end_utc: u64,
+                    title: String,
+                    notes: String,
+                    link: Option<String>,
+                    host_principal: Option<Principal>,
+                    status: EventStatus,
+                    color: Option<String>,
+                    created_at: u64,
+                    occurrence_start: Option<u64>,
+                    assigned_by: Option<Principal>,
+                    assigned_at: Option<u64>,
+                    created_by: Option<Principal>,
+                }
+                if let Ok(v5) = Decode!(bytes.as_ref(), V5EventInstance) {
+                    return EventInstance {
+                        instance_id: v5.instance_id,
+                        series_id: v5.series_id,
+                        start_utc: v5.start_utc,
+                        end_utc: v5.end_utc,
+                        title: v5.title,
+                        notes: v5.notes,
+                        link: v5.link,
+                        host_principal: v5.host_principal,
+                        status: v5.status,
+                        color: v5.color,
+                        created_at: v5.created_at,
+                        occurrence_start: v5.occurrence_start,
+                        assigned_by: v5.assigned_by,
+                        assigned_at: v5.assigned_at,
+                        created_by: v5.created_by,
+                        public: true,
+                        capacity: None,
+                        rsvp_count: 0,
+                        overridden_fields: vec![],
+                        series_title: None,
+                    };
+                }
+                // V4: has assignment attribution but no created_by
+                #[derive(CandidType, Deserialize)]
+                struct V4EventInstance {
+                    instance_id: [u8; 16],
+                    series_id: Option<[u8; 16]>,
+                    start_utc: u64,
+                    end_utc: u64,
+                    title: String,
+                    notes: String,
+                    link: Option<String>,
+                    host_principal: Option<Principal>,
+                    status: EventStatus,
+                    color: Option<String>,
+                    created_at: u64,
+                    occurrence_start: Option<u64>,
+                    assigned_by: Option<Principal>,
+                    assigned_at: Option<u64>,
+                }
+                if let Ok(v4) = Decode!(bytes.as_ref(), V4EventInstance) {
+                    return EventInstance {
+                        instance_id: v4.instance_id,
+                        series_id: v4.series_id,
+                        start_utc: v4.start_utc,
+                        end_utc: v4.end_utc,
+                        title: v4.title,
+                        notes: v4.notes,
+                        link: v4.link,
+                        host_principal: v4.host_principal,
+                        status: v4.status,
+                        color: v4.color,
+                        created_at: v4.created_at,
+                        occurrence_start: v4.occurrence_start,
+                        assigned_by: v4.assigned_by,
+                        assigned_at: v4.assigned_at,
+                        created_by: None,
+                        public: true,
+                        capacity: None,
+                        rsvp_count: 0,
+                        overridden_fields: vec![],
+                        series_title: None,
+                    };
+                }
+                // V3: has occurrence_start but no assignment attribution
+                #[derive(CandidType, Deserialize)]
+                struct V3EventInstance {
+                    instance_id: [u8; 16],
+                    series_id: Option<[u8; 16]>,
+                    start_utc: u64,
+                    end_utc: u64,
+                    title: String,
+                    notes: String,
+                    link: Option<String>,
+                    host_principal: Option<Principal>,
+                    status: EventStatus,
+                    color: Option<String>,
+                    created_at: u64,
+                    occurrence_start: Option<u64>,
+                }
+                if let Ok(v3) = Decode!(bytes.as_ref(), V3EventInstance) {
+                    return EventInstance {
+                        instance_id: v3.instance_id,
+                        series_id: v3.series_id,
+                        start_utc: v3.start_utc,
+                        end_utc: v3.end_utc,
+                        title: v3.title,
+                        notes: v3.notes,
+                        link: v3.link,
+                        host_principal: v3.host_principal,
+                        status: v3.status,
+                        color: v3.color,
+                        created_at: v3.created_at,
+                        occurrence_start: v3.occurrence_start,
+                        assigned_by: None,
+                        assigned_at: None,
+                        created_by: None,
+                        public: true,
+                        capacity: None,
+                        rsvp_count: 0,
+                        overridden_fields: vec![],
+                        series_title: None,
+                    };
+                }
+                // V2: has color but no occurrence_start
+                #[derive(CandidType, Deserialize)]
+                struct V2EventInstance {
+                    instance_id: [u8; 16],
+                    series_id: Option<[u8; 16]>,
+                    start_utc: u64,
+                    end_utc: u64,
+                    title: String,
+                    notes: String,
+                    link: Option<String>,
+                    host_principal: Option<Principal>,
+                    status: EventStatus,
+                    color: Option<String>,
+                    created_at: u64,
+                }
+                if let Ok(v2) = Decode!(bytes.as_ref(), V2EventInstance) {
+                    return EventInstance {
+                        instance_id: v2.instance_id,
+                        series_id: v2.series_id,
+                        start_utc: v2.start_utc,
+                        end_utc: v2.end_utc,
+                        title: v2.title,
+                        notes: v2.notes,
+                        link: v2.link,
+                        host_principal: v2.host_principal,
+                        status: v2.status,
+                        color: v2.color,
+                        created_at: v2.created_at,
+                        occurrence_start: None,
+                    assigned_by: None,
+                    assigned_at: None,
+                    created_by: None,
+                    public: true,
+                    capacity: None,
+                    rsvp_count: 0,
+                    overridden_fields: vec![],
+                        series_title: None,
+                    };
+                }
                 // Try decoding as old EventInstance format (without color)
                 #[derive(CandidType, Deserialize)]
                 struct OldEventInstance {
@@ -638,6 +1964,15 @@ impl Storable for EventInstance {
                     status: old.status,
                     color: None,
                     created_at: old.created_at,
+                    occurrence_start: None,
+                assigned_by: None,
+                assigned_at: None,
+                created_by: None,
+                public: true,
+                capacity: None,
+                rsvp_count: 0,
+                overridden_fields: vec![],
+                    series_title: None,
                 }
             }
         }
@@ -650,12 +1985,78 @@ impl Storable for EventInstance {
 }
 
 impl Storable for InstanceOverride {
-    fn to_bytes(&self) -> Cow<[u8]> {
+    fn to_bytes(&self) -> Cow<'_, [u8]> {
         Cow::Owned(Encode!(self).unwrap())
     }
 
-    fn from_bytes(bytes: Cow<[u8]>) -> Self {
-        Decode!(bytes.as_ref(), Self).unwrap()
+    fn from_bytes(bytes: Cow<'_, [u8]>) -> Self {
+        match Decode!(bytes.as_ref(), Self) {
+            Ok(o) => o,
+            Err(_) => {
+                // V2: has title_override but no assignment attribution
+                #[derive(CandidType, Deserialize)]
+                struct V2InstanceOverride {
+                    series_id: [u8; 16],
+                    occurrence_start_utc: u64,
+                    start_utc: Option<u64>,
+                    end_utc: Option<u64>,
+                    notes: Option<String>,
+                    title_override: Option<String>,
+                    host_principal: Option<Principal>,
+                    host_cleared: bool,
+                    cancelled: bool,
+                    updated_at: u64,
+                    updated_by: Principal,
+                }
+                if let Ok(v2) = Decode!(bytes.as_ref(), V2InstanceOverride) {
+                    return InstanceOverride {
+                        series_id: v2.series_id,
+                        occurrence_start_utc: v2.occurrence_start_utc,
+                        start_utc: v2.start_utc,
+                        end_utc: v2.end_utc,
+                        notes: v2.notes,
+                        title_override: v2.title_override,
+                        host_principal: v2.host_principal,
+                        host_cleared: v2.host_cleared,
+                        assigned_by: None,
+                        assigned_at: None,
+                        cancelled: v2.cancelled,
+                        updated_at: v2.updated_at,
+                        updated_by: v2.updated_by,
+                    };
+                }
+                // Try decoding as old InstanceOverride format (without title_override)
+                #[derive(CandidType, Deserialize)]
+                struct OldInstanceOverride {
+                    series_id: [u8; 16],
+                    occurrence_start_utc: u64,
+                    start_utc: Option<u64>,
+                    end_utc: Option<u64>,
+                    notes: Option<String>,
+                    host_principal: Option<Principal>,
+                    host_cleared: bool,
+                    cancelled: bool,
+                    updated_at: u64,
+                    updated_by: Principal,
+                }
+                let old = Decode!(bytes.as_ref(), OldInstanceOverride).unwrap();
+                InstanceOverride {
+                    series_id: old.series_id,
+                    occurrence_start_utc: old.occurrence_start_utc,
+                    start_utc: old.start_utc,
+                    end_utc: old.end_utc,
+                    notes: old.notes,
+                    title_override: None,
+                    host_principal: old.host_principal,
+                    host_cleared: old.host_cleared,
+                    assigned_by: None,
+                    assigned_at: None,
+                    cancelled: old.cancelled,
+                    updated_at: old.updated_at,
+                    updated_by: old.updated_by,
+                }
+            }
+        }
     }
 
     const BOUND: Bound = Bound::Bounded {
@@ -666,12 +2067,82 @@ impl Storable for InstanceOverride {
 
 
 impl Storable for NotificationJob {
-    fn to_bytes(&self) -> Cow<[u8]> {
+    fn to_bytes(&self) -> Cow<'_, [u8]> {
         Cow::Owned(Encode!(self).unwrap())
     }
 
-    fn from_bytes(bytes: Cow<[u8]>) -> Self {
-        Decode!(bytes.as_ref(), Self).unwrap()
+    fn from_bytes(bytes: Cow<'_, [u8]>) -> Self {
+        match Decode!(bytes.as_ref(), Self) {
+            Ok(j) => j,
+            Err(_) => {
+                // V2: before instance_id/series_id
+                #[derive(CandidType, Deserialize)]
+                struct V2NotificationJob {
+                    job_id: [u8; 16],
+                    created_at: u64,
+                    notification_type: NotificationType,
+                    recipient_principal: Principal,
+                    recipient_email: String,
+                    subject: String,
+                    body_text: String,
+                    ics_payload: Option<String>,
+                    status: NotificationStatus,
+                    sent_at: Option<u64>,
+                    error_message: Option<String>,
+                    requeued_at: Option<u64>,
+                }
+                if let Ok(v2) = Decode!(bytes.as_ref(), V2NotificationJob) {
+                    return NotificationJob {
+                        job_id: v2.job_id,
+                        created_at: v2.created_at,
+                        notification_type: v2.notification_type,
+                        recipient_principal: v2.recipient_principal,
+                        recipient_email: v2.recipient_email,
+                        subject: v2.subject,
+                        body_text: v2.body_text,
+                        ics_payload: v2.ics_payload,
+                        status: v2.status,
+                        sent_at: v2.sent_at,
+                        error_message: v2.error_message,
+                        requeued_at: v2.requeued_at,
+                        instance_id: None,
+                        series_id: None,
+                    };
+                }
+                // Try decoding as old NotificationJob format (without requeued_at)
+                #[derive(CandidType, Deserialize)]
+                struct OldNotificationJob {
+                    job_id: [u8; 16],
+                    created_at: u64,
+                    notification_type: NotificationType,
+                    recipient_principal: Principal,
+                    recipient_email: String,
+                    subject: String,
+                    body_text: String,
+                    ics_payload: Option<String>,
+                    status: NotificationStatus,
+                    sent_at: Option<u64>,
+                    error_message: Option<String>,
+                }
+                let old = Decode!(bytes.as_ref(), OldNotificationJob).unwrap();
+                NotificationJob {
+                    job_id: old.job_id,
+                    created_at: old.created_at,
+                    notification_type: old.notification_type,
+                    recipient_principal: old.recipient_principal,
+                    recipient_email: old.recipient_email,
+                    subject: old.subject,
+                    body_text: old.body_text,
+                    ics_payload: old.ics_payload,
+                    status: old.status,
+                    sent_at: old.sent_at,
+                    error_message: old.error_message,
+                    requeued_at: None,
+                    instance_id: None,
+                    series_id: None,
+                }
+            }
+        }
     }
 
     const BOUND: Bound = Bound::Bounded {
@@ -680,15 +2151,827 @@ impl Storable for NotificationJob {
     };
 }
 
+impl Storable for AuditEntry {
+    fn to_bytes(&self) -> Cow<'_, [u8]> {
+        Cow::Owned(Encode!(self).unwrap())
+    }
+
+    fn from_bytes(bytes: Cow<'_, [u8]>) -> Self {
+        Decode!(bytes.as_ref(), Self).unwrap()
+    }
+
+    const BOUND: Bound = Bound::Bounded {
+        max_size: MAX_AUDIT_ENTRY_SIZE,
+        is_fixed_size: false,
+    };
+}
+
+impl Storable for SettingsChange {
+    fn to_bytes(&self) -> Cow<'_, [u8]> {
+        Cow::Owned(Encode!(self).unwrap())
+    }
+
+    fn from_bytes(bytes: Cow<'_, [u8]>) -> Self {
+        Decode!(bytes.as_ref(), Self).unwrap()
+    }
+
+    const BOUND: Bound = Bound::Bounded {
+        max_size: MAX_SETTINGS_CHANGE_SIZE,
+        is_fixed_size: false,
+    };
+}
+
 impl Storable for GlobalSettings {
-    fn to_bytes(&self) -> Cow<[u8]> {
+    fn to_bytes(&self) -> Cow<'_, [u8]> {
         Cow::Owned(Encode!(self).unwrap())
     }
 
-    fn from_bytes(bytes: Cow<[u8]>) -> Self {
+    fn from_bytes(bytes: Cow<'_, [u8]>) -> Self {
         match Decode!(bytes.as_ref(), Self) {
             Ok(s) => s,
             Err(_) => {
+                // V17: has org timezone but no notification templates
+                #[derive(CandidType, Deserialize)]
+                struct V17GlobalSettings {
+                    forward_window_months: u8,
+                    claims_paused: bool,
+                    default_event_duration_minutes: u32,
+                    org_name: Option<String>,
+                    org_tagline: Option<String>,
+                    org_logo_url: Option<String>,
+                    max_concurrent_sessions: Option<u32>,
+                    enforce_concurrency_cap: bool,
+                    validate_one_off_host: bool,
+                    require_link: bool,
+                    auto_cancel_past_unclaimed: bool,
+                    auto_cancel_grace_hours: u32,
+                    business_hours: Option<(u32, u32)>,
+                    min_duration_minutes: Option<u32>,
+                    max_duration_minutes: Option<u32>,
+                    namespaced_instance_ids: bool,
+                    notify_admins_on_join: bool,
+                    org_default_notification_settings: Option<NotificationSettings>,
+                    week_starts_on: Weekday,
+                    version: u64,
+                    coverage_alert_threshold_pct: Option<f64>,
+                    min_claim_lead_minutes: Option<u32>,
+                    always_email_critical_removals: bool,
+                    org_timezone: Option<String>,
+                }
+                if let Ok(v17) = Decode!(bytes.as_ref(), V17GlobalSettings) {
+                    return GlobalSettings {
+                        forward_window_months: v17.forward_window_months,
+                        claims_paused: v17.claims_paused,
+                        default_event_duration_minutes: v17.default_event_duration_minutes,
+                        org_name: v17.org_name,
+                        org_tagline: v17.org_tagline,
+                        org_logo_url: v17.org_logo_url,
+                        max_concurrent_sessions: v17.max_concurrent_sessions,
+                        enforce_concurrency_cap: v17.enforce_concurrency_cap,
+                        validate_one_off_host: v17.validate_one_off_host,
+                        require_link: v17.require_link,
+                        auto_cancel_past_unclaimed: v17.auto_cancel_past_unclaimed,
+                        auto_cancel_grace_hours: v17.auto_cancel_grace_hours,
+                        business_hours: v17.business_hours,
+                        min_duration_minutes: v17.min_duration_minutes,
+                        max_duration_minutes: v17.max_duration_minutes,
+                        namespaced_instance_ids: v17.namespaced_instance_ids,
+                        notify_admins_on_join: v17.notify_admins_on_join,
+                        org_default_notification_settings: v17.org_default_notification_settings,
+                        week_starts_on: v17.week_starts_on,
+                        version: v17.version,
+                        coverage_alert_threshold_pct: v17.coverage_alert_threshold_pct,
+                        min_claim_lead_minutes: v17.min_claim_lead_minutes,
+                        always_email_critical_removals: v17.always_email_critical_removals,
+                        org_timezone: v17.org_timezone,
+                        notification_templates: vec![],
+                    };
+                }
+                // V16: has critical-removal override but no org timezone
+                #[derive(CandidType, Deserialize)]
+                struct V16GlobalSettings {
+                    forward_window_months: u8,
+                    claims_paused: bool,
+                    default_event_duration_minutes: u32,
+                    org_name: Option<String>,
+                    org_tagline: Option<String>,
+                    org_logo_url: Option<String>,
+                    max_concurrent_sessions: Option<u32>,
+                    enforce_concurrency_cap: bool,
+                    validate_one_off_host: bool,
+                    require_link: bool,
+                    auto_cancel_past_unclaimed: bool,
+                    auto_cancel_grace_hours: u32,
+                    business_hours: Option<(u32, u32)>,
+                    min_duration_minutes: Option<u32>,
+                    max_duration_minutes: Option<u32>,
+                    namespaced_instance_ids: bool,
+                    notify_admins_on_join: bool,
+                    org_default_notification_settings: Option<NotificationSettings>,
+                    week_starts_on: Weekday,
+                    version: u64,
+                    coverage_alert_threshold_pct: Option<f64>,
+                    min_claim_lead_minutes: Option<u32>,
+                    always_email_critical_removals: bool,
+                }
+                if let Ok(v16) = Decode!(bytes.as_ref(), V16GlobalSettings) {
+                    return GlobalSettings {
+                        forward_window_months: v16.forward_window_months,
+                        claims_paused: v16.claims_paused,
+                        default_event_duration_minutes: v16.default_event_duration_minutes,
+                        org_name: v16.org_name,
+                        org_tagline: v16.org_tagline,
+                        org_logo_url: v16.org_logo_url,
+                        max_concurrent_sessions: v16.max_concurrent_sessions,
+                        enforce_concurrency_cap: v16.enforce_concurrency_cap,
+                        validate_one_off_host: v16.validate_one_off_host,
+                        require_link: v16.require_link,
+                        auto_cancel_past_unclaimed: v16.auto_cancel_past_unclaimed,
+                        auto_cancel_grace_hours: v16.auto_cancel_grace_hours,
+                        business_hours: v16.business_hours,
+                        min_duration_minutes: v16.min_duration_minutes,
+                        max_duration_minutes: v16.max_duration_minutes,
+                        namespaced_instance_ids: v16.namespaced_instance_ids,
+                        notify_admins_on_join: v16.notify_admins_on_join,
+                        org_default_notification_settings: v16.org_default_notification_settings,
+                        week_starts_on: v16.week_starts_on,
+                        version: v16.version,
+                        coverage_alert_threshold_pct: v16.coverage_alert_threshold_pct,
+                        min_claim_lead_minutes: v16.min_claim_lead_minutes,
+                        always_email_critical_removals: v16.always_email_critical_removals,
+                        org_timezone: None,
+                        notification_templates: vec![],
+                    };
+                }
+                // V15: has claim lead time but no critical-removal override
+                #[derive(CandidType, Deserialize)]
+                struct V15GlobalSettings {
+                    forward_window_months: u8,
+                    claims_paused: bool,
+                    default_event_duration_minutes: u32,
+                    org_name: Option<String>,
+                    org_tagline: Option<String>,
+                    org_logo_url: Option<String>,
+                    max_concurrent_sessions: Option<u32>,
+                    enforce_concurrency_cap: bool,
+                    validate_one_off_host: bool,
+                    require_link: bool,
+                    auto_cancel_past_unclaimed: bool,
+                    auto_cancel_grace_hours: u32,
+                    business_hours: Option<(u32, u32)>,
+                    min_duration_minutes: Option<u32>,
+                    max_duration_minutes: Option<u32>,
+                    namespaced_instance_ids: bool,
+                    notify_admins_on_join: bool,
+                    org_default_notification_settings: Option<NotificationSettings>,
+                    week_starts_on: Weekday,
+                    version: u64,
+                    coverage_alert_threshold_pct: Option<f64>,
+                    min_claim_lead_minutes: Option<u32>,
+                }
+                if let Ok(v15) = Decode!(bytes.as_ref(), V15GlobalSettings) {
+                    return GlobalSettings {
+                        forward_window_months: v15.forward_window_months,
+                        claims_paused: v15.claims_paused,
+                        default_event_duration_minutes: v15.default_event_duration_minutes,
+                        org_name: v15.org_name,
+                        org_tagline: v15.org_tagline,
+                        org_logo_url: v15.org_logo_url,
+                        max_concurrent_sessions: v15.max_concurrent_sessions,
+                        enforce_concurrency_cap: v15.enforce_concurrency_cap,
+                        validate_one_off_host: v15.validate_one_off_host,
+                        require_link: v15.require_link,
+                        auto_cancel_past_unclaimed: v15.auto_cancel_past_unclaimed,
+                        auto_cancel_grace_hours: v15.auto_cancel_grace_hours,
+                        business_hours: v15.business_hours,
+                        min_duration_minutes: v15.min_duration_minutes,
+                        max_duration_minutes: v15.max_duration_minutes,
+                        namespaced_instance_ids: v15.namespaced_instance_ids,
+                        notify_admins_on_join: v15.notify_admins_on_join,
+                        org_default_notification_settings: v15.org_default_notification_settings,
+                        week_starts_on: v15.week_starts_on,
+                        version: v15.version,
+                        coverage_alert_threshold_pct: v15.coverage_alert_threshold_pct,
+                        min_claim_lead_minutes: v15.min_claim_lead_minutes,
+                        always_email_critical_removals: false,
+                        org_timezone: None,
+                        notification_templates: vec![],
+                    };
+                }
+                // V14: has coverage alert threshold but no claim lead time
+                #[derive(CandidType, Deserialize)]
+                struct V14GlobalSettings {
+                    forward_window_months: u8,
+                    claims_paused: bool,
+                    default_event_duration_minutes: u32,
+                    org_name: Option<String>,
+                    org_tagline: Option<String>,
+                    org_logo_url: Option<String>,
+                    max_concurrent_sessions: Option<u32>,
+                    enforce_concurrency_cap: bool,
+                    validate_one_off_host: bool,
+                    require_link: bool,
+                    auto_cancel_past_unclaimed: bool,
+                    auto_cancel_grace_hours: u32,
+                    business_hours: Option<(u32, u32)>,
+                    min_duration_minutes: Option<u32>,
+                    max_duration_minutes: Option<u32>,
+                    namespaced_instance_ids: bool,
+                    notify_admins_on_join: bool,
+                    org_default_notification_settings: Option<NotificationSettings>,
+                    week_starts_on: Weekday,
+                    version: u64,
+                    coverage_alert_threshold_pct: Option<f64>,
+                }
+                if let Ok(v14) = Decode!(bytes.as_ref(), V14GlobalSettings) {
+                    return GlobalSettings {
+                        forward_window_months: v14.forward_window_months,
+                        claims_paused: v14.claims_paused,
+                        default_event_duration_minutes: v14.default_event_duration_minutes,
+                        org_name: v14.org_name,
+                        org_tagline: v14.org_tagline,
+                        org_logo_url: v14.org_logo_url,
+                        max_concurrent_sessions: v14.max_concurrent_sessions,
+                        enforce_concurrency_cap: v14.enforce_concurrency_cap,
+                        validate_one_off_host: v14.validate_one_off_host,
+                        require_link: v14.require_link,
+                        auto_cancel_past_unclaimed: v14.auto_cancel_past_unclaimed,
+                        auto_cancel_grace_hours: v14.auto_cancel_grace_hours,
+                        business_hours: v14.business_hours,
+                        min_duration_minutes: v14.min_duration_minutes,
+                        max_duration_minutes: v14.max_duration_minutes,
+                        namespaced_instance_ids: v14.namespaced_instance_ids,
+                        notify_admins_on_join: v14.notify_admins_on_join,
+                        org_default_notification_settings: v14.org_default_notification_settings,
+                        week_starts_on: v14.week_starts_on,
+                        version: v14.version,
+                        coverage_alert_threshold_pct: v14.coverage_alert_threshold_pct,
+                        min_claim_lead_minutes: None,
+                        always_email_critical_removals: false,
+                        org_timezone: None,
+                        notification_templates: vec![],
+                    };
+                }
+                // V13: has version but no coverage alert threshold
+                #[derive(CandidType, Deserialize)]
+                struct V13GlobalSettings {
+                    forward_window_months: u8,
+                    claims_paused: bool,
+                    default_event_duration_minutes: u32,
+                    org_name: Option<String>,
+                    org_tagline: Option<String>,
+                    org_logo_url: Option<String>,
+                    max_concurrent_sessions: Option<u32>,
+                    enforce_concurrency_cap: bool,
+                    validate_one_off_host: bool,
+                    require_link: bool,
+                    auto_cancel_past_unclaimed: bool,
+                    auto_cancel_grace_hours: u32,
+                    business_hours: Option<(u32, u32)>,
+                    min_duration_minutes: Option<u32>,
+                    max_duration_minutes: Option<u32>,
+                    namespaced_instance_ids: bool,
+                    notify_admins_on_join: bool,
+                    org_default_notification_settings: Option<NotificationSettings>,
+                    week_starts_on: Weekday,
+                    version: u64,
+                }
+                if let Ok(v13) = Decode!(bytes.as_ref(), V13GlobalSettings) {
+                    return GlobalSettings {
+                        forward_window_months: v13.forward_window_months,
+                        claims_paused: v13.claims_paused,
+                        default_event_duration_minutes: v13.default_event_duration_minutes,
+                        org_name: v13.org_name,
+                        org_tagline: v13.org_tagline,
+                        org_logo_url: v13.org_logo_url,
+                        max_concurrent_sessions: v13.max_concurrent_sessions,
+                        enforce_concurrency_cap: v13.enforce_concurrency_cap,
+                        validate_one_off_host: v13.validate_one_off_host,
+                        require_link: v13.require_link,
+                        auto_cancel_past_unclaimed: v13.auto_cancel_past_unclaimed,
+                        auto_cancel_grace_hours: v13.auto_cancel_grace_hours,
+                        business_hours: v13.business_hours,
+                        min_duration_minutes: v13.min_duration_minutes,
+                        max_duration_minutes: v13.max_duration_minutes,
+                        namespaced_instance_ids: v13.namespaced_instance_ids,
+                        notify_admins_on_join: v13.notify_admins_on_join,
+                        org_default_notification_settings: v13.org_default_notification_settings,
+                        week_starts_on: v13.week_starts_on,
+                        version: v13.version,
+                        coverage_alert_threshold_pct: None,
+                        min_claim_lead_minutes: None,
+                        always_email_critical_removals: false,
+                        org_timezone: None,
+                        notification_templates: vec![],
+                    };
+                }
+                // V12: has week start but no version
+                #[derive(CandidType, Deserialize)]
+                struct V12GlobalSettings {
+                    forward_window_months: u8,
+                    claims_paused: bool,
+                    default_event_duration_minutes: u32,
+                    org_name: Option<String>,
+                    org_tagline: Option<String>,
+                    org_logo_url: Option<String>,
+                    max_concurrent_sessions: Option<u32>,
+                    enforce_concurrency_cap: bool,
+                    validate_one_off_host: bool,
+                    require_link: bool,
+                    auto_cancel_past_unclaimed: bool,
+                    auto_cancel_grace_hours: u32,
+                    business_hours: Option<(u32, u32)>,
+                    min_duration_minutes: Option<u32>,
+                    max_duration_minutes: Option<u32>,
+                    namespaced_instance_ids: bool,
+                    notify_admins_on_join: bool,
+                    org_default_notification_settings: Option<NotificationSettings>,
+                    week_starts_on: Weekday,
+                }
+                if let Ok(v12) = Decode!(bytes.as_ref(), V12GlobalSettings) {
+                    return GlobalSettings {
+                        forward_window_months: v12.forward_window_months,
+                        claims_paused: v12.claims_paused,
+                        default_event_duration_minutes: v12.default_event_duration_minutes,
+                        org_name: v12.org_name,
+                        org_tagline: v12.org_tagline,
+                        org_logo_url: v12.org_logo_url,
+                        max_concurrent_sessions: v12.max_concurrent_sessions,
+                        enforce_concurrency_cap: v12.enforce_concurrency_cap,
+                        validate_one_off_host: v12.validate_one_off_host,
+                        require_link: v12.require_link,
+                        auto_cancel_past_unclaimed: v12.auto_cancel_past_unclaimed,
+                        auto_cancel_grace_hours: v12.auto_cancel_grace_hours,
+                        business_hours: v12.business_hours,
+                        min_duration_minutes: v12.min_duration_minutes,
+                        max_duration_minutes: v12.max_duration_minutes,
+                        namespaced_instance_ids: v12.namespaced_instance_ids,
+                        notify_admins_on_join: v12.notify_admins_on_join,
+                        org_default_notification_settings: v12.org_default_notification_settings,
+                        week_starts_on: v12.week_starts_on,
+                        version: 0,
+                        coverage_alert_threshold_pct: None,
+                        min_claim_lead_minutes: None,
+                        always_email_critical_removals: false,
+                        org_timezone: None,
+                        notification_templates: vec![],
+                    };
+                }
+                // V11: has org default notification settings but no week start
+                #[derive(CandidType, Deserialize)]
+                struct V11GlobalSettings {
+                    forward_window_months: u8,
+                    claims_paused: bool,
+                    default_event_duration_minutes: u32,
+                    org_name: Option<String>,
+                    org_tagline: Option<String>,
+                    org_logo_url: Option<String>,
+                    max_concurrent_sessions: Option<u32>,
+                    enforce_concurrency_cap: bool,
+                    validate_one_off_host: bool,
+                    require_link: bool,
+                    auto_cancel_past_unclaimed: bool,
+                    auto_cancel_grace_hours: u32,
+                    business_hours: Option<(u32, u32)>,
+                    min_duration_minutes: Option<u32>,
+                    max_duration_minutes: Option<u32>,
+                    namespaced_instance_ids: bool,
+                    notify_admins_on_join: bool,
+                    org_default_notification_settings: Option<NotificationSettings>,
+                }
+                if let Ok(v11) = Decode!(bytes.as_ref(), V11GlobalSettings) {
+                    return GlobalSettings {
+                        forward_window_months: v11.forward_window_months,
+                        claims_paused: v11.claims_paused,
+                        default_event_duration_minutes: v11.default_event_duration_minutes,
+                        org_name: v11.org_name,
+                        org_tagline: v11.org_tagline,
+                        org_logo_url: v11.org_logo_url,
+                        max_concurrent_sessions: v11.max_concurrent_sessions,
+                        enforce_concurrency_cap: v11.enforce_concurrency_cap,
+                        validate_one_off_host: v11.validate_one_off_host,
+                        require_link: v11.require_link,
+                        auto_cancel_past_unclaimed: v11.auto_cancel_past_unclaimed,
+                        auto_cancel_grace_hours: v11.auto_cancel_grace_hours,
+                        business_hours: v11.business_hours,
+                        min_duration_minutes: v11.min_duration_minutes,
+                        max_duration_minutes: v11.max_duration_minutes,
+                        namespaced_instance_ids: v11.namespaced_instance_ids,
+                        notify_admins_on_join: v11.notify_admins_on_join,
+                        org_default_notification_settings: v11.org_default_notification_settings,
+                        week_starts_on: Weekday::Mon,
+                        version: 0,
+                        coverage_alert_threshold_pct: None,
+                        min_claim_lead_minutes: None,
+                        always_email_critical_removals: false,
+                        org_timezone: None,
+                        notification_templates: vec![],
+                    };
+                }
+                // V10: has join notifications but no org default notification settings
+                #[derive(CandidType, Deserialize)]
+                struct V10GlobalSettings {
+                    forward_window_months: u8,
+                    claims_paused: bool,
+                    default_event_duration_minutes: u32,
+                    org_name: Option<String>,
+                    org_tagline: Option<String>,
+                    org_logo_url: Option<String>,
+                    max_concurrent_sessions: Option<u32>,
+                    enforce_concurrency_cap: bool,
+                    validate_one_off_host: bool,
+                    require_link: bool,
+                    auto_cancel_past_unclaimed: bool,
+                    auto_cancel_grace_hours: u32,
+                    business_hours: Option<(u32, u32)>,
+                    min_duration_minutes: Option<u32>,
+                    max_duration_minutes: Option<u32>,
+                    namespaced_instance_ids: bool,
+                    notify_admins_on_join: bool,
+                }
+                if let Ok(v10) = Decode!(bytes.as_ref(), V10GlobalSettings) {
+                    return GlobalSettings {
+                        forward_window_months: v10.forward_window_months,
+                        claims_paused: v10.claims_paused,
+                        default_event_duration_minutes: v10.default_event_duration_minutes,
+                        org_name: v10.org_name,
+                        org_tagline: v10.org_tagline,
+                        org_logo_url: v10.org_logo_url,
+                        max_concurrent_sessions: v10.max_concurrent_sessions,
+                        enforce_concurrency_cap: v10.enforce_concurrency_cap,
+                        validate_one_off_host: v10.validate_one_off_host,
+                        require_link: v10.require_link,
+                        auto_cancel_past_unclaimed: v10.auto_cancel_past_unclaimed,
+                        auto_cancel_grace_hours: v10.auto_cancel_grace_hours,
+                        business_hours: v10.business_hours,
+                        min_duration_minutes: v10.min_duration_minutes,
+                        max_duration_minutes: v10.max_duration_minutes,
+                        namespaced_instance_ids: v10.namespaced_instance_ids,
+                        notify_admins_on_join: v10.notify_admins_on_join,
+                        org_default_notification_settings: None,
+                        week_starts_on: Weekday::Mon,
+                        version: 0,
+                        coverage_alert_threshold_pct: None,
+                        min_claim_lead_minutes: None,
+                        always_email_critical_removals: false,
+                        org_timezone: None,
+                        notification_templates: vec![],
+                    };
+                }
+                // V9: has instance id namespacing but no join notifications
+                #[derive(CandidType, Deserialize)]
+                struct V9GlobalSettings {
+                    forward_window_months: u8,
+                    claims_paused: bool,
+                    default_event_duration_minutes: u32,
+                    org_name: Option<String>,
+                    org_tagline: Option<String>,
+                    org_logo_url: Option<String>,
+                    max_concurrent_sessions: Option<u32>,
+                    enforce_concurrency_cap: bool,
+                    validate_one_off_host: bool,
+                    require_link: bool,
+                    auto_cancel_past_unclaimed: bool,
+                    auto_cancel_grace_hours: u32,
+                    business_hours: Option<(u32, u32)>,
+                    min_duration_minutes: Option<u32>,
+                    max_duration_minutes: Option<u32>,
+                    namespaced_instance_ids: bool,
+                }
+                if let Ok(v9) = Decode!(bytes.as_ref(), V9GlobalSettings) {
+                    return GlobalSettings {
+                        forward_window_months: v9.forward_window_months,
+                        claims_paused: v9.claims_paused,
+                        default_event_duration_minutes: v9.default_event_duration_minutes,
+                        org_name: v9.org_name,
+                        org_tagline: v9.org_tagline,
+                        org_logo_url: v9.org_logo_url,
+                        max_concurrent_sessions: v9.max_concurrent_sessions,
+                        enforce_concurrency_cap: v9.enforce_concurrency_cap,
+                        validate_one_off_host: v9.validate_one_off_host,
+                        require_link: v9.require_link,
+                        auto_cancel_past_unclaimed: v9.auto_cancel_past_unclaimed,
+                        auto_cancel_grace_hours: v9.auto_cancel_grace_hours,
+                        business_hours: v9.business_hours,
+                        min_duration_minutes: v9.min_duration_minutes,
+                        max_duration_minutes: v9.max_duration_minutes,
+                        namespaced_instance_ids: v9.namespaced_instance_ids,
+                        notify_admins_on_join: true,
+                        org_default_notification_settings: None,
+                        week_starts_on: Weekday::Mon,
+                        version: 0,
+                        coverage_alert_threshold_pct: None,
+                        min_claim_lead_minutes: None,
+                        always_email_critical_removals: false,
+                        org_timezone: None,
+                        notification_templates: vec![],
+                    };
+                }
+                // V8: has duration limits but no instance id namespacing
+                #[derive(CandidType, Deserialize)]
+                struct V8GlobalSettings {
+                    forward_window_months: u8,
+                    claims_paused: bool,
+                    default_event_duration_minutes: u32,
+                    org_name: Option<String>,
+                    org_tagline: Option<String>,
+                    org_logo_url: Option<String>,
+                    max_concurrent_sessions: Option<u32>,
+                    enforce_concurrency_cap: bool,
+                    validate_one_off_host: bool,
+                    require_link: bool,
+                    auto_cancel_past_unclaimed: bool,
+                    auto_cancel_grace_hours: u32,
+                    business_hours: Option<(u32, u32)>,
+                    min_duration_minutes: Option<u32>,
+                    max_duration_minutes: Option<u32>,
+                }
+                if let Ok(v8) = Decode!(bytes.as_ref(), V8GlobalSettings) {
+                    return GlobalSettings {
+                        forward_window_months: v8.forward_window_months,
+                        claims_paused: v8.claims_paused,
+                        default_event_duration_minutes: v8.default_event_duration_minutes,
+                        org_name: v8.org_name,
+                        org_tagline: v8.org_tagline,
+                        org_logo_url: v8.org_logo_url,
+                        max_concurrent_sessions: v8.max_concurrent_sessions,
+                        enforce_concurrency_cap: v8.enforce_concurrency_cap,
+                        validate_one_off_host: v8.validate_one_off_host,
+                        require_link: v8.require_link,
+                        auto_cancel_past_unclaimed: v8.auto_cancel_past_unclaimed,
+                        auto_cancel_grace_hours: v8.auto_cancel_grace_hours,
+                        business_hours: v8.business_hours,
+                        min_duration_minutes: v8.min_duration_minutes,
+                        max_duration_minutes: v8.max_duration_minutes,
+                        namespaced_instance_ids: false,
+                        notify_admins_on_join: true,
+                        org_default_notification_settings: None,
+                        week_starts_on: Weekday::Mon,
+                        version: 0,
+                        coverage_alert_threshold_pct: None,
+                        min_claim_lead_minutes: None,
+                        always_email_critical_removals: false,
+                        org_timezone: None,
+                        notification_templates: vec![],
+                    };
+                }
+                // V7: has business hours but no duration limits
+                #[derive(CandidType, Deserialize)]
+                struct V7GlobalSettings {
+                    forward_window_months: u8,
+                    claims_paused: bool,
+                    default_event_duration_minutes: u32,
+                    org_name: Option<String>,
+                    org_tagline: Option<String>,
+                    org_logo_url: Option<String>,
+                    max_concurrent_sessions: Option<u32>,
+                    enforce_concurrency_cap: bool,
+                    validate_one_off_host: bool,
+                    require_link: bool,
+                    auto_cancel_past_unclaimed: bool,
+                    auto_cancel_grace_hours: u32,
+                    business_hours: Option<(u32, u32)>,
+                }
+                if let Ok(v7) = Decode!(bytes.as_ref(), V7GlobalSettings) {
+                    return GlobalSettings {
+                        forward_window_months: v7.forward_window_months,
+                        claims_paused: v7.claims_paused,
+                        default_event_duration_minutes: v7.default_event_duration_minutes,
+                        org_name: v7.org_name,
+                        org_tagline: v7.org_tagline,
+                        org_logo_url: v7.org_logo_url,
+                        max_concurrent_sessions: v7.max_concurrent_sessions,
+                        enforce_concurrency_cap: v7.enforce_concurrency_cap,
+                        validate_one_off_host: v7.validate_one_off_host,
+                        require_link: v7.require_link,
+                        auto_cancel_past_unclaimed: v7.auto_cancel_past_unclaimed,
+                        auto_cancel_grace_hours: v7.auto_cancel_grace_hours,
+                        business_hours: v7.business_hours,
+                        min_duration_minutes: None,
+                        max_duration_minutes: None,
+                        namespaced_instance_ids: false,
+                        notify_admins_on_join: true,
+                        org_default_notification_settings: None,
+                        week_starts_on: Weekday::Mon,
+                        version: 0,
+                        coverage_alert_threshold_pct: None,
+                        min_claim_lead_minutes: None,
+                        always_email_critical_removals: false,
+                        org_timezone: None,
+                        notification_templates: vec![],
+                    };
+                }
+                // V6: has auto-cancel settings but no business hours
+                #[derive(CandidType, Deserialize)]
+                struct V6GlobalSettings {
+                    forward_window_months: u8,
+                    claims_paused: bool,
+                    default_event_duration_minutes: u32,
+                    org_name: Option<String>,
+                    org_tagline: Option<String>,
+                    org_logo_url: Option<String>,
+                    max_concurrent_sessions: Option<u32>,
+                    enforce_concurrency_cap: bool,
+                    validate_one_off_host: bool,
+                    require_link: bool,
+                    auto_cancel_past_unclaimed: bool,
+                    auto_cancel_grace_hours: u32,
+                }
+                if let Ok(v6) = Decode!(bytes.as_ref(), V6GlobalSettings) {
+                    return GlobalSettings {
+                        forward_window_months: v6.forward_window_months,
+                        claims_paused: v6.claims_paused,
+                        default_event_duration_minutes: v6.default_event_duration_minutes,
+                        org_name: v6.org_name,
+                        org_tagline: v6.org_tagline,
+                        org_logo_url: v6.org_logo_url,
+                        max_concurrent_sessions: v6.max_concurrent_sessions,
+                        enforce_concurrency_cap: v6.enforce_concurrency_cap,
+                        validate_one_off_host: v6.validate_one_off_host,
+                        require_link: v6.require_link,
+                        auto_cancel_past_unclaimed: v6.auto_cancel_past_unclaimed,
+                        auto_cancel_grace_hours: v6.auto_cancel_grace_hours,
+                        business_hours: None,
+                        min_duration_minutes: None,
+                        max_duration_minutes: None,
+                        namespaced_instance_ids: false,
+                        notify_admins_on_join: true,
+                        org_default_notification_settings: None,
+                        week_starts_on: Weekday::Mon,
+                        version: 0,
+                        coverage_alert_threshold_pct: None,
+                        min_claim_lead_minutes: None,
+                        always_email_critical_removals: false,
+                        org_timezone: None,
+                        notification_templates: vec![],
+                    };
+                }
+                // V5: has require_link but no auto-cancel settings
+                #[derive(CandidType, Deserialize)]
+                struct V5GlobalSettings {
+                    forward_window_months: u8,
+                    claims_paused: bool,
+                    default_event_duration_minutes: u32,
+                    org_name: Option<String>,
+                    org_tagline: Option<String>,
+                    org_logo_url: Option<String>,
+                    max_concurrent_sessions: Option<u32>,
+                    enforce_concurrency_cap: bool,
+                    validate_one_off_host: bool,
+                    require_link: bool,
+                }
+                if let Ok(v5) = Decode!(bytes.as_ref(), V5GlobalSettings) {
+                    return GlobalSettings {
+                        forward_window_months: v5.forward_window_months,
+                        claims_paused: v5.claims_paused,
+                        default_event_duration_minutes: v5.default_event_duration_minutes,
+                        org_name: v5.org_name,
+                        org_tagline: v5.org_tagline,
+                        org_logo_url: v5.org_logo_url,
+                        max_concurrent_sessions: v5.max_concurrent_sessions,
+                        enforce_concurrency_cap: v5.enforce_concurrency_cap,
+                        validate_one_off_host: v5.validate_one_off_host,
+                        require_link: v5.require_link,
+                        auto_cancel_past_unclaimed: false,
+                        auto_cancel_grace_hours: 24,
+                        business_hours: None,
+                        min_duration_minutes: None,
+                        max_duration_minutes: None,
+                        namespaced_instance_ids: false,
+                        notify_admins_on_join: true,
+                        org_default_notification_settings: None,
+                        week_starts_on: Weekday::Mon,
+                        version: 0,
+                        coverage_alert_threshold_pct: None,
+                        min_claim_lead_minutes: None,
+                        always_email_critical_removals: false,
+                        org_timezone: None,
+                        notification_templates: vec![],
+                    };
+                }
+                // V4: has one-off host validation but no require_link
+                #[derive(CandidType, Deserialize)]
+                struct V4GlobalSettings {
+                    forward_window_months: u8,
+                    claims_paused: bool,
+                    default_event_duration_minutes: u32,
+                    org_name: Option<String>,
+                    org_tagline: Option<String>,
+                    org_logo_url: Option<String>,
+                    max_concurrent_sessions: Option<u32>,
+                    enforce_concurrency_cap: bool,
+                    validate_one_off_host: bool,
+                }
+                if let Ok(v4) = Decode!(bytes.as_ref(), V4GlobalSettings) {
+                    return GlobalSettings {
+                        forward_window_months: v4.forward_window_months,
+                        claims_paused: v4.claims_paused,
+                        default_event_duration_minutes: v4.default_event_duration_minutes,
+                        org_name: v4.org_name,
+                        org_tagline: v4.org_tagline,
+                        org_logo_url: v4.org_logo_url,
+                        max_concurrent_sessions: v4.max_concurrent_sessions,
+                        enforce_concurrency_cap: v4.enforce_concurrency_cap,
+                        validate_one_off_host: v4.validate_one_off_host,
+                        require_link: false,
+                        auto_cancel_past_unclaimed: false,
+                        auto_cancel_grace_hours: 24,
+                        business_hours: None,
+                        min_duration_minutes: None,
+                        max_duration_minutes: None,
+                        namespaced_instance_ids: false,
+                        notify_admins_on_join: true,
+                        org_default_notification_settings: None,
+                        week_starts_on: Weekday::Mon,
+                        version: 0,
+                        coverage_alert_threshold_pct: None,
+                        min_claim_lead_minutes: None,
+                        always_email_critical_removals: false,
+                        org_timezone: None,
+                        notification_templates: vec![],
+                    };
+                }
+                // V3: has concurrency cap but no one-off host validation flag
+                #[derive(CandidType, Deserialize)]
+                struct V3GlobalSettings {
+                    forward_window_months: u8,
+                    claims_paused: bool,
+                    default_event_duration_minutes: u32,
+                    org_name: Option<String>,
+                    org_tagline: Option<String>,
+                    org_logo_url: Option<String>,
+                    max_concurrent_sessions: Option<u32>,
+                    enforce_concurrency_cap: bool,
+                }
+                if let Ok(v3) = Decode!(bytes.as_ref(), V3GlobalSettings) {
+                    return GlobalSettings {
+                        forward_window_months: v3.forward_window_months,
+                        claims_paused: v3.claims_paused,
+                        default_event_duration_minutes: v3.default_event_duration_minutes,
+                        org_name: v3.org_name,
+                        org_tagline: v3.org_tagline,
+                        org_logo_url: v3.org_logo_url,
+                        max_concurrent_sessions: v3.max_concurrent_sessions,
+                        enforce_concurrency_cap: v3.enforce_concurrency_cap,
+                        validate_one_off_host: true,
+                        require_link: false,
+                        auto_cancel_past_unclaimed: false,
+                        auto_cancel_grace_hours: 24,
+                        business_hours: None,
+                        min_duration_minutes: None,
+                        max_duration_minutes: None,
+                        namespaced_instance_ids: false,
+                        notify_admins_on_join: true,
+                        org_default_notification_settings: None,
+                        week_starts_on: Weekday::Mon,
+                        version: 0,
+                        coverage_alert_threshold_pct: None,
+                        min_claim_lead_minutes: None,
+                        always_email_critical_removals: false,
+                        org_timezone: None,
+                        notification_templates: vec![],
+                    };
+                }
+                // V2: has org branding but no concurrency cap
+                #[derive(CandidType, Deserialize)]
+                struct V2GlobalSettings {
+                    forward_window_months: u8,
+                    claims_paused: bool,
+                    default_event_duration_minutes: u32,
+                    org_name: Option<String>,
+                    org_tagline: Option<String>,
+                    org_logo_url: Option<String>,
+                }
+                if let Ok(v2) = Decode!(bytes.as_ref(), V2GlobalSettings) {
+                    return GlobalSettings {
+                        forward_window_months: v2.forward_window_months,
+                        claims_paused: v2.claims_paused,
+                        default_event_duration_minutes: v2.default_event_duration_minutes,
+                        org_name: v2.org_name,
+                        org_tagline: v2.org_tagline,
+                        org_logo_url: v2.org_logo_url,
+                        max_concurrent_sessions: None,
+                        enforce_concurrency_cap: false,
+                        validate_one_off_host: true,
+                        require_link: false,
+                        auto_cancel_past_unclaimed: false,
+                        auto_cancel_grace_hours: 24,
+                        business_hours: None,
+                        min_duration_minutes: None,
+                        max_duration_minutes: None,
+                        namespaced_instance_ids: false,
+                        notify_admins_on_join: true,
+                        org_default_notification_settings: None,
+                        week_starts_on: Weekday::Mon,
+                        version: 0,
+                        coverage_alert_threshold_pct: None,
+                        min_claim_lead_minutes: None,
+                        always_email_critical_removals: false,
+                        org_timezone: None,
+                        notification_templates: vec![],
+                    };
+                }
+                // V1: no org branding
                 #[derive(CandidType, Deserialize)]
                 struct OldGlobalSettings {
                     forward_window_months: u8,
@@ -703,6 +2986,25 @@ impl Storable for GlobalSettings {
                     org_name: None,
                     org_tagline: None,
                     org_logo_url: None,
+                    max_concurrent_sessions: None,
+                    enforce_concurrency_cap: false,
+                    validate_one_off_host: true,
+                    require_link: false,
+                    auto_cancel_past_unclaimed: false,
+                    auto_cancel_grace_hours: 24,
+                    business_hours: None,
+                    min_duration_minutes: None,
+                    max_duration_minutes: None,
+                    namespaced_instance_ids: false,
+                    notify_admins_on_join: true,
+                    org_default_notification_settings: None,
+                    week_starts_on: Weekday::Mon,
+                    version: 0,
+                    coverage_alert_threshold_pct: None,
+                    min_claim_lead_minutes: None,
+                    always_email_critical_removals: false,
+                    org_timezone: None,
+                    notification_templates: vec![],
                 }
             }
         }
@@ -717,14 +3019,14 @@ impl Storable for GlobalSettings {
 
 // OverrideKey needs special handling for BTreeMap key
 impl Storable for OverrideKey {
-    fn to_bytes(&self) -> Cow<[u8]> {
+    fn to_bytes(&self) -> Cow<'_, [u8]> {
         let mut bytes = Vec::with_capacity(24);
         bytes.extend_from_slice(&self.series_id);
         bytes.extend_from_slice(&self.occurrence_start_utc.to_be_bytes());
         Cow::Owned(bytes)
     }
 
-    fn from_bytes(bytes: Cow<[u8]>) -> Self {
+    fn from_bytes(bytes: Cow<'_, [u8]>) -> Self {
         let mut series_id = [0u8; 16];
         series_id.copy_from_slice(&bytes[0..16]);
         let occurrence_start_utc = u64::from_be_bytes(bytes[16..24].try_into().unwrap());
@@ -755,11 +3057,11 @@ impl Uuid {
 }
 
 impl Storable for Uuid {
-    fn to_bytes(&self) -> Cow<[u8]> {
+    fn to_bytes(&self) -> Cow<'_, [u8]> {
         Cow::Borrowed(&self.0)
     }
 
-    fn from_bytes(bytes: Cow<[u8]>) -> Self {
+    fn from_bytes(bytes: Cow<'_, [u8]>) -> Self {
         let mut arr = [0u8; 16];
         arr.copy_from_slice(&bytes);
         Self(arr)
@@ -0,0 +1,630 @@
+//! Input validation shared by the event and series endpoints
+//!
+//! Validators return `ApiResult<()>` so endpoints can bail out with `?`.
+
+use crate::auth;
+use crate::datetime;
+use crate::notifications;
+use crate::recurrence;
+use crate::storage;
+use crate::types::*;
+use candid::Principal;
+
+/// Maximum lengths (in characters) for user-supplied strings.
+/// Kept well inside the Storable bounds in types.rs so oversized input is
+/// rejected here instead of trapping on insert.
+pub const MAX_TITLE_LEN: usize = 200;
+pub const MAX_NOTES_LEN: usize = 2000;
+pub const MAX_LINK_LEN: usize = 500;
+pub const MAX_NAME_LEN: usize = 100;
+pub const MAX_EMAIL_LEN: usize = 254;
+pub const MAX_OOO_REASON_LEN: usize = 200;
+pub const MAX_LOCALE_LEN: usize = 35;
+
+/// Maximum number of entries in a user's schedule lists, for the same reason
+pub const MAX_OOO_BLOCKS: usize = 20;
+pub const MAX_AVAILABILITY_BLOCKS: usize = 50;
+
+/// Combined UTF-8 size of all notification template text, in bytes
+/// Per-field character limits alone would let multi-byte text push the
+/// settings past their 32 KiB Storable bound.
+pub const MAX_TEMPLATE_BYTES: usize = 16 * 1024;
+
+/// How far from now (either way) a series may start, in years
+pub const MAX_SERIES_START_OFFSET_YEARS: u64 = 5;
+
+/// Peak number of existing sessions running at the same time within [start, end)
+pub fn peak_concurrency(start_utc: u64, end_utc: u64) -> u32 {
+    // Look back a day so sessions that started earlier but still overlap are included
+    let lookback = 86400 * 1_000_000_000;
+    let events = recurrence::materialize_events(start_utc.saturating_sub(lookback), end_utc);
+
+    // Sweep over the overlapping sessions, clipped to the slot
+    let mut edges: Vec<(u64, i32)> = Vec::new();
+    for e in events.iter().filter(|e| e.start_utc < end_utc && e.end_utc > start_utc) {
+        edges.push((e.start_utc.max(start_utc), 1));
+        edges.push((e.end_utc.min(end_utc), -1));
+    }
+    // Ends sort before starts at the same instant so back-to-back sessions don't overlap
+    edges.sort();
+
+    let mut current = 0i32;
+    let mut peak = 0i32;
+    for (_, delta) in edges {
+        current += delta;
+        peak = peak.max(current);
+    }
+    peak as u32
+}
+
+/// Check that adding sessions in `slots` keeps every slot within the
+/// configured concurrency cap. Depending on settings this either rejects or
+/// returns the overruns as warnings for the caller to pass on.
+pub fn check_concurrency_cap(settings: &GlobalSettings, slots: &[(u64, u64)]) -> ApiResult<Vec<String>> {
+    let cap = match settings.max_concurrent_sessions {
+        Some(cap) => cap,
+        None => return Ok(vec![]),
+    };
+    let mut warnings = Vec::new();
+
+    for &(start, end) in slots {
+        let existing = peak_concurrency(start, end);
+        if existing + 1 > cap {
+            let msg = format!(
+                "Session would exceed the limit of {} concurrent sessions ({} already scheduled in this slot)",
+                cap, existing
+            );
+            if settings.enforce_concurrency_cap {
+                return Err(ApiError::Conflict(msg));
+            }
+            warnings.push(msg);
+        }
+    }
+
+    Ok(warnings)
+}
+
+/// Check that a prospective host is a known, active user who is free
+/// (not out-of-office) for the whole event window
+pub fn validate_host(host: &Principal, start_utc: u64, end_utc: u64) -> ApiResult<()> {
+    let user = storage::get_user(host)
+        .ok_or(ApiError::InvalidInput("Host is not a known user".to_string()))?;
+
+    if user.status != UserStatus::Active {
+        return Err(ApiError::Conflict("Host is disabled".to_string()));
+    }
+    if !auth::can_be_assigned_host(&user, start_utc, end_utc) {
+        return Err(ApiError::Conflict(
+            "Host cannot be assigned (out-of-office during this session)".to_string()
+        ));
+    }
+
+    Ok(())
+}
+
+/// Validate out-of-office blocks and merge any that overlap or touch
+/// Blocks are half-open `[start_utc, end_utc)`; the result is sorted by start.
+/// A merged block keeps the earliest block's reason, if it had one.
+pub fn normalize_ooo_blocks(mut blocks: Vec<OOOBlock>) -> ApiResult<Vec<OOOBlock>> {
+    if blocks.len() > MAX_OOO_BLOCKS {
+        return Err(ApiError::InvalidInput(format!(
+            "At most {} out-of-office blocks are allowed", MAX_OOO_BLOCKS
+        )));
+    }
+    for block in &mut blocks {
+        if block.start_utc >= block.end_utc {
+            return Err(ApiError::InvalidInput(
+                "Out-of-office block must end after it starts".to_string()
+            ));
+        }
+        if let Some(reason) = &block.reason {
+            check_length("reason", reason, MAX_OOO_REASON_LEN)?;
+        }
+        block.reason = block.reason.take().filter(|r| !r.trim().is_empty());
+    }
+    
+    blocks.sort_by_key(|b| b.start_utc);
+    let mut merged: Vec<OOOBlock> = Vec::with_capacity(blocks.len());
+    for block in blocks {
+        match merged.last_mut() {
+            Some(last) if block.start_utc <= last.end_utc => {
+                last.end_utc = last.end_utc.max(block.end_utc);
+                if last.reason.is_none() {
+                    last.reason = block.reason;
+                }
+            }
+            _ => merged.push(block),
+        }
+    }
+    
+    Ok(merged)
+}
+
+/// Check a `business_hours` setting: both ends within the day and not equal
+pub fn validate_business_hours(hours: Option<(u32, u32)>) -> ApiResult<()> {
+    if let Some((open, close)) = hours {
+        if open >= 24 * 60 || close >= 24 * 60 || open == close {
+            return Err(ApiError::InvalidInput(
+                "Business hours must be two different minutes of the day (0-1439)".to_string()
+            ));
+        }
+    }
+    Ok(())
+}
+
+/// Reject a session starting outside the configured business hours
+/// The window may wrap past midnight (close < open).
+pub fn check_business_hours(settings: &GlobalSettings, start_utc: u64) -> ApiResult<()> {
+    let (open, close) = match settings.business_hours {
+        Some(hours) => hours,
+        None => return Ok(()),
+    };
+    let minute = ((start_utc / (60 * 1_000_000_000)) % (24 * 60)) as u32;
+    let inside = if open < close {
+        minute >= open && minute < close
+    } else {
+        minute >= open || minute < close
+    };
+    
+    if !inside {
+        return Err(ApiError::InvalidInput(format!(
+            "Sessions must start between {:02}:{:02} and {:02}:{:02} UTC",
+            open / 60, open % 60, close / 60, close % 60
+        )));
+    }
+    Ok(())
+}
+
+/// Check the duration limits settings are consistent
+pub fn validate_duration_limits(settings: &GlobalSettings) -> ApiResult<()> {
+    if settings.min_duration_minutes == Some(0) {
+        return Err(ApiError::InvalidInput("Minimum duration must be at least 1 minute".to_string()));
+    }
+    if let (Some(min), Some(max)) = (settings.min_duration_minutes, settings.max_duration_minutes) {
+        if min > max {
+            return Err(ApiError::InvalidInput("Minimum duration cannot exceed maximum duration".to_string()));
+        }
+    }
+    Ok(())
+}
+
+/// Check a coverage alert threshold is a percentage
+pub fn validate_coverage_alert_threshold(threshold: Option<f64>) -> ApiResult<()> {
+    if let Some(pct) = threshold {
+        if !(0.0..=100.0).contains(&pct) {
+            return Err(ApiError::InvalidInput("Coverage alert threshold must be between 0 and 100".to_string()));
+        }
+    }
+    Ok(())
+}
+
+/// Monthly series need a weekday_ordinal; weekly and biweekly ones must not
+/// have one, since it would be silently ignored
+pub fn check_weekday_ordinal(frequency: &Frequency, ordinal: &Option<WeekdayOrdinal>) -> ApiResult<()> {
+    match (frequency, ordinal) {
+        (Frequency::Monthly, None) => Err(ApiError::InvalidInput(
+            "Monthly frequency requires weekday_ordinal".to_string()
+        )),
+        (Frequency::Weekly | Frequency::Biweekly, Some(_)) => Err(ApiError::InvalidInput(
+            "weekday_ordinal is only allowed for monthly series".to_string()
+        )),
+        _ => Ok(()),
+    }
+}
+
+/// Check a series' date range: it must start within MAX_SERIES_START_OFFSET_YEARS
+/// of now, and end (if it ends) no earlier than it starts
+pub fn check_series_dates(start_date: u64, end_date: Option<u64>, now: u64) -> ApiResult<()> {
+    let max_offset = MAX_SERIES_START_OFFSET_YEARS * 366 * 86400 * 1_000_000_000;
+    if start_date < now.saturating_sub(max_offset) || start_date > now.saturating_add(max_offset) {
+        return Err(ApiError::InvalidInput(format!(
+            "start_date must be within {} years of today", MAX_SERIES_START_OFFSET_YEARS
+        )));
+    }
+    if end_date.is_some_and(|end| end < start_date) {
+        return Err(ApiError::InvalidInput("end_date cannot be before start_date".to_string()));
+    }
+    Ok(())
+}
+
+/// Trim a locale tag (e.g. "en" or "pt-BR"); blank clears it
+/// Only the shape is checked: unknown languages fall back to English when rendering.
+pub fn normalize_locale(locale: Option<String>) -> ApiResult<Option<String>> {
+    let Some(tag) = locale.map(|l| l.trim().to_string()).filter(|l| !l.is_empty()) else {
+        return Ok(None);
+    };
+    check_length("locale", &tag, MAX_LOCALE_LEN)?;
+    let well_formed = tag.split('-').all(|part| {
+        (1..=8).contains(&part.len()) && part.chars().all(|c| c.is_ascii_alphanumeric())
+    });
+    if !well_formed {
+        return Err(ApiError::InvalidInput(format!("Invalid locale: {}", tag)));
+    }
+    Ok(Some(tag))
+}
+
+/// Check notification templates: one per templated type, within length
+/// limits, and using only notifications::TEMPLATE_PLACEHOLDERS
+pub fn validate_notification_templates(templates: &[NotificationTemplate]) -> ApiResult<()> {
+    for (i, template) in templates.iter().enumerate() {
+        if !notifications::TEMPLATED_TYPES.contains(&template.notification_type) {
+            return Err(ApiError::InvalidInput(format!(
+                "{:?} notifications can't be templated", template.notification_type
+            )));
+        }
+        if templates[..i].iter().any(|t| t.notification_type == template.notification_type) {
+            return Err(ApiError::InvalidInput(format!(
+                "Duplicate template for {:?}", template.notification_type
+            )));
+        }
+        if let Some(subject) = &template.subject {
+            check_length("template subject", subject, MAX_TITLE_LEN)?;
+            check_placeholders(subject)?;
+        }
+        if let Some(body) = &template.body {
+            check_length("template body", body, MAX_NOTES_LEN)?;
+            check_placeholders(body)?;
+        }
+    }
+    let total_bytes: usize = templates
+        .iter()
+        .flat_map(|t| [t.subject.as_deref(), t.body.as_deref()])
+        .flatten()
+        .map(str::len)
+        .sum();
+    if total_bytes > MAX_TEMPLATE_BYTES {
+        return Err(ApiError::InvalidInput(format!(
+            "Notification templates total {} bytes; the limit is {}", total_bytes, MAX_TEMPLATE_BYTES
+        )));
+    }
+    Ok(())
+}
+
+fn check_placeholders(template: &str) -> ApiResult<()> {
+    let mut rest = template;
+    while let Some(open) = rest.find('{') {
+        let after = &rest[open + 1..];
+        let close = after.find('}').ok_or_else(|| ApiError::InvalidInput(
+            "Template has an unclosed '{'".to_string()
+        ))?;
+        let name = &after[..close];
+        if !notifications::TEMPLATE_PLACEHOLDERS.contains(&name) {
+            return Err(ApiError::InvalidInput(format!(
+                "Unknown template placeholder {{{}}}; use {}",
+                name,
+                notifications::TEMPLATE_PLACEHOLDERS.iter().map(|p| format!("{{{}}}", p)).collect::<Vec<_>>().join(", ")
+            )));
+        }
+        rest = &after[close + 1..];
+    }
+    Ok(())
+}
+
+/// Check an org timezone is one `datetime` has offsets for
+pub fn validate_timezone(timezone: &Option<String>) -> ApiResult<()> {
+    if let Some(tz) = timezone {
+        if !datetime::is_known_timezone(tz) {
+            return Err(ApiError::InvalidInput(format!("Unknown timezone: {}", tz)));
+        }
+    }
+    Ok(())
+}
+
+/// Reject a session length outside the configured min/max duration
+pub fn check_duration(settings: &GlobalSettings, minutes: u64) -> ApiResult<()> {
+    if let Some(min) = settings.min_duration_minutes {
+        if minutes < min as u64 {
+            return Err(ApiError::InvalidInput(format!(
+                "Sessions must be at least {} minutes long ({} given)", min, minutes
+            )));
+        }
+    }
+    if let Some(max) = settings.max_duration_minutes {
+        if minutes > max as u64 {
+            return Err(ApiError::InvalidInput(format!(
+                "Sessions can be at most {} minutes long ({} given)", max, minutes
+            )));
+        }
+    }
+    Ok(())
+}
+
+/// Whether a link is present and non-blank
+pub fn has_link(link: &Option<String>) -> bool {
+    link.as_deref().map(|l| !l.trim().is_empty()).unwrap_or(false)
+}
+
+/// Enforce `require_link` on a new session or series
+pub fn check_required_link(settings: &GlobalSettings, link: &Option<String>) -> ApiResult<()> {
+    if settings.require_link && !has_link(link) {
+        return Err(ApiError::InvalidInput("A meeting link is required".to_string()));
+    }
+    Ok(())
+}
+
+/// Validate an optional `#RRGGBB` color and normalize it to lowercase
+pub fn normalize_color(color: Option<String>) -> ApiResult<Option<String>> {
+    let color = match color {
+        Some(c) => c,
+        None => return Ok(None),
+    };
+    
+    let valid = color.len() == 7
+        && color.starts_with('#')
+        && color[1..].chars().all(|c| c.is_ascii_hexdigit());
+    if !valid {
+        return Err(ApiError::InvalidInput(format!(
+            "Invalid color '{}': expected #RRGGBB", color
+        )));
+    }
+    
+    Ok(Some(color.to_ascii_lowercase()))
+}
+
+/// Validate an email address and return it normalized
+/// Structural check only: one `@`, non-empty local part, a dotted domain, no
+/// whitespace. Surrounding whitespace is trimmed and the domain lowercased.
+pub fn validate_email(email: &str) -> ApiResult<String> {
+    let email = email.trim();
+    check_length("email", email, MAX_EMAIL_LEN)?;
+    let invalid = || ApiError::InvalidInput(format!("email: '{}' is not a valid email address", email));
+    
+    if email.chars().any(char::is_whitespace) {
+        return Err(invalid());
+    }
+    let (local, domain) = email.split_once('@').ok_or_else(invalid)?;
+    if local.is_empty() || domain.contains('@') {
+        return Err(invalid());
+    }
+    let domain_ok = domain.contains('.')
+        && domain.split('.').all(|label| !label.is_empty());
+    if !domain_ok {
+        return Err(invalid());
+    }
+    
+    Ok(format!("{}@{}", local, domain.to_ascii_lowercase()))
+}
+
+/// Like `validate_email`, but a blank email is allowed and stored as empty
+/// (admins may add users before their email is known)
+pub fn validate_optional_email(email: &str) -> ApiResult<String> {
+    if email.trim().is_empty() {
+        return Ok(String::new());
+    }
+    validate_email(email)
+}
+
+/// Validate an optional meeting link: http(s) scheme, non-empty host, no whitespace
+/// A blank link is treated as no link. Other schemes (`javascript:`, `data:`,
+/// ...) are rejected since links are rendered as clickable anchors.
+pub fn validate_link(link: Option<String>) -> ApiResult<Option<String>> {
+    let link = match link.as_deref().map(str::trim) {
+        Some(l) if !l.is_empty() => l.to_string(),
+        _ => return Ok(None),
+    };
+    check_length("link", &link, MAX_LINK_LEN)?;
+    let invalid = || ApiError::InvalidInput(format!("link: '{}' is not a valid http(s) URL", link));
+    
+    let lower = link.to_ascii_lowercase();
+    let rest = lower.strip_prefix("https://")
+        .or_else(|| lower.strip_prefix("http://"))
+        .ok_or_else(invalid)?;
+    let host = rest.split(['/', '?', '#']).next().unwrap_or("");
+    if host.is_empty() || host.starts_with(':') || link.chars().any(|c| c.is_whitespace() || c.is_control()) {
+        return Err(invalid());
+    }
+    
+    Ok(Some(link))
+}
+
+/// Reject `value` if it is longer than `max` characters
+pub fn check_length(field: &str, value: &str, max: usize) -> ApiResult<()> {
+    if value.chars().count() > max {
+        return Err(ApiError::InvalidInput(format!(
+            "{} must be at most {} characters", field, max
+        )));
+    }
+    Ok(())
+}
+
+/// How far ahead to sample a new series for collisions with existing ones;
+/// long enough to include a few occurrences of a monthly series
+const SERIES_COLLISION_SAMPLE_DAYS: u64 = 90;
+
+/// Reject a new series whose occurrences start at the same instant as an
+/// existing series' occurrences over a short sample window. The error names
+/// the first colliding series.
+pub fn check_series_collision(series: &EventSeries, now: u64) -> ApiResult<()> {
+    let sample_start = now.max(series.start_date);
+    let sample_end = sample_start + SERIES_COLLISION_SAMPLE_DAYS * 86400 * 1_000_000_000;
+    
+    let occurrences = recurrence::generate_occurrences(series, sample_start, sample_end);
+    if occurrences.is_empty() {
+        return Ok(());
+    }
+    
+    let existing = recurrence::materialize_events(sample_start, sample_end);
+    for occ in occurrences {
+        let collision = existing.iter()
+            .find(|e| e.start_utc == occ && e.series_id.is_some() && e.series_id != Some(series.series_id));
+        if let Some(other) = collision.and_then(|e| e.series_id) {
+            return Err(ApiError::Conflict(format!(
+                "Series collides with existing series {} at {}; pass force to create it anyway",
+                hex::encode(other),
+                datetime::format_iso8601(occ),
+            )));
+        }
+    }
+    
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const HOUR: u64 = 3600 * 1_000_000_000;
+
+    fn one_off(tag: u8, start_utc: u64, end_utc: u64) -> EventInstance {
+        EventInstance {
+            instance_id: [tag; 16],
+            series_id: None,
+            start_utc,
+            end_utc,
+            title: format!("Session {}", tag),
+            notes: String::new(),
+            link: None,
+            host_principal: None,
+            status: EventStatus::Active,
+            color: None,
+            created_at: 0,
+            occurrence_start: None,
+            assigned_by: None,
+            assigned_at: None,
+            created_by: None,
+            public: false,
+            capacity: None,
+            rsvp_count: 0,
+            overridden_fields: vec![],
+            series_title: None,
+        }
+    }
+
+    fn capped_settings(cap: u32, enforce: bool) -> GlobalSettings {
+        GlobalSettings {
+            max_concurrent_sessions: Some(cap),
+            enforce_concurrency_cap: enforce,
+            namespaced_instance_ids: false, // the canister id isn't available off-chain
+            ..Default::default()
+        }
+    }
+
+    fn member(id: u8, status: UserStatus, out_of_office: Vec<OOOBlock>) -> User {
+        User {
+            principal: Principal::from_slice(&[id]),
+            name: format!("Member {}", id),
+            email: format!("member{}@example.com", id),
+            role: Role::User,
+            status,
+            out_of_office,
+            availability: vec![],
+            notification_settings: auth::default_notification_settings(),
+            last_active: 0,
+            sessions_hosted_count: 0,
+            created_at: 0,
+            updated_at: 0,
+            locale: None,
+        }
+    }
+
+    #[test]
+    fn one_off_host_must_be_an_active_available_user() {
+        let start = 1_700_000_000 * 1_000_000_000;
+        let end = start + HOUR;
+        storage::insert_user(member(1, UserStatus::Active, vec![]));
+        storage::insert_user(member(2, UserStatus::Disabled, vec![]));
+        storage::insert_user(member(3, UserStatus::Active, vec![OOOBlock { start_utc: start - HOUR, end_utc: end, reason: None }]));
+
+        assert!(validate_host(&Principal::from_slice(&[1]), start, end).is_ok());
+        assert!(matches!(validate_host(&Principal::from_slice(&[2]), start, end), Err(ApiError::Conflict(_))));
+        assert!(matches!(validate_host(&Principal::from_slice(&[3]), start, end), Err(ApiError::Conflict(_))));
+        assert!(matches!(validate_host(&Principal::from_slice(&[9]), start, end), Err(ApiError::InvalidInput(_))));
+    }
+
+    #[test]
+    fn required_link_rejects_missing_and_blank_links() {
+        let strict = GlobalSettings { require_link: true, ..Default::default() };
+        let lax = GlobalSettings::default();
+        let zoom = Some("https://zoom.us/j/1".to_string());
+
+        assert!(check_required_link(&strict, &zoom).is_ok());
+        assert!(matches!(check_required_link(&strict, &None), Err(ApiError::InvalidInput(_))));
+        assert!(check_required_link(&strict, &Some("   ".to_string())).is_err());
+        assert!(check_required_link(&lax, &None).is_ok());
+    }
+
+    #[test]
+    fn length_limits_count_characters_not_bytes() {
+        assert!(check_length("title", &"a".repeat(MAX_TITLE_LEN), MAX_TITLE_LEN).is_ok());
+        assert!(matches!(
+            check_length("title", &"a".repeat(MAX_TITLE_LEN + 1), MAX_TITLE_LEN),
+            Err(ApiError::InvalidInput(msg)) if msg == "title must be at most 200 characters"
+        ));
+        // 200 three-byte characters are still 200 characters
+        assert!(check_length("title", &"語".repeat(MAX_TITLE_LEN), MAX_TITLE_LEN).is_ok());
+    }
+
+    fn ooo(start_utc: u64, end_utc: u64, reason: Option<&str>) -> OOOBlock {
+        OOOBlock { start_utc, end_utc, reason: reason.map(str::to_string) }
+    }
+
+    #[test]
+    fn inverted_or_empty_ooo_blocks_are_rejected() {
+        assert!(matches!(normalize_ooo_blocks(vec![ooo(20, 10, None)]), Err(ApiError::InvalidInput(_))));
+        assert!(normalize_ooo_blocks(vec![ooo(10, 10, None)]).is_err());
+    }
+
+    #[test]
+    fn overlapping_and_touching_ooo_blocks_merge() {
+        let merged = normalize_ooo_blocks(vec![
+            ooo(50, 60, Some("conference")),
+            ooo(10, 30, None),
+            ooo(20, 40, Some("travel")),
+            ooo(40, 45, None), // touches the end of the block before it
+        ]).unwrap();
+
+        let spans: Vec<(u64, u64, Option<&str>)> = merged.iter()
+            .map(|b| (b.start_utc, b.end_utc, b.reason.as_deref()))
+            .collect();
+        assert_eq!(spans, vec![(10, 45, Some("travel")), (50, 60, Some("conference"))]);
+    }
+
+    #[test]
+    fn series_must_not_end_before_it_starts() {
+        let now = 1_700_000_000 * 1_000_000_000;
+        let start = now + 24 * HOUR;
+        assert!(check_series_dates(start, None, now).is_ok());
+        assert!(check_series_dates(start, Some(start), now).is_ok());
+        assert!(matches!(check_series_dates(start, Some(start - HOUR), now), Err(ApiError::InvalidInput(_))));
+    }
+
+    #[test]
+    fn series_start_must_be_within_five_years() {
+        let now = 1_700_000_000 * 1_000_000_000;
+        let year = 365 * 24 * HOUR;
+        assert!(check_series_dates(now - 4 * year, None, now).is_ok());
+        assert!(check_series_dates(now - 6 * year, None, now).is_err());
+        assert!(check_series_dates(now + 6 * year, None, now).is_err());
+    }
+
+    #[test]
+    fn fourth_overlapping_session_exceeds_cap_of_three() {
+        let noon = 1_700_000_000 * 1_000_000_000;
+        for tag in 1..=3 {
+            storage::insert_instance(one_off(tag, noon, noon + HOUR));
+        }
+        let slot = [(noon + HOUR / 2, noon + 2 * HOUR)];
+
+        storage::update_settings(capped_settings(3, false));
+        let warnings = check_concurrency_cap(&storage::get_settings(), &slot).unwrap();
+        assert_eq!(warnings.len(), 1);
+
+        storage::update_settings(capped_settings(3, true));
+        assert!(matches!(
+            check_concurrency_cap(&storage::get_settings(), &slot),
+            Err(ApiError::Conflict(_))
+        ));
+
+        storage::update_settings(capped_settings(4, true));
+        assert_eq!(check_concurrency_cap(&storage::get_settings(), &slot).unwrap(), Vec::<String>::new());
+    }
+
+    #[test]
+    fn back_to_back_sessions_do_not_overlap() {
+        let noon = 1_700_000_000 * 1_000_000_000;
+        storage::update_settings(capped_settings(1, true));
+        storage::insert_instance(one_off(1, noon, noon + HOUR));
+
+        assert!(check_concurrency_cap(&storage::get_settings(), &[(noon + HOUR, noon + 2 * HOUR)]).is_ok());
+    }
+}
@@ -155,7 +155,7 @@ function UserManagement() {
     setError(null);
     try {
       const roleArg = role === 'Admin' ? { Admin: null } : { User: null };
-      const result = await actor.generate_invite_code(roleArg);
+      const result = await actor.generate_invite_code(roleArg, [], [], []);
       if ('Ok' in result) {
         setShowInviteCode({ code: result.Ok.code });
         fetchUsers(); // refresh invite codes list
@@ -745,6 +745,7 @@ function EventSeriesManagement() {
   const [showAddForm, setShowAddForm] = useState(false);
   const [editingSeries, setEditingSeries] = useState<EventSeries | null>(null);
   const [deletingId, setDeletingId] = useState<string | null>(null);
+  const [pauseNotice, setPauseNotice] = useState<string | null>(null);
 
   const fetchSeries = async () => {
     if (!actor) return;
@@ -774,7 +775,7 @@ function EventSeriesManagement() {
     if (!actor) return;
     const confirmed = await confirm({
       title: 'Delete Series',
-      message: `Delete series "${s.title}"? This will remove all future instances. This cannot be undone.`,
+      message: `Delete series "${s.title}"? This will remove all future instances. An admin can restore it later.`,
       confirmLabel: 'Delete',
       variant: 'danger',
     });
@@ -800,9 +801,15 @@ function EventSeriesManagement() {
   const handleTogglePause = async (s: any) => {
     if (!actor) return;
     try {
-      const result = await actor.toggle_series_pause(s.series_id);
-      if ('Ok' in result) fetchSeries();
-      else setError(getErrorMessage(result.Err));
+      const result = await actor.toggle_series_pause(s.series_id, []);
+      if ('Ok' in result) {
+        const { series: updated, affected_occurrences, hosted_occurrences } = result.Ok;
+        const sessions = `${affected_occurrences} upcoming session${affected_occurrences === 1 ? '' : 's'}`;
+        setPauseNotice(updated.paused
+          ? `Paused "${updated.title}": ${sessions} hidden, ${hosted_occurrences} of them hosted.`
+          : `Resumed "${updated.title}": ${sessions} restored, ${hosted_occurrences} of them hosted.`);
+        fetchSeries();
+      } else setError(getErrorMessage(result.Err));
     } catch (err) {
       if (isSessionExpiredError(err)) {
         triggerSessionExpired();
@@ -853,6 +860,7 @@ function EventSeriesManagement() {
         </button>
       </div>
       {error && <div style={styles.error}>{error}</div>}
+      {pauseNotice && <div style={styles.success}>{pauseNotice}</div>}
       {showAddForm && (
         <AddSeriesForm 
           actor={actor}
@@ -991,9 +999,16 @@ function AddSeriesForm({ actor, triggerSessionExpired, onSuccess, onCancel }: {
         color: color ? [color] : [],
         default_host: defaultHost ? [Principal.fromText(defaultHost)] : [],
       };
-      const result = await actor.create_event_series(input);
-      if ('Ok' in result) onSuccess();
-      else setError(getErrorMessage(result.Err));
+      let result = await actor.create_event_series(input, false);
+      if ('Err' in result && 'Conflict' in result.Err && result.Err.Conflict.includes('collides')) {
+        if (window.confirm(`${result.Err.Conflict.split(';')[0]}. Create it anyway?`)) {
+          result = await actor.create_event_series(input, true);
+        }
+      }
+      if ('Ok' in result) {
+        if (result.Ok.warnings.length > 0) window.alert(result.Ok.warnings.join('\n'));
+        onSuccess();
+      } else setError(getErrorMessage(result.Err));
     } catch (err: any) {
       if (isSessionExpiredError(err)) {
         triggerSessionExpired();
@@ -1167,8 +1182,11 @@ function SystemSettings() {
     setError(null);
     setSuccess(false);
     try {
-      const result = await actor.update_global_settings(settings);
-      if ('Ok' in result) { setSuccess(true); setTimeout(() => setSuccess(false), 3000); }
+      const result = await actor.update_global_settings(settings, settings.version);
+      if ('Ok' in result) {
+        setSettings({ ...settings, version: settings.version + BigInt(1) });
+        setSuccess(true); setTimeout(() => setSuccess(false), 3000);
+      }
       else setError(getErrorMessage(result.Err));
     } catch (err) {
       if (isSessionExpiredError(err)) {
@@ -1310,7 +1328,8 @@ function Reports() {
             const now = new Date();
             const start = dateToNanos(now);
             const end = dateToNanos(new Date(now.getTime() + 60 * 24 * 60 * 60 * 1000));
-            const result = await actor.export_events_csv(start, end);
+            // getTimezoneOffset is minutes behind UTC; the backend wants minutes ahead
+            const result = await actor.export_events_csv(start, end, -now.getTimezoneOffset());
             if ('Ok' in result) {
               const blob = new Blob([result.Ok], { type: 'text/csv' });
               const url = URL.createObjectURL(blob);
@@ -493,7 +493,7 @@ function EventDetailModal({ event, hostName, currentUser, actor, triggerSessionE
     try {
       const result = await actor.assign_host(
         event.series_id,
-        (event.series_id && event.series_id.length > 0) ? [event.start_utc] : [],
+        event.occurrence_start,
         event.instance_id,
         selectedUser.principal
       );
@@ -517,7 +517,7 @@ function EventDetailModal({ event, hostName, currentUser, actor, triggerSessionE
     try {
       const result = await actor.unassign_host(
         event.series_id,
-        (event.series_id && event.series_id.length > 0) ? [event.start_utc] : [],
+        event.occurrence_start,
         event.instance_id
       );
       if ('Ok' in result) onRefresh();
@@ -673,9 +673,11 @@ function CreateEventModal({ actor, triggerSessionExpired, onClose, onCreated }:
         start_utc: dateToNanos(startDateTime),
         end_utc: dateToNanos(endDateTime),
         host_principal: [],
-      });
-      if ('Ok' in result) onCreated();
-      else setError(getErrorMessage(result.Err));
+      }, false);
+      if ('Ok' in result) {
+        if (result.Ok.warnings.length > 0) window.alert(result.Ok.warnings.join('\n'));
+        onCreated();
+      } else setError(getErrorMessage(result.Err));
     } catch (err: any) {
       if (isSessionExpiredError(err)) {
         triggerSessionExpired();
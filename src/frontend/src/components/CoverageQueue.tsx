@@ -106,8 +106,8 @@ export default function CoverageQueue() {
     try {
       // series_id comes from Candid as opt blob: [] for None, [Uint8Array] for Some
       const seriesId = event.series_id;
-      // occurrence_start is opt nat64: pass [start_utc] if series event, [] if one-off
-      const occurrenceStart = (seriesId && seriesId.length > 0) ? [event.start_utc] : [];
+      // occurrence_start is opt nat64: set for series events, [] for one-offs
+      const occurrenceStart = event.occurrence_start;
       
       const result = await actor.assign_host(
         seriesId,
@@ -156,8 +156,7 @@ export default function CoverageQueue() {
     setAssigningId(eventKey);
     try {
       const seriesId = event.series_id;
-      const occurrenceStart = (seriesId && seriesId.length > 0) ? [event.start_utc] : [];
-      const result = await actor.assign_host(seriesId, occurrenceStart, event.instance_id, user.principal);
+      const result = await actor.assign_host(seriesId, event.occurrence_start, event.instance_id, user.principal);
       if ('Ok' in result) {
         showToast(`Claimed by you!`);
         setCoveredIds(prev => new Set(prev).add(eventKey));
@@ -225,7 +224,7 @@ export default function CoverageQueue() {
       try {
         const result = await actor.assign_host(
           event.series_id ? [Array.from(event.series_id)] : [],
-          event.start_utc ? [event.start_utc] : [],
+          event.occurrence_start,
           Array.from(event.instance_id as number[]),
           Principal.fromText(bulkHost)
         );
@@ -7,9 +7,9 @@ import { theme } from '../theme';
 
 // Minimal IDL just for redeem_invite_code
   const redeemIdlFactory = ({ IDL }: { IDL: any }) => {
-  const Role = IDL.Variant({ 'Admin': IDL.Null, 'User': IDL.Null });
+  const Role = IDL.Variant({ 'Admin': IDL.Null, 'User': IDL.Null, 'Coordinator': IDL.Null });
   const UserStatus = IDL.Variant({ 'Active': IDL.Null, 'Disabled': IDL.Null });
-  const OOOBlock = IDL.Record({ 'start_utc': IDL.Nat64, 'end_utc': IDL.Nat64 });
+  const OOOBlock = IDL.Record({ 'start_utc': IDL.Nat64, 'end_utc': IDL.Nat64, 'reason': IDL.Opt(IDL.Text) });
   const NotificationSettings = IDL.Record({
     'email_on_assigned': IDL.Bool, 'email_on_removed': IDL.Bool,
     'email_on_cancelled': IDL.Bool, 'email_on_time_changed': IDL.Bool,
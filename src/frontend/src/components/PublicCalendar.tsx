@@ -18,6 +18,8 @@ const publicIdlFactory = ({ IDL }: { IDL: any }) => {
     'notes': IDL.Text,
     'link': IDL.Opt(IDL.Text),
     'host_name': IDL.Opt(IDL.Text),
+    'is_assigned': IDL.Bool,
+    'rsvp_count': IDL.Nat32,
     'status': IDL.Variant({ 'Active': IDL.Null, 'Cancelled': IDL.Null }),
     'color': IDL.Opt(IDL.Text),
   });
@@ -28,6 +30,7 @@ const publicIdlFactory = ({ IDL }: { IDL: any }) => {
     'org_name': IDL.Opt(IDL.Text),
     'org_tagline': IDL.Opt(IDL.Text),
     'org_logo_url': IDL.Opt(IDL.Text),
+    'org_timezone': IDL.Opt(IDL.Text),
   });
   const ApiError = IDL.Variant({
     'Unauthorized': IDL.Null,
@@ -52,6 +55,8 @@ interface PublicEvent {
   notes: string;
   link: [string] | [];
   host_name: [string] | [];
+  is_assigned: boolean;
+  rsvp_count: number;
   status: { Active: null } | { Cancelled: null };
   color: [string] | [];
 }
@@ -72,6 +77,7 @@ export default function PublicCalendar() {
   const [showTzSelector, setShowTzSelector] = useState(false);
   const [tzSearch, setTzSearch] = useState('');
   const tzRef = useRef<HTMLDivElement>(null);
+  const tzChosenRef = useRef(false);
 
   const abbrev = getTimezoneAbbrev(timezone);
 
@@ -155,6 +161,8 @@ export default function PublicCalendar() {
           if (settings.org_name?.[0]) setOrgName(settings.org_name[0]);
           if (settings.org_tagline?.[0]) setOrgTagline(settings.org_tagline[0]);
           if (settings.org_logo_url?.[0]) setOrgLogoUrl(settings.org_logo_url[0]);
+          // Show the org's timezone until the viewer picks one themselves
+          if (settings.org_timezone?.[0] && !tzChosenRef.current) setTimezone(settings.org_timezone[0]);
         } catch { /* keep defaults */ }
       } catch (err) {
         console.error('Failed to fetch events:', err);
@@ -244,7 +252,7 @@ export default function PublicCalendar() {
   // Compute stats for the month
   const monthStats = useMemo(() => {
     const active = events.filter(e => 'Active' in e.status);
-    const needsHost = active.filter(e => e.host_name.length === 0);
+    const needsHost = active.filter(e => !e.is_assigned);
     return { total: active.length, needsHost: needsHost.length };
   }, [events]);
 
@@ -335,7 +343,7 @@ export default function PublicCalendar() {
                           ...styles.tzOption,
                           ...(t.tz === timezone ? styles.tzOptionActive : {}),
                         }}
-                        onClick={() => { setTimezone(t.tz); setShowTzSelector(false); setTzSearch(''); }}
+                        onClick={() => { tzChosenRef.current = true; setTimezone(t.tz); setShowTzSelector(false); setTzSearch(''); }}
                       >
                         <span>{t.label}</span>
                         <span style={styles.tzAbbrev}>{getTimezoneAbbrev(t.tz)}</span>
@@ -404,8 +412,8 @@ export default function PublicCalendar() {
                       </div>
                       <div style={styles.dayCellEvents}>
                         {dayEvents.filter(e => 'Active' in e.status).slice(0, 4).map((event, idx) => {
-                          const isNoHost = event.host_name.length === 0;
-                          const hostName = isNoHost ? 'No host' : event.host_name[0];
+                          const isNoHost = !event.is_assigned;
+                          const hostName = isNoHost ? 'No host' : event.host_name[0] ?? 'Hosted';
                           const color = isNoHost ? NO_HOST_COLOR : getSeriesColor(event.title, event.color?.[0]);
                           return (
                             <div key={idx} style={{
@@ -445,7 +453,7 @@ export default function PublicCalendar() {
                 const dateStr = new Date(Number(event.start_utc / BigInt(1_000_000))).toLocaleDateString('en-US', { weekday: 'short', month: 'short', day: 'numeric', timeZone: timezone });
                 const showDate = dateStr !== lastDate;
                 lastDate = dateStr;
-                const isNoHost = event.host_name.length === 0;
+                const isNoHost = !event.is_assigned;
                 const color = isNoHost ? NO_HOST_COLOR : getSeriesColor(event.title, event.color?.[0]);
                 return (
                   <div key={idx}>
@@ -455,7 +463,7 @@ export default function PublicCalendar() {
                       <div style={{ flex: 1 }}>
                         <div style={styles.agendaTitle}>{event.title}</div>
                         <div style={{ fontSize: '12px', color: isNoHost ? '#F87171' : theme.accent }}>
-                          {isNoHost ? 'No host' : event.host_name[0]}
+                          {isNoHost ? 'No host' : event.host_name[0] ?? 'Hosted'}
                         </div>
                       </div>
                     </div>
@@ -482,8 +490,8 @@ export default function PublicCalendar() {
             </div>
             <div style={pubModalStyles.detail}>
               <span style={pubModalStyles.label}>HOST</span>
-              <span style={{ ...pubModalStyles.value, color: selectedEvent.host_name.length === 0 ? '#F87171' : theme.textPrimary }}>
-                {selectedEvent.host_name.length > 0 ? selectedEvent.host_name[0] : 'No host assigned'}
+              <span style={{ ...pubModalStyles.value, color: selectedEvent.is_assigned ? theme.textPrimary : '#F87171' }}>
+                {selectedEvent.is_assigned ? selectedEvent.host_name[0] ?? 'Host assigned' : 'No host assigned'}
               </span>
             </div>
             {selectedEvent.notes && (
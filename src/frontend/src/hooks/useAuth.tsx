@@ -5,11 +5,12 @@ import { Principal } from '@dfinity/principal';
 
 // Backend canister interface (minimal for auth check)
 const idlFactory = ({ IDL }: { IDL: any }) => {
-  const Role = IDL.Variant({ 'Admin': IDL.Null, 'User': IDL.Null });
+  const Role = IDL.Variant({ 'Admin': IDL.Null, 'User': IDL.Null, 'Coordinator': IDL.Null });
   const UserStatus = IDL.Variant({ 'Active': IDL.Null, 'Disabled': IDL.Null });
   const OOOBlock = IDL.Record({
     'start_utc': IDL.Nat64,
     'end_utc': IDL.Nat64,
+    'reason': IDL.Opt(IDL.Text),
   });
   const NotificationSettings = IDL.Record({
     'email_on_assigned': IDL.Bool,
@@ -61,7 +62,7 @@ interface User {
   principal: Principal;
   name: string;
   email: string;
-  role: { Admin: null } | { User: null };
+  role: { Admin: null } | { User: null } | { Coordinator: null };
   status: { Active: null } | { Disabled: null };
   notification_settings: NotificationSettings;
 }
@@ -5,7 +5,7 @@ import { Principal } from '@dfinity/principal';
 
 // Full backend canister IDL
 const idlFactory = ({ IDL }: { IDL: any }) => {
-  const Role = IDL.Variant({ 'Admin': IDL.Null, 'User': IDL.Null });
+  const Role = IDL.Variant({ 'Admin': IDL.Null, 'User': IDL.Null, 'Coordinator': IDL.Null });
   const UserStatus = IDL.Variant({ 'Active': IDL.Null, 'Disabled': IDL.Null });
   const Frequency = IDL.Variant({ 'Weekly': IDL.Null, 'Biweekly': IDL.Null, 'Monthly': IDL.Null });
   const Weekday = IDL.Variant({ 
@@ -21,6 +21,7 @@ const idlFactory = ({ IDL }: { IDL: any }) => {
   const OOOBlock = IDL.Record({
     'start_utc': IDL.Nat64,
     'end_utc': IDL.Nat64,
+    'reason': IDL.Opt(IDL.Text),
   });
 
   const NotificationSettings = IDL.Record({
@@ -44,6 +45,7 @@ const idlFactory = ({ IDL }: { IDL: any }) => {
     'sessions_hosted_count': IDL.Nat32,
     'created_at': IDL.Nat64,
     'updated_at': IDL.Nat64,
+    'locale': IDL.Opt(IDL.Text),
   });
 
   const EventSeries = IDL.Record({
@@ -65,6 +67,8 @@ const idlFactory = ({ IDL }: { IDL: any }) => {
   });
 
 
+  const OverrideField = IDL.Variant({ 'Time': IDL.Null, 'Title': IDL.Null, 'Notes': IDL.Null, 'Host': IDL.Null });
+
   const EventInstance = IDL.Record({
     'instance_id': IDL.Vec(IDL.Nat8),
     'series_id': IDL.Opt(IDL.Vec(IDL.Nat8)),
@@ -77,6 +81,32 @@ const idlFactory = ({ IDL }: { IDL: any }) => {
     'status': EventStatus,
     'color': IDL.Opt(IDL.Text),
     'created_at': IDL.Nat64,
+    'occurrence_start': IDL.Opt(IDL.Nat64),
+    'assigned_by': IDL.Opt(IDL.Principal),
+    'assigned_at': IDL.Opt(IDL.Nat64),
+    'created_by': IDL.Opt(IDL.Principal),
+    'public': IDL.Bool,
+    'capacity': IDL.Opt(IDL.Nat32),
+    'rsvp_count': IDL.Nat32,
+    'overridden_fields': IDL.Vec(OverrideField),
+    'series_title': IDL.Opt(IDL.Text),
+  });
+
+  const NotificationType = IDL.Variant({
+    'HostAssigned': IDL.Null,
+    'HostRemoved': IDL.Null,
+    'InstanceTimeChanged': IDL.Null,
+    'InstanceCancelled': IDL.Null,
+    'UnclaimedReminder': IDL.Null,
+    'CoverageNeededSoon': IDL.Null,
+    'DailyDigest': IDL.Null,
+    'WeeklyDigest': IDL.Null,
+    'UserJoined': IDL.Null,
+  });
+  const NotificationTemplate = IDL.Record({
+    'notification_type': NotificationType,
+    'subject': IDL.Opt(IDL.Text),
+    'body': IDL.Opt(IDL.Text),
   });
 
   const GlobalSettings = IDL.Record({
@@ -86,6 +116,25 @@ const idlFactory = ({ IDL }: { IDL: any }) => {
     'org_name': IDL.Opt(IDL.Text),
     'org_tagline': IDL.Opt(IDL.Text),
     'org_logo_url': IDL.Opt(IDL.Text),
+    'max_concurrent_sessions': IDL.Opt(IDL.Nat32),
+    'enforce_concurrency_cap': IDL.Bool,
+    'validate_one_off_host': IDL.Bool,
+    'require_link': IDL.Bool,
+    'auto_cancel_past_unclaimed': IDL.Bool,
+    'auto_cancel_grace_hours': IDL.Nat32,
+    'business_hours': IDL.Opt(IDL.Tuple(IDL.Nat32, IDL.Nat32)),
+    'min_duration_minutes': IDL.Opt(IDL.Nat32),
+    'max_duration_minutes': IDL.Opt(IDL.Nat32),
+    'namespaced_instance_ids': IDL.Bool,
+    'notify_admins_on_join': IDL.Bool,
+    'org_default_notification_settings': IDL.Opt(NotificationSettings),
+    'week_starts_on': Weekday,
+    'version': IDL.Nat64,
+    'coverage_alert_threshold_pct': IDL.Opt(IDL.Float64),
+    'min_claim_lead_minutes': IDL.Opt(IDL.Nat32),
+    'always_email_critical_removals': IDL.Bool,
+    'org_timezone': IDL.Opt(IDL.Text),
+    'notification_templates': IDL.Vec(NotificationTemplate),
   });
 
   const CoverageStats = IDL.Record({
@@ -105,6 +154,14 @@ const idlFactory = ({ IDL }: { IDL: any }) => {
     'host_principal': IDL.Opt(IDL.Principal),
   });
 
+  const UpdateInstanceInput = IDL.Record({
+    'start_utc': IDL.Opt(IDL.Nat64),
+    'end_utc': IDL.Opt(IDL.Nat64),
+    'notes': IDL.Opt(IDL.Text),
+    'title': IDL.Opt(IDL.Text),
+    'link': IDL.Opt(IDL.Opt(IDL.Text)),
+  });
+
   const CreateSeriesInput = IDL.Record({
     'title': IDL.Text,
     'notes': IDL.Text,
@@ -130,6 +187,33 @@ const idlFactory = ({ IDL }: { IDL: any }) => {
     'default_host': IDL.Opt(IDL.Opt(IDL.Principal)),
   });
 
+  const GlobalSettingsPatch = IDL.Record({
+    'forward_window_months': IDL.Opt(IDL.Nat8),
+    'claims_paused': IDL.Opt(IDL.Bool),
+    'default_event_duration_minutes': IDL.Opt(IDL.Nat32),
+    'org_name': IDL.Opt(IDL.Opt(IDL.Text)),
+    'org_tagline': IDL.Opt(IDL.Opt(IDL.Text)),
+    'org_logo_url': IDL.Opt(IDL.Opt(IDL.Text)),
+    'max_concurrent_sessions': IDL.Opt(IDL.Opt(IDL.Nat32)),
+    'enforce_concurrency_cap': IDL.Opt(IDL.Bool),
+    'validate_one_off_host': IDL.Opt(IDL.Bool),
+    'require_link': IDL.Opt(IDL.Bool),
+    'auto_cancel_past_unclaimed': IDL.Opt(IDL.Bool),
+    'auto_cancel_grace_hours': IDL.Opt(IDL.Nat32),
+    'business_hours': IDL.Opt(IDL.Opt(IDL.Tuple(IDL.Nat32, IDL.Nat32))),
+    'min_duration_minutes': IDL.Opt(IDL.Opt(IDL.Nat32)),
+    'max_duration_minutes': IDL.Opt(IDL.Opt(IDL.Nat32)),
+    'namespaced_instance_ids': IDL.Opt(IDL.Bool),
+    'notify_admins_on_join': IDL.Opt(IDL.Bool),
+    'org_default_notification_settings': IDL.Opt(IDL.Opt(NotificationSettings)),
+    'week_starts_on': IDL.Opt(Weekday),
+    'coverage_alert_threshold_pct': IDL.Opt(IDL.Opt(IDL.Float64)),
+    'min_claim_lead_minutes': IDL.Opt(IDL.Opt(IDL.Nat32)),
+    'always_email_critical_removals': IDL.Opt(IDL.Bool),
+    'org_timezone': IDL.Opt(IDL.Opt(IDL.Text)),
+    'notification_templates': IDL.Opt(IDL.Vec(NotificationTemplate)),
+  });
+
   const ApiError = IDL.Variant({
     'Unauthorized': IDL.Null,
     'NotFound': IDL.Null,
@@ -146,12 +230,85 @@ const idlFactory = ({ IDL }: { IDL: any }) => {
     'name': IDL.Text,
     'role': Role,
     'status': UserStatus,
+    'out_of_office': IDL.Opt(IDL.Vec(OOOBlock)),
+  });
+  const EligibleHost = IDL.Record({
+    'principal': IDL.Principal,
+    'name': IDL.Text,
+    'role': Role,
+    'requires_override': IDL.Bool,
   });
+  const ClaimEligibility = IDL.Record({
+    'allowed': IDL.Bool,
+    'reason': IDL.Opt(IDL.Text),
+  });
+  const Result_ClaimEligibility = IDL.Variant({ 'Ok': ClaimEligibility, 'Err': ApiError });
+  const HostNote = IDL.Record({
+    'note': IDL.Text,
+    'updated_at': IDL.Nat64,
+  });
+  const Result_Opt_HostNote = IDL.Variant({ 'Ok': IDL.Opt(HostNote), 'Err': ApiError });
+  const VersionInfo = IDL.Record({
+    'schema_version': IDL.Nat32,
+    'package_version': IDL.Text,
+    'build': IDL.Opt(IDL.Text),
+  });
+  const AdjacentOccurrences = IDL.Record({
+    'previous': IDL.Opt(IDL.Nat64),
+    'next': IDL.Opt(IDL.Nat64),
+  });
+  const Result_AdjacentOccurrences = IDL.Variant({ 'Ok': AdjacentOccurrences, 'Err': ApiError });
+  const WhoamiStatus = IDL.Variant({
+    'Anonymous': IDL.Null,
+    'Unauthorized': IDL.Principal,
+    'Authorized': User,
+    'Disabled': IDL.Principal,
+  });
+  const Capability = IDL.Variant({ 'ManageUsers': IDL.Null, 'ManageSeries': IDL.Null });
+  const UserAccess = IDL.Record({
+    'principal': IDL.Principal,
+    'role': Role,
+    'status': UserStatus,
+    'capabilities': IDL.Vec(Capability),
+    'current_out_of_office': IDL.Opt(OOOBlock),
+    'can_claim_now': IDL.Bool,
+  });
+  const Result_UserAccess = IDL.Variant({ 'Ok': UserAccess, 'Err': ApiError });
+  const Result_Vec_EligibleHost = IDL.Variant({ 'Ok': IDL.Vec(EligibleHost), 'Err': ApiError });
   const Result_Vec_UserDirectoryEntry = IDL.Variant({ 'Ok': IDL.Vec(UserDirectoryEntry), 'Err': ApiError });
   const Result_Unit = IDL.Variant({ 'Ok': IDL.Null, 'Err': ApiError });
+  const Result_Nat32 = IDL.Variant({ 'Ok': IDL.Nat32, 'Err': ApiError });
   const Result_Vec_EventInstance = IDL.Variant({ 'Ok': IDL.Vec(EventInstance), 'Err': ApiError });
   const Result_EventInstance = IDL.Variant({ 'Ok': EventInstance, 'Err': ApiError });
+  const EventsPage = IDL.Record({
+    'events': IDL.Vec(EventInstance),
+    'next_cursor': IDL.Opt(IDL.Nat64),
+  });
+  const Result_EventsPage = IDL.Variant({ 'Ok': EventsPage, 'Err': ApiError });
+  const BulkCreateEventsResult = IDL.Record({
+    'created': IDL.Vec(EventInstance),
+    'failed': IDL.Vec(IDL.Record({ 'index': IDL.Nat32, 'error': ApiError })),
+    'warnings': IDL.Vec(IDL.Text),
+  });
+  const Result_BulkCreateEventsResult = IDL.Variant({ 'Ok': BulkCreateEventsResult, 'Err': ApiError });
+  const Result_Opt_EventInstance = IDL.Variant({ 'Ok': IDL.Opt(EventInstance), 'Err': ApiError });
   const Result_EventSeries = IDL.Variant({ 'Ok': EventSeries, 'Err': ApiError });
+  const Result_CreatedEvent = IDL.Variant({
+    'Ok': IDL.Record({ 'event': EventInstance, 'warnings': IDL.Vec(IDL.Text) }),
+    'Err': ApiError,
+  });
+  const Result_CreatedSeries = IDL.Variant({
+    'Ok': IDL.Record({ 'series': EventSeries, 'warnings': IDL.Vec(IDL.Text) }),
+    'Err': ApiError,
+  });
+  const Result_SeriesPauseResult = IDL.Variant({
+    'Ok': IDL.Record({
+      'series': EventSeries,
+      'affected_occurrences': IDL.Nat32,
+      'hosted_occurrences': IDL.Nat32,
+    }),
+    'Err': ApiError,
+  });
   const Result_Vec_EventSeries = IDL.Variant({ 'Ok': IDL.Vec(EventSeries), 'Err': ApiError });
   const Result_GlobalSettings = IDL.Variant({ 'Ok': GlobalSettings, 'Err': ApiError });
   const Result_String = IDL.Variant({ 'Ok': IDL.Text, 'Err': ApiError });
@@ -168,6 +325,7 @@ const idlFactory = ({ IDL }: { IDL: any }) => {
     'redeemed_at': IDL.Opt(IDL.Nat64),
     'user_placeholder_principal': IDL.Opt(IDL.Principal),
   });
+  const InviteCodeStrength = IDL.Variant({ 'Standard': IDL.Null, 'Strong': IDL.Null });
   const Result_InviteCode = IDL.Variant({ 'Ok': InviteCode, 'Err': ApiError });
   const Result_Vec_InviteCode = IDL.Variant({ 'Ok': IDL.Vec(InviteCode), 'Err': ApiError });
 
@@ -175,35 +333,54 @@ const idlFactory = ({ IDL }: { IDL: any }) => {
     // Auth / User
     'get_current_user': IDL.Func([], [Result_User], ['query']),
     'whoami': IDL.Func([], [IDL.Principal], ['query']),
+    'whoami_status': IDL.Func([], [WhoamiStatus], ['query']),
     'update_notification_settings': IDL.Func([NotificationSettings], [Result_Unit], []),
+    'set_my_locale': IDL.Func([IDL.Opt(IDL.Text)], [Result_Unit], []),
     'set_out_of_office': IDL.Func([IDL.Vec(OOOBlock)], [Result_Unit], []),
 
     // Admin - Users  
     'list_users': IDL.Func([], [Result_Vec_User], ['query']),
     'list_user_directory': IDL.Func([], [Result_Vec_UserDirectoryEntry], ['query']),
+    'list_eligible_hosts': IDL.Func([IDL.Opt(IDL.Vec(IDL.Nat8)), IDL.Opt(IDL.Nat64), IDL.Vec(IDL.Nat8)], [Result_Vec_EligibleHost], ['query']),
+    'list_unavailable_hosts': IDL.Func([IDL.Nat64, IDL.Nat64], [Result_Vec_UserDirectoryEntry], ['query']),
     'authorize_user': IDL.Func([IDL.Principal, IDL.Text, IDL.Text, Role], [Result_User], []),
     'disable_user': IDL.Func([IDL.Principal], [Result_Unit], []),
     'enable_user': IDL.Func([IDL.Principal], [Result_Unit], []),
     'update_user': IDL.Func([IDL.Principal, IDL.Text, IDL.Text, Role], [Result_User], []),
     'delete_user': IDL.Func([IDL.Principal], [Result_Unit], []),
+    'describe_user_access': IDL.Func([IDL.Principal], [Result_UserAccess], ['query']),
 
 
     // Events - Authenticated
     'list_events': IDL.Func([IDL.Nat64, IDL.Nat64], [Result_Vec_EventInstance], ['query']),
     'list_unclaimed_events': IDL.Func([], [Result_Vec_EventInstance], ['query']),
-    'create_one_off_event': IDL.Func([CreateEventInput], [Result_EventInstance], []),
+    'list_events_page': IDL.Func([IDL.Opt(IDL.Nat64), IDL.Nat32], [Result_EventsPage], ['query']),
+    'list_events_for_host': IDL.Func([IDL.Principal, IDL.Nat64, IDL.Nat64], [Result_Vec_EventInstance], ['query']),
+    'next_unclaimed_in_series': IDL.Func([IDL.Vec(IDL.Nat8)], [Result_Opt_EventInstance], ['query']),
+    'adjacent_occurrences': IDL.Func([IDL.Vec(IDL.Nat8), IDL.Nat64], [Result_AdjacentOccurrences], ['query']),
+    'create_one_off_event': IDL.Func([CreateEventInput, IDL.Bool], [Result_CreatedEvent], []),
+    'create_one_off_events': IDL.Func([IDL.Vec(CreateEventInput)], [Result_BulkCreateEventsResult], []),
+    'update_one_off_event': IDL.Func([IDL.Vec(IDL.Nat8), UpdateInstanceInput, IDL.Bool], [Result_EventInstance], []),
+    'rsvp': IDL.Func([IDL.Vec(IDL.Nat8)], [Result_EventInstance], []),
+    'cancel_rsvp': IDL.Func([IDL.Vec(IDL.Nat8)], [Result_EventInstance], []),
+    'set_host_note': IDL.Func([IDL.Vec(IDL.Nat8), IDL.Opt(IDL.Text)], [Result_Unit], []),
+    'get_host_note': IDL.Func([IDL.Vec(IDL.Nat8), IDL.Opt(IDL.Principal)], [Result_Opt_HostNote], ['query']),
 
     // Event Series (Admin)
-    'create_event_series': IDL.Func([CreateSeriesInput], [Result_EventSeries], []),
+    'create_event_series': IDL.Func([CreateSeriesInput, IDL.Bool], [Result_CreatedSeries], []),
     'update_event_series': IDL.Func([IDL.Vec(IDL.Nat8), UpdateSeriesInput], [Result_EventSeries], []),
+    'end_series_at': IDL.Func([IDL.Vec(IDL.Nat8), IDL.Nat64], [Result_Nat32], []),
     'delete_event_series': IDL.Func([IDL.Vec(IDL.Nat8)], [Result_Unit], []),
     'list_event_series': IDL.Func([], [Result_Vec_EventSeries], ['query']),
+    'list_deleted_event_series': IDL.Func([], [Result_Vec_EventSeries], ['query']),
+    'restore_event_series': IDL.Func([IDL.Vec(IDL.Nat8)], [Result_EventSeries], []),
+    'purge_event_series': IDL.Func([IDL.Vec(IDL.Nat8)], [Result_Unit], []),
 
     // Series Pause/Resume
-    'toggle_series_pause': IDL.Func([IDL.Vec(IDL.Nat8)], [Result_EventSeries], []),
+    'toggle_series_pause': IDL.Func([IDL.Vec(IDL.Nat8), IDL.Opt(IDL.Bool)], [Result_SeriesPauseResult], []),
 
     // CSV Export
-    'export_events_csv': IDL.Func([IDL.Nat64, IDL.Nat64], [Result_String], ['query']),
+    'export_events_csv': IDL.Func([IDL.Nat64, IDL.Nat64, IDL.Int32], [Result_String], ['query']),
 
     // Coverage Queue
     'assign_host': IDL.Func(
@@ -216,18 +393,25 @@ const idlFactory = ({ IDL }: { IDL: any }) => {
       [Result_EventInstance], 
       []
     ),
+    'can_i_claim': IDL.Func(
+      [IDL.Opt(IDL.Vec(IDL.Nat8)), IDL.Opt(IDL.Nat64), IDL.Vec(IDL.Nat8)],
+      [Result_ClaimEligibility],
+      ['query']
+    ),
 
     // Admin - System
-    'update_global_settings': IDL.Func([GlobalSettings], [Result_Unit], []),
+    'update_global_settings': IDL.Func([GlobalSettings, IDL.Nat64], [Result_Unit], []),
+    'patch_global_settings': IDL.Func([GlobalSettingsPatch], [Result_Unit], []),
     'get_global_settings': IDL.Func([], [Result_GlobalSettings], ['query']),
     'get_org_settings': IDL.Func([], [GlobalSettings], ['query']),
+    'get_version': IDL.Func([], [VersionInfo], ['query']),
     'get_coverage_history': IDL.Func([IDL.Nat8], [Result_Vec_CoverageStats], ['query']),
 
     // ICS
     'get_event_ics': IDL.Func([IDL.Vec(IDL.Nat8)], [Result_String], ['query']),
 
     // Invite Codes
-    'generate_invite_code': IDL.Func([Role], [Result_InviteCode], []),
+    'generate_invite_code': IDL.Func([Role, IDL.Opt(IDL.Nat32), IDL.Opt(IDL.Nat32), IDL.Opt(InviteCodeStrength)], [Result_InviteCode], []),
     'generate_personal_invite_code': IDL.Func([IDL.Principal], [Result_InviteCode], []),
     'redeem_invite_code': IDL.Func([IDL.Text, IDL.Text, IDL.Text], [Result_User], []),
     'list_invite_codes': IDL.Func([], [Result_Vec_InviteCode], ['query']),
@@ -240,7 +424,7 @@ export interface User {
   principal: Principal;
   name: string;
   email: string;
-  role: { Admin: null } | { User: null };
+  role: { Admin: null } | { User: null } | { Coordinator: null };
   status: { Active: null } | { Disabled: null };
   out_of_office: OOOBlock[];
   notification_settings: NotificationSettings;
@@ -248,18 +432,91 @@ export interface User {
   sessions_hosted_count: number;
   created_at: bigint;
   updated_at: bigint;
+  locale: [string] | [];
 }
 
 export interface OOOBlock {
   start_utc: bigint;
   end_utc: bigint;
+  reason: [string] | [];
 }
 
 export interface UserDirectoryEntry {
   principal: Principal;
   name: string;
-  role: { Admin: null } | { User: null };
+  role: { Admin: null } | { User: null } | { Coordinator: null };
   status: { Active: null } | { Disabled: null };
+  out_of_office: [OOOBlock[]] | [];
+}
+
+export interface EligibleHost {
+  principal: Principal;
+  name: string;
+  role: { Admin: null } | { User: null } | { Coordinator: null };
+  requires_override: boolean;
+}
+
+export interface ClaimEligibility {
+  allowed: boolean;
+  reason: [string] | [];
+}
+
+export interface HostNote {
+  note: string;
+  updated_at: bigint;
+}
+
+export interface VersionInfo {
+  schema_version: number;
+  package_version: string;
+  build: [string] | [];
+}
+
+export interface AdjacentOccurrences {
+  previous: [bigint] | [];
+  next: [bigint] | [];
+}
+
+export type WhoamiStatus =
+  | { Anonymous: null }
+  | { Unauthorized: Principal }
+  | { Authorized: User }
+  | { Disabled: Principal };
+
+export interface GlobalSettingsPatch {
+  forward_window_months: [number] | [];
+  claims_paused: [boolean] | [];
+  default_event_duration_minutes: [number] | [];
+  org_name: [[] | [string]] | [];
+  org_tagline: [[] | [string]] | [];
+  org_logo_url: [[] | [string]] | [];
+  max_concurrent_sessions: [[] | [number]] | [];
+  enforce_concurrency_cap: [boolean] | [];
+  validate_one_off_host: [boolean] | [];
+  require_link: [boolean] | [];
+  auto_cancel_past_unclaimed: [boolean] | [];
+  auto_cancel_grace_hours: [number] | [];
+  business_hours: [[] | [[number, number]]] | [];
+  min_duration_minutes: [[] | [number]] | [];
+  max_duration_minutes: [[] | [number]] | [];
+  namespaced_instance_ids: [boolean] | [];
+  notify_admins_on_join: [boolean] | [];
+  org_default_notification_settings: [[] | [NotificationSettings]] | [];
+  week_starts_on: [{ Mon: null } | { Tue: null } | { Wed: null } | { Thu: null } | { Fri: null } | { Sat: null } | { Sun: null }] | [];
+  coverage_alert_threshold_pct: [[] | [number]] | [];
+  min_claim_lead_minutes: [[] | [number]] | [];
+  always_email_critical_removals: [boolean] | [];
+  org_timezone: [[] | [string]] | [];
+  notification_templates: [NotificationTemplate[]] | [];
+}
+
+export interface UserAccess {
+  principal: Principal;
+  role: { Admin: null } | { User: null } | { Coordinator: null };
+  status: { Active: null } | { Disabled: null };
+  capabilities: ({ ManageUsers: null } | { ManageSeries: null })[];
+  current_out_of_office: [OOOBlock] | [];
+  can_claim_now: boolean;
 }
 
 export interface NotificationSettings {
@@ -283,6 +540,20 @@ export interface EventInstance {
   status: { Active: null } | { Cancelled: null };
   color: [string] | [];
   created_at: bigint;
+  occurrence_start: [bigint] | [];
+  assigned_by: [Principal] | [];
+  assigned_at: [bigint] | [];
+  created_by: [Principal] | [];
+  public: boolean;
+  capacity: number[];
+  rsvp_count: number;
+  overridden_fields: ({ Time: null } | { Title: null } | { Notes: null } | { Host: null })[];
+  series_title: [string] | [];
+}
+
+export interface EventsPage {
+  events: EventInstance[];
+  next_cursor: [bigint] | [];
 }
 
 
@@ -304,6 +575,17 @@ export interface EventSeries {
   created_by: Principal;
 }
 
+export type NotificationType =
+  | { HostAssigned: null } | { HostRemoved: null } | { InstanceTimeChanged: null }
+  | { InstanceCancelled: null } | { UnclaimedReminder: null } | { CoverageNeededSoon: null }
+  | { DailyDigest: null } | { WeeklyDigest: null } | { UserJoined: null };
+
+export interface NotificationTemplate {
+  notification_type: NotificationType;
+  subject: [string] | [];
+  body: [string] | [];
+}
+
 export interface GlobalSettings {
   forward_window_months: number;
   claims_paused: boolean;
@@ -311,6 +593,25 @@ export interface GlobalSettings {
   org_name: string[];
   org_tagline: string[];
   org_logo_url: string[];
+  max_concurrent_sessions: number[];
+  enforce_concurrency_cap: boolean;
+  validate_one_off_host: boolean;
+  require_link: boolean;
+  auto_cancel_past_unclaimed: boolean;
+  auto_cancel_grace_hours: number;
+  business_hours: [[number, number]] | [];
+  min_duration_minutes: number[];
+  max_duration_minutes: number[];
+  namespaced_instance_ids: boolean;
+  notify_admins_on_join: boolean;
+  org_default_notification_settings: [NotificationSettings] | [];
+  week_starts_on: { Mon: null } | { Tue: null } | { Wed: null } | { Thu: null } | { Fri: null } | { Sat: null } | { Sun: null };
+  version: bigint;
+  coverage_alert_threshold_pct: [number] | [];
+  min_claim_lead_minutes: [number] | [];
+  always_email_critical_removals: boolean;
+  org_timezone: [string] | [];
+  notification_templates: NotificationTemplate[];
 }
 
 export interface CoverageStats {
@@ -323,7 +624,7 @@ export interface CoverageStats {
 
 export interface InviteCode {
   code: string;
-  role: { Admin: null } | { User: null };
+  role: { Admin: null } | { User: null } | { Coordinator: null };
   created_at: bigint;
   created_by: Principal;
   expires_at: bigint;